@@ -0,0 +1,26 @@
+use utils::serialize::i64_to_str;
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::app_checks::CheckStatus;
+
+// 应用的告警状态，用于判断是否需要发送通知
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_alerts")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub app_id: i64,
+  // 最近一次通知的状态
+  pub status: CheckStatus,
+  // 连续检查失败的次数
+  pub failures: i32,
+  #[sea_orm(nullable)]
+  pub notified_at: Option<DateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
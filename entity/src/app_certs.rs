@@ -0,0 +1,38 @@
+use utils::serialize::i64_to_str;
+
+use sea_orm::{entity::prelude::*, FromJsonQueryResult};
+use serde::{Deserialize, Serialize};
+
+// 应用 HTTPS 证书的最近一次检查结果
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_certs")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub app_id: i64,
+  #[sea_orm(nullable)]
+  pub issuer: Option<String>,
+  #[sea_orm(nullable)]
+  pub subject: Option<String>,
+  // 证书中的主机名列表
+  pub sans: SubjectAltNames,
+  #[sea_orm(nullable)]
+  pub not_before: Option<DateTime>,
+  #[sea_orm(nullable)]
+  pub expires_at: Option<DateTime>,
+  // 证书是否与应用 URL 中的主机名匹配
+  pub hostname_match: bool,
+  // 获取证书失败时的错误信息
+  #[sea_orm(nullable)]
+  pub error: Option<String>,
+  pub checked_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct SubjectAltNames(pub Vec<String>);
@@ -0,0 +1,41 @@
+use utils::serialize::i64_to_str;
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// 应用的健康检查记录
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_checks")]
+pub struct Model {
+  #[sea_orm(primary_key)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub id: i64,
+  #[serde(serialize_with = "i64_to_str")]
+  pub app_id: i64,
+  pub status: CheckStatus,
+  #[sea_orm(nullable)]
+  pub status_code: Option<i32>,
+  // 响应时间，单位毫秒
+  #[sea_orm(nullable)]
+  pub latency: Option<i32>,
+  #[sea_orm(nullable)]
+  pub message: Option<String>,
+  pub checked_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
+pub enum CheckStatus {
+  #[sea_orm(string_value = "Up")]
+  Up,
+  #[sea_orm(string_value = "Down")]
+  Down,
+  // 维护时间窗口内的检查，不计入可用率
+  #[sea_orm(string_value = "Maintenance")]
+  Maintenance,
+}
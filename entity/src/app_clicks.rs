@@ -0,0 +1,27 @@
+use utils::serialize::{i64_to_str, option_i64_to_str};
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// 用户打开应用的记录
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_clicks")]
+pub struct Model {
+  #[sea_orm(primary_key)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub id: i64,
+  #[serde(serialize_with = "i64_to_str")]
+  pub app_id: i64,
+  #[serde(serialize_with = "i64_to_str")]
+  pub user_id: i64,
+  // 打开应用时所在的面板
+  #[sea_orm(nullable)]
+  #[serde(serialize_with = "option_i64_to_str")]
+  pub dashboard_id: Option<i64>,
+  pub clicked_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,27 @@
+use utils::serialize::{i64_to_str, option_i64_to_str};
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// 用户对全站应用的个人设置
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_prefs")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub user_id: i64,
+  #[sea_orm(primary_key, auto_increment = false)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub app_id: i64,
+  pub hidden: bool,
+  #[sea_orm(nullable)]
+  pub index: Option<i32>,
+  #[sea_orm(nullable)]
+  #[serde(serialize_with = "option_i64_to_str")]
+  pub group_id: Option<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,20 @@
+use utils::serialize::i64_to_str;
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_tags")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub app_id: i64,
+  #[sea_orm(primary_key, auto_increment = false)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub tag_id: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,6 +1,9 @@
-use utils::serialize::i64_to_str;
+use utils::{
+  deserialize::vec_str_to_i64,
+  serialize::{i64_to_str, option_i64_to_str, vec_i64_to_str},
+};
 
-use sea_orm::entity::prelude::*;
+use sea_orm::{entity::prelude::*, FromJsonQueryResult};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
@@ -11,13 +14,48 @@ pub struct Model {
   pub id: i64,
   pub name: String,
   pub url: String,
+  // 内网地址，客户端在内网中时打开该地址，健康检查与图标获取也使用该地址，只返回给可以管理应用的用户
+  #[sea_orm(nullable)]
+  #[serde(skip)]
+  pub internal_url: Option<String>,
   #[sea_orm(nullable)]
   pub description: Option<String>,
   #[sea_orm(nullable)]
   pub icon: Option<String>,
+  // 短链接，通过 /go/{slug} 跳转到应用
+  #[sea_orm(nullable, indexed)]
+  pub slug: Option<String>,
   pub index: i32,
   #[serde(serialize_with = "i64_to_str")]
   pub owner_id: i64,
+  // 所属分组，为空时表示未分组
+  #[sea_orm(nullable)]
+  #[serde(serialize_with = "option_i64_to_str")]
+  pub group_id: Option<i64>,
+  // 所属面板，为空时属于用户的默认面板
+  #[sea_orm(nullable)]
+  #[serde(serialize_with = "option_i64_to_str")]
+  pub dashboard_id: Option<i64>,
+  // 管理员创建的全站应用，对所有用户可见
+  pub is_global: bool,
+  // 全站应用的可见范围，为空时对所有用户可见
+  #[sea_orm(nullable)]
+  pub audience: Option<Audience>,
+  // 应用的附加链接，如管理后台、接口文档等，按顺序显示
+  #[sea_orm(nullable)]
+  pub links: Option<AppLinks>,
+  // 应用的监控配置，为空时使用 HTTP 检查，请求头中可能包含凭据，只返回给可以管理应用的用户
+  #[sea_orm(nullable)]
+  #[serde(skip)]
+  pub monitor: Option<Monitor>,
+  // 推送监控的令牌，只在查询推送地址时返回
+  #[sea_orm(nullable, unique)]
+  #[serde(skip)]
+  pub push_token: Option<String>,
+  // 状态徽章的令牌，只在查询徽章地址时返回
+  #[sea_orm(nullable, unique)]
+  #[serde(skip)]
+  pub badge_token: Option<String>,
   pub created_at: DateTime,
   #[sea_orm(nullable)]
   pub deleted_at: Option<DateTime>,
@@ -27,3 +65,110 @@ pub struct Model {
 pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+  // 服务端访问应用时优先使用内网地址
+  pub fn server_url(&self) -> &str {
+    self.internal_url.as_deref().unwrap_or(&self.url)
+  }
+
+  // 按客户端所在的网络选择打开的地址
+  pub fn client_url(&self, internal: bool) -> &str {
+    match internal {
+      true => self.server_url(),
+      false => &self.url,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AudienceRole {
+  Admin,
+  User,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct Audience {
+  #[serde(default)]
+  pub roles: Vec<AudienceRole>,
+  #[serde(
+    default,
+    serialize_with = "vec_i64_to_str",
+    deserialize_with = "vec_str_to_i64"
+  )]
+  pub user_ids: Vec<i64>,
+}
+
+impl Audience {
+  // 角色与用户都未指定时，对所有用户可见
+  pub fn contains(&self, user_id: i64, is_admin: bool) -> bool {
+    if self.roles.is_empty() && self.user_ids.is_empty() {
+      return true;
+    }
+
+    let role = if is_admin {
+      AudienceRole::Admin
+    } else {
+      AudienceRole::User
+    };
+
+    self.roles.contains(&role) || self.user_ids.contains(&user_id)
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppLink {
+  pub name: String,
+  pub url: String,
+  pub icon: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct AppLinks(pub Vec<AppLink>);
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MonitorKind {
+  #[default]
+  Http,
+  Tcp,
+  Dns,
+  // 由应用主动推送心跳
+  Push,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MonitorHeader {
+  pub name: String,
+  pub value: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct Monitor {
+  #[serde(default)]
+  pub kind: MonitorKind,
+  // HTTP 请求方法，为空时使用 GET
+  pub method: Option<String>,
+  #[serde(default)]
+  pub headers: Vec<MonitorHeader>,
+  // 响应内容中需要匹配的关键字
+  pub keyword: Option<String>,
+  // 关键字按正则表达式匹配
+  #[serde(default)]
+  pub keyword_regex: bool,
+  // 关键字不存在时才视为正常
+  #[serde(default)]
+  pub keyword_absent: bool,
+  // 对 JSON 响应求值的 JSONPath 表达式
+  pub json_path: Option<String>,
+  // JSONPath 结果或 DNS 解析地址的期望值，为空时只要求有结果
+  pub expected: Option<String>,
+  // TCP 与 DNS 检查的主机，为空时使用应用 URL 中的主机
+  pub host: Option<String>,
+  // TCP 检查的端口，为空时使用应用 URL 中的端口
+  pub port: Option<u16>,
+  // 推送监控的心跳间隔，单位秒，超过该时间未收到心跳时视为异常
+  pub push_interval: Option<u64>,
+  // 连续失败多少次后发送通知，为空时使用全局配置
+  pub notify_after: Option<u32>,
+}
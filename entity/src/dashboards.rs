@@ -0,0 +1,45 @@
+use utils::serialize::i64_to_str;
+
+use sea_orm::{entity::prelude::*, FromJsonQueryResult};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "dashboards")]
+pub struct Model {
+  #[sea_orm(primary_key)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub id: i64,
+  pub name: String,
+  pub index: i32,
+  // 每个用户有且只有一个默认面板
+  pub is_default: bool,
+  #[sea_orm(nullable)]
+  pub layout: Option<Layout>,
+  // 面板背景，为空时使用用户设置中的背景
+  #[sea_orm(nullable)]
+  pub background: Option<Background>,
+  #[serde(serialize_with = "i64_to_str")]
+  pub owner_id: i64,
+  pub created_at: DateTime,
+  #[sea_orm(nullable)]
+  pub deleted_at: Option<DateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct Layout {
+  // 每行显示的应用数量
+  pub columns: Option<i8>,
+  // 紧凑模式，不显示应用描述
+  pub compact: Option<bool>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct Background {
+  pub bg_image: Option<String>,
+  pub bg_blur: Option<i8>,
+}
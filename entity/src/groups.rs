@@ -0,0 +1,32 @@
+use utils::serialize::{i64_to_str, option_i64_to_str};
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "groups")]
+pub struct Model {
+  #[sea_orm(primary_key)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub id: i64,
+  pub name: String,
+  #[sea_orm(nullable)]
+  pub icon: Option<String>,
+  // 分组是否折叠
+  pub collapsed: bool,
+  pub index: i32,
+  #[serde(serialize_with = "i64_to_str")]
+  pub owner_id: i64,
+  // 所属面板，为空时属于用户的默认面板
+  #[sea_orm(nullable)]
+  #[serde(serialize_with = "option_i64_to_str")]
+  pub dashboard_id: Option<i64>,
+  pub created_at: DateTime,
+  #[sea_orm(nullable)]
+  pub deleted_at: Option<DateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -2,5 +2,19 @@
 
 pub mod prelude;
 
+pub mod app_alerts;
+pub mod app_certs;
+pub mod app_checks;
+pub mod app_clicks;
+pub mod app_prefs;
+pub mod app_tags;
 pub mod apps;
+pub mod dashboards;
+pub mod groups;
+pub mod maintenances;
+pub mod notifications;
+pub mod shares;
+pub mod status_components;
+pub mod status_incidents;
+pub mod tags;
 pub mod users;
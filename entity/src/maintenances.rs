@@ -0,0 +1,49 @@
+use utils::{
+  deserialize::vec_str_to_i64,
+  serialize::{i64_to_str, vec_i64_to_str},
+};
+
+use sea_orm::{entity::prelude::*, FromJsonQueryResult};
+use serde::{Deserialize, Serialize};
+
+// 维护时间窗口，窗口内的应用暂停告警且不计入可用率
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "maintenances")]
+pub struct Model {
+  #[sea_orm(primary_key)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub id: i64,
+  pub title: String,
+  #[sea_orm(nullable)]
+  pub description: Option<String>,
+  // 维护的应用
+  pub app_ids: TargetIds,
+  // 维护的分组，分组内的全部应用都处于维护中
+  pub group_ids: TargetIds,
+  // 一次性维护的开始时间，周期维护从该时间开始生效
+  pub starts_at: DateTime,
+  // 一次性维护的结束时间，周期维护在该时间后失效，为空时一直有效
+  #[sea_orm(nullable)]
+  pub ends_at: Option<DateTime>,
+  // 周期维护的 cron 表达式，为空时为一次性维护
+  #[sea_orm(nullable)]
+  pub cron: Option<String>,
+  // 周期维护每次持续的时间，单位分钟
+  #[sea_orm(nullable)]
+  pub duration: Option<i32>,
+  pub enabled: bool,
+  #[serde(serialize_with = "i64_to_str")]
+  pub owner_id: i64,
+  pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct TargetIds(
+  #[serde(serialize_with = "vec_i64_to_str", deserialize_with = "vec_str_to_i64")] pub Vec<i64>,
+);
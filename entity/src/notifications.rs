@@ -0,0 +1,83 @@
+use utils::serialize::i64_to_str;
+
+use sea_orm::{entity::prelude::*, FromJsonQueryResult};
+use serde::{Deserialize, Serialize};
+
+// 应用状态变化时的通知渠道
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "notifications")]
+pub struct Model {
+  #[sea_orm(primary_key)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub id: i64,
+  pub name: String,
+  pub kind: NotificationKind,
+  pub config: NotificationConfig,
+  // 通知标题模板，为空时使用默认模板
+  #[sea_orm(nullable)]
+  pub title_template: Option<String>,
+  // 通知内容模板，为空时使用默认模板
+  #[sea_orm(nullable)]
+  pub body_template: Option<String>,
+  pub enabled: bool,
+  // 管理员创建的全站通知渠道，接收所有应用的状态变化
+  pub is_global: bool,
+  #[serde(serialize_with = "i64_to_str")]
+  pub owner_id: i64,
+  pub created_at: DateTime,
+  #[sea_orm(nullable)]
+  pub deleted_at: Option<DateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
+pub enum NotificationKind {
+  // 通用 JSON Webhook
+  #[sea_orm(string_value = "Webhook")]
+  Webhook,
+  #[sea_orm(string_value = "Ntfy")]
+  Ntfy,
+  #[sea_orm(string_value = "Gotify")]
+  Gotify,
+  #[sea_orm(string_value = "Discord")]
+  Discord,
+  #[sea_orm(string_value = "Slack")]
+  Slack,
+  // SMTP 邮件
+  #[sea_orm(string_value = "Smtp")]
+  Smtp,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SmtpSecurity {
+  None,
+  #[default]
+  StartTls,
+  Tls,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct NotificationConfig {
+  // Webhook 地址，或 ntfy、Gotify 的服务器地址
+  pub url: Option<String>,
+  // ntfy 的访问令牌或 Gotify 的应用令牌
+  pub token: Option<String>,
+  // ntfy 的主题
+  pub topic: Option<String>,
+  // ntfy 与 Gotify 的消息优先级
+  pub priority: Option<i8>,
+  pub smtp_host: Option<String>,
+  pub smtp_port: Option<u16>,
+  #[serde(default)]
+  pub smtp_security: SmtpSecurity,
+  pub smtp_username: Option<String>,
+  pub smtp_password: Option<String>,
+  pub smtp_from: Option<String>,
+  #[serde(default)]
+  pub smtp_to: Vec<String>,
+}
@@ -1,4 +1,18 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.12
 
+pub use super::app_alerts::Entity as AppAlerts;
+pub use super::app_certs::Entity as AppCerts;
+pub use super::app_checks::Entity as AppChecks;
+pub use super::app_clicks::Entity as AppClicks;
+pub use super::app_prefs::Entity as AppPrefs;
+pub use super::app_tags::Entity as AppTags;
 pub use super::apps::Entity as Apps;
+pub use super::dashboards::Entity as Dashboards;
+pub use super::groups::Entity as Groups;
+pub use super::maintenances::Entity as Maintenances;
+pub use super::notifications::Entity as Notifications;
+pub use super::shares::Entity as Shares;
+pub use super::status_components::Entity as StatusComponents;
+pub use super::status_incidents::Entity as StatusIncidents;
+pub use super::tags::Entity as Tags;
 pub use super::users::Entity as Users;
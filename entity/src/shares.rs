@@ -0,0 +1,54 @@
+use utils::serialize::{i64_to_str, option_i64_to_str};
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "shares")]
+pub struct Model {
+  #[sea_orm(primary_key)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub id: i64,
+  #[serde(serialize_with = "i64_to_str")]
+  pub owner_id: i64,
+  // 被分享的用户
+  #[serde(serialize_with = "i64_to_str")]
+  pub grantee_id: i64,
+  // 分享的分组，为空时分享整个面板
+  #[sea_orm(nullable)]
+  #[serde(serialize_with = "option_i64_to_str")]
+  pub group_id: Option<i64>,
+  // 分享的面板，分享分组时为空
+  #[sea_orm(nullable)]
+  #[serde(serialize_with = "option_i64_to_str")]
+  pub dashboard_id: Option<i64>,
+  pub permission: SharePermission,
+  pub created_at: DateTime,
+  #[sea_orm(nullable)]
+  pub deleted_at: Option<DateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
+pub enum SharePermission {
+  // 只读
+  #[sea_orm(string_value = "Read")]
+  Read,
+  // 可编辑
+  #[sea_orm(string_value = "Edit")]
+  Edit,
+}
+
+impl SharePermission {
+  pub fn allows(&self, required: SharePermission) -> bool {
+    match required {
+      SharePermission::Read => true,
+      SharePermission::Edit => *self == SharePermission::Edit,
+    }
+  }
+}
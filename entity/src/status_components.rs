@@ -0,0 +1,33 @@
+use utils::{
+  deserialize::vec_str_to_i64,
+  serialize::{i64_to_str, vec_i64_to_str},
+};
+
+use sea_orm::{entity::prelude::*, FromJsonQueryResult};
+use serde::{Deserialize, Serialize};
+
+// 公开状态页中的组件，组件由若干应用组成
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "status_components")]
+pub struct Model {
+  #[sea_orm(primary_key)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub id: i64,
+  pub name: String,
+  #[sea_orm(nullable)]
+  pub description: Option<String>,
+  pub app_ids: ComponentApps,
+  pub index: i32,
+  pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct ComponentApps(
+  #[serde(serialize_with = "vec_i64_to_str", deserialize_with = "vec_str_to_i64")] pub Vec<i64>,
+);
@@ -0,0 +1,48 @@
+use utils::serialize::{i64_to_str, option_i64_to_str};
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// 管理员在公开状态页中发布的事件说明
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "status_incidents")]
+pub struct Model {
+  #[sea_orm(primary_key)]
+  #[serde(serialize_with = "i64_to_str")]
+  pub id: i64,
+  pub title: String,
+  pub content: String,
+  pub status: IncidentStatus,
+  // 受影响的组件，为空时表示影响整个站点
+  #[sea_orm(nullable)]
+  #[serde(serialize_with = "option_i64_to_str")]
+  pub component_id: Option<i64>,
+  pub started_at: DateTime,
+  #[sea_orm(nullable)]
+  pub resolved_at: Option<DateTime>,
+  #[serde(serialize_with = "i64_to_str")]
+  pub owner_id: i64,
+  pub created_at: DateTime,
+  pub updated_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
+pub enum IncidentStatus {
+  // 调查中
+  #[sea_orm(string_value = "Investigating")]
+  Investigating,
+  // 已确认原因
+  #[sea_orm(string_value = "Identified")]
+  Identified,
+  // 已修复，观察中
+  #[sea_orm(string_value = "Monitoring")]
+  Monitoring,
+  #[sea_orm(string_value = "Resolved")]
+  Resolved,
+}
@@ -38,4 +38,7 @@ pub struct Setting {
   pub theme: SettingTheme,
   pub bg_image: Option<String>,
   pub bg_blur: Option<i8>,
+  // 关闭后不再记录打开应用的次数
+  #[serde(default)]
+  pub disable_tracking: bool,
 }
@@ -1,12 +1,50 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20230301_000000_create_table;
+mod m20261019_000000_create_groups_table;
+mod m20261019_000001_create_tags_table;
+mod m20261019_000002_create_global_apps;
+mod m20261019_000003_create_shares_table;
+mod m20261019_000004_create_dashboards_table;
+mod m20261019_000005_create_app_checks_table;
+mod m20261019_000006_add_app_monitor;
+mod m20261019_000007_create_app_certs_table;
+mod m20261019_000008_add_app_push_token;
+mod m20261019_000009_create_notifications_table;
+mod m20261019_000010_create_status_page_tables;
+mod m20261019_000011_create_maintenances_table;
+mod m20261019_000012_add_app_badge_token;
+mod m20261019_000013_create_app_clicks_table;
+mod m20261019_000014_add_app_slug;
+mod m20261019_000015_create_app_search_index;
+mod m20261019_000016_add_app_links;
+mod m20261019_000017_add_app_internal_url;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
   fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-    vec![Box::new(m20230301_000000_create_table::Migration)]
+    vec![
+      Box::new(m20230301_000000_create_table::Migration),
+      Box::new(m20261019_000000_create_groups_table::Migration),
+      Box::new(m20261019_000001_create_tags_table::Migration),
+      Box::new(m20261019_000002_create_global_apps::Migration),
+      Box::new(m20261019_000003_create_shares_table::Migration),
+      Box::new(m20261019_000004_create_dashboards_table::Migration),
+      Box::new(m20261019_000005_create_app_checks_table::Migration),
+      Box::new(m20261019_000006_add_app_monitor::Migration),
+      Box::new(m20261019_000007_create_app_certs_table::Migration),
+      Box::new(m20261019_000008_add_app_push_token::Migration),
+      Box::new(m20261019_000009_create_notifications_table::Migration),
+      Box::new(m20261019_000010_create_status_page_tables::Migration),
+      Box::new(m20261019_000011_create_maintenances_table::Migration),
+      Box::new(m20261019_000012_add_app_badge_token::Migration),
+      Box::new(m20261019_000013_create_app_clicks_table::Migration),
+      Box::new(m20261019_000014_add_app_slug::Migration),
+      Box::new(m20261019_000015_create_app_search_index::Migration),
+      Box::new(m20261019_000016_add_app_links::Migration),
+      Box::new(m20261019_000017_add_app_internal_url::Migration),
+    ]
   }
 }
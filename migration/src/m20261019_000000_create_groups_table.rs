@@ -0,0 +1,89 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Groups::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(Groups::Id)
+              .big_integer()
+              .auto_increment()
+              .primary_key()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(Groups::Name)
+              .string()
+              .string_len(255)
+              .not_null(),
+          )
+          .col(ColumnDef::new(Groups::Icon).string().string_len(255).null())
+          .col(
+            ColumnDef::new(Groups::Collapsed)
+              .boolean()
+              .not_null()
+              .default(false),
+          )
+          .col(ColumnDef::new(Groups::Index).integer().not_null())
+          .col(ColumnDef::new(Groups::OwnerId).big_integer().not_null())
+          .col(
+            ColumnDef::new(Groups::CreatedAt)
+              .date_time()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .col(ColumnDef::new(Groups::DeletedAt).date_time().null())
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Apps::Table)
+          .add_column(ColumnDef::new(Apps::GroupId).big_integer().null())
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Apps::Table)
+          .drop_column(Apps::GroupId)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .drop_table(Table::drop().table(Groups::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum Groups {
+  Table,
+  Id,
+  Name,
+  Icon,
+  Collapsed,
+  Index,
+  OwnerId,
+  CreatedAt,
+  DeletedAt,
+}
+
+#[derive(Iden)]
+enum Apps {
+  Table,
+  GroupId,
+}
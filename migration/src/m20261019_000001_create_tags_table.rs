@@ -0,0 +1,74 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Tags::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(Tags::Id)
+              .big_integer()
+              .auto_increment()
+              .primary_key()
+              .not_null(),
+          )
+          .col(ColumnDef::new(Tags::Name).string().string_len(255).not_null())
+          .col(ColumnDef::new(Tags::Color).string().string_len(255).null())
+          .col(ColumnDef::new(Tags::OwnerId).big_integer().not_null())
+          .col(
+            ColumnDef::new(Tags::CreatedAt)
+              .date_time()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .col(ColumnDef::new(Tags::DeletedAt).date_time().null())
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(AppTags::Table)
+          .if_not_exists()
+          .col(ColumnDef::new(AppTags::AppId).big_integer().not_null())
+          .col(ColumnDef::new(AppTags::TagId).big_integer().not_null())
+          .primary_key(Index::create().col(AppTags::AppId).col(AppTags::TagId))
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(AppTags::Table).to_owned())
+      .await?;
+    manager
+      .drop_table(Table::drop().table(Tags::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum Tags {
+  Table,
+  Id,
+  Name,
+  Color,
+  OwnerId,
+  CreatedAt,
+  DeletedAt,
+}
+
+#[derive(Iden)]
+enum AppTags {
+  Table,
+  AppId,
+  TagId,
+}
@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Apps::Table)
+          .add_column(
+            ColumnDef::new(Apps::IsGlobal)
+              .boolean()
+              .not_null()
+              .default(false),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Apps::Table)
+          .add_column(ColumnDef::new(Apps::Audience).json().null())
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(AppPrefs::Table)
+          .if_not_exists()
+          .col(ColumnDef::new(AppPrefs::UserId).big_integer().not_null())
+          .col(ColumnDef::new(AppPrefs::AppId).big_integer().not_null())
+          .col(
+            ColumnDef::new(AppPrefs::Hidden)
+              .boolean()
+              .not_null()
+              .default(false),
+          )
+          .col(ColumnDef::new(AppPrefs::Index).integer().null())
+          .col(ColumnDef::new(AppPrefs::GroupId).big_integer().null())
+          .primary_key(Index::create().col(AppPrefs::UserId).col(AppPrefs::AppId))
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(AppPrefs::Table).to_owned())
+      .await?;
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Apps::Table)
+          .drop_column(Apps::Audience)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Apps::Table)
+          .drop_column(Apps::IsGlobal)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum Apps {
+  Table,
+  IsGlobal,
+  Audience,
+}
+
+#[derive(Iden)]
+enum AppPrefs {
+  Table,
+  UserId,
+  AppId,
+  Hidden,
+  Index,
+  GroupId,
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Shares::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(Shares::Id)
+              .big_integer()
+              .auto_increment()
+              .primary_key()
+              .not_null(),
+          )
+          .col(ColumnDef::new(Shares::OwnerId).big_integer().not_null())
+          .col(ColumnDef::new(Shares::GranteeId).big_integer().not_null())
+          .col(ColumnDef::new(Shares::GroupId).big_integer().null())
+          .col(
+            ColumnDef::new(Shares::Permission)
+              .string()
+              .string_len(20)
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(Shares::CreatedAt)
+              .date_time()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .col(ColumnDef::new(Shares::DeletedAt).date_time().null())
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(Shares::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum Shares {
+  Table,
+  Id,
+  OwnerId,
+  GranteeId,
+  GroupId,
+  Permission,
+  CreatedAt,
+  DeletedAt,
+}
@@ -0,0 +1,254 @@
+use sea_orm_migration::{prelude::*, sea_orm::DatabaseBackend};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Dashboards::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(Dashboards::Id)
+              .big_integer()
+              .auto_increment()
+              .primary_key()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(Dashboards::Name)
+              .string()
+              .string_len(255)
+              .not_null(),
+          )
+          .col(ColumnDef::new(Dashboards::Index).integer().not_null())
+          .col(
+            ColumnDef::new(Dashboards::IsDefault)
+              .boolean()
+              .not_null()
+              .default(false),
+          )
+          .col(ColumnDef::new(Dashboards::Layout).json().null())
+          .col(ColumnDef::new(Dashboards::Background).json().null())
+          .col(ColumnDef::new(Dashboards::OwnerId).big_integer().not_null())
+          .col(
+            ColumnDef::new(Dashboards::CreatedAt)
+              .date_time()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .col(ColumnDef::new(Dashboards::DeletedAt).date_time().null())
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Apps::Table)
+          .add_column(ColumnDef::new(Apps::DashboardId).big_integer().null())
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Groups::Table)
+          .add_column(ColumnDef::new(Groups::DashboardId).big_integer().null())
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Shares::Table)
+          .add_column(ColumnDef::new(Shares::DashboardId).big_integer().null())
+          .to_owned(),
+      )
+      .await?;
+
+    // 每个用户只能有一个默认面板，MySQL 不支持部分索引，使用函数索引代替
+    let db = manager.get_connection();
+    match manager.get_database_backend() {
+      DatabaseBackend::MySql => {
+        db.execute_unprepared(
+          "CREATE UNIQUE INDEX idx_dashboards_default_owner
+          ON dashboards ((CASE WHEN is_default THEN owner_id END))",
+        )
+        .await?;
+      }
+      DatabaseBackend::Postgres | DatabaseBackend::Sqlite => {
+        db.execute_unprepared(
+          "CREATE UNIQUE INDEX IF NOT EXISTS idx_dashboards_default_owner
+          ON dashboards (owner_id) WHERE is_default",
+        )
+        .await?;
+      }
+    }
+
+    // 为已有用户创建默认面板，并将已有的应用、分组与整体分享归入默认面板
+    manager
+      .exec_stmt(
+        Query::insert()
+          .into_table(Dashboards::Table)
+          .columns([
+            Dashboards::Name,
+            Dashboards::Index,
+            Dashboards::IsDefault,
+            Dashboards::OwnerId,
+          ])
+          .select_from(
+            Query::select()
+              .expr(Expr::val("默认"))
+              .expr(Expr::val(0))
+              .expr(Expr::val(true))
+              .column(Users::Id)
+              .from(Users::Table)
+              .to_owned(),
+          )
+          .map_err(|err| DbErr::Migration(err.to_string()))?
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .exec_stmt(
+        Query::update()
+          .table(Apps::Table)
+          .value(
+            Apps::DashboardId,
+            default_dashboard_id(Apps::Table, Apps::OwnerId),
+          )
+          .and_where(Expr::col(Apps::IsGlobal).eq(false))
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .exec_stmt(
+        Query::update()
+          .table(Groups::Table)
+          .value(
+            Groups::DashboardId,
+            default_dashboard_id(Groups::Table, Groups::OwnerId),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .exec_stmt(
+        Query::update()
+          .table(Shares::Table)
+          .value(
+            Shares::DashboardId,
+            default_dashboard_id(Shares::Table, Shares::OwnerId),
+          )
+          .and_where(Expr::col(Shares::GroupId).is_null())
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_index(
+        Index::drop()
+          .name("idx_dashboards_default_owner")
+          .table(Dashboards::Table)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Shares::Table)
+          .drop_column(Shares::DashboardId)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Groups::Table)
+          .drop_column(Groups::DashboardId)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Apps::Table)
+          .drop_column(Apps::DashboardId)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .drop_table(Table::drop().table(Dashboards::Table).to_owned())
+      .await
+  }
+}
+
+// 数据所有者的默认面板
+fn default_dashboard_id<T: Iden + 'static>(table: T, owner_id: T) -> SimpleExpr {
+  SimpleExpr::SubQuery(
+    None,
+    Box::new(
+      Query::select()
+        .column(Dashboards::Id)
+        .from(Dashboards::Table)
+        .and_where(Expr::col((Dashboards::Table, Dashboards::OwnerId)).equals((table, owner_id)))
+        .and_where(Expr::col((Dashboards::Table, Dashboards::IsDefault)).eq(true))
+        .to_owned()
+        .into_sub_query_statement(),
+    ),
+  )
+}
+
+#[derive(Iden)]
+enum Dashboards {
+  Table,
+  Id,
+  Name,
+  Index,
+  IsDefault,
+  Layout,
+  Background,
+  OwnerId,
+  CreatedAt,
+  DeletedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+  Table,
+  Id,
+}
+
+#[derive(Iden)]
+enum Apps {
+  Table,
+  OwnerId,
+  IsGlobal,
+  DashboardId,
+}
+
+#[derive(Iden)]
+enum Groups {
+  Table,
+  OwnerId,
+  DashboardId,
+}
+
+#[derive(Iden)]
+enum Shares {
+  Table,
+  OwnerId,
+  GroupId,
+  DashboardId,
+}
@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(AppChecks::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(AppChecks::Id)
+              .big_integer()
+              .auto_increment()
+              .primary_key()
+              .not_null(),
+          )
+          .col(ColumnDef::new(AppChecks::AppId).big_integer().not_null())
+          .col(
+            ColumnDef::new(AppChecks::Status)
+              .string()
+              .string_len(20)
+              .not_null(),
+          )
+          .col(ColumnDef::new(AppChecks::StatusCode).integer().null())
+          .col(ColumnDef::new(AppChecks::Latency).integer().null())
+          .col(
+            ColumnDef::new(AppChecks::Message)
+              .string()
+              .string_len(255)
+              .null(),
+          )
+          .col(
+            ColumnDef::new(AppChecks::CheckedAt)
+              .date_time()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_app_checks_app_id_checked_at")
+          .table(AppChecks::Table)
+          .col(AppChecks::AppId)
+          .col(AppChecks::CheckedAt)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(AppChecks::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum AppChecks {
+  Table,
+  Id,
+  AppId,
+  Status,
+  StatusCode,
+  Latency,
+  Message,
+  CheckedAt,
+}
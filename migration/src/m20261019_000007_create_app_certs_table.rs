@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(AppCerts::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(AppCerts::AppId)
+              .big_integer()
+              .primary_key()
+              .not_null(),
+          )
+          .col(ColumnDef::new(AppCerts::Issuer).text().null())
+          .col(ColumnDef::new(AppCerts::Subject).text().null())
+          .col(ColumnDef::new(AppCerts::Sans).json().not_null())
+          .col(ColumnDef::new(AppCerts::NotBefore).date_time().null())
+          .col(ColumnDef::new(AppCerts::ExpiresAt).date_time().null())
+          .col(
+            ColumnDef::new(AppCerts::HostnameMatch)
+              .boolean()
+              .not_null()
+              .default(false),
+          )
+          .col(
+            ColumnDef::new(AppCerts::Error)
+              .string()
+              .string_len(255)
+              .null(),
+          )
+          .col(
+            ColumnDef::new(AppCerts::CheckedAt)
+              .date_time()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(AppCerts::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum AppCerts {
+  Table,
+  AppId,
+  Issuer,
+  Subject,
+  Sans,
+  NotBefore,
+  ExpiresAt,
+  HostnameMatch,
+  Error,
+  CheckedAt,
+}
@@ -0,0 +1,131 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Notifications::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(Notifications::Id)
+              .big_integer()
+              .auto_increment()
+              .primary_key()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(Notifications::Name)
+              .string()
+              .string_len(30)
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(Notifications::Kind)
+              .string()
+              .string_len(20)
+              .not_null(),
+          )
+          .col(ColumnDef::new(Notifications::Config).json().not_null())
+          .col(
+            ColumnDef::new(Notifications::TitleTemplate)
+              .string()
+              .string_len(255)
+              .null(),
+          )
+          .col(ColumnDef::new(Notifications::BodyTemplate).text().null())
+          .col(
+            ColumnDef::new(Notifications::Enabled)
+              .boolean()
+              .not_null()
+              .default(true),
+          )
+          .col(
+            ColumnDef::new(Notifications::IsGlobal)
+              .boolean()
+              .not_null()
+              .default(false),
+          )
+          .col(
+            ColumnDef::new(Notifications::OwnerId)
+              .big_integer()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(Notifications::CreatedAt)
+              .date_time()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .col(ColumnDef::new(Notifications::DeletedAt).date_time().null())
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(AppAlerts::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(AppAlerts::AppId)
+              .big_integer()
+              .primary_key()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(AppAlerts::Status)
+              .string()
+              .string_len(20)
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(AppAlerts::Failures)
+              .integer()
+              .not_null()
+              .default(0),
+          )
+          .col(ColumnDef::new(AppAlerts::NotifiedAt).date_time().null())
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(AppAlerts::Table).to_owned())
+      .await?;
+    manager
+      .drop_table(Table::drop().table(Notifications::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum Notifications {
+  Table,
+  Id,
+  Name,
+  Kind,
+  Config,
+  TitleTemplate,
+  BodyTemplate,
+  Enabled,
+  IsGlobal,
+  OwnerId,
+  CreatedAt,
+  DeletedAt,
+}
+
+#[derive(Iden)]
+enum AppAlerts {
+  Table,
+  AppId,
+  Status,
+  Failures,
+  NotifiedAt,
+}
@@ -0,0 +1,156 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(StatusComponents::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(StatusComponents::Id)
+              .big_integer()
+              .auto_increment()
+              .primary_key()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(StatusComponents::Name)
+              .string()
+              .string_len(30)
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(StatusComponents::Description)
+              .string()
+              .string_len(255)
+              .null(),
+          )
+          .col(ColumnDef::new(StatusComponents::AppIds).json().not_null())
+          .col(
+            ColumnDef::new(StatusComponents::Index)
+              .integer()
+              .not_null()
+              .default(0),
+          )
+          .col(
+            ColumnDef::new(StatusComponents::CreatedAt)
+              .date_time()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(StatusIncidents::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(StatusIncidents::Id)
+              .big_integer()
+              .auto_increment()
+              .primary_key()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(StatusIncidents::Title)
+              .string()
+              .string_len(100)
+              .not_null(),
+          )
+          .col(ColumnDef::new(StatusIncidents::Content).text().not_null())
+          .col(
+            ColumnDef::new(StatusIncidents::Status)
+              .string()
+              .string_len(20)
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(StatusIncidents::ComponentId)
+              .big_integer()
+              .null(),
+          )
+          .col(
+            ColumnDef::new(StatusIncidents::StartedAt)
+              .date_time()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(StatusIncidents::ResolvedAt)
+              .date_time()
+              .null(),
+          )
+          .col(
+            ColumnDef::new(StatusIncidents::OwnerId)
+              .big_integer()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(StatusIncidents::CreatedAt)
+              .date_time()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .col(
+            ColumnDef::new(StatusIncidents::UpdatedAt)
+              .date_time()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_status_incidents_started_at")
+          .table(StatusIncidents::Table)
+          .col(StatusIncidents::StartedAt)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(StatusIncidents::Table).to_owned())
+      .await?;
+    manager
+      .drop_table(Table::drop().table(StatusComponents::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum StatusComponents {
+  Table,
+  Id,
+  Name,
+  Description,
+  AppIds,
+  Index,
+  CreatedAt,
+}
+
+#[derive(Iden)]
+enum StatusIncidents {
+  Table,
+  Id,
+  Title,
+  Content,
+  Status,
+  ComponentId,
+  StartedAt,
+  ResolvedAt,
+  OwnerId,
+  CreatedAt,
+  UpdatedAt,
+}
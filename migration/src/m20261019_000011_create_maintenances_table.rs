@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Maintenances::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(Maintenances::Id)
+              .big_integer()
+              .auto_increment()
+              .primary_key()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(Maintenances::Title)
+              .string()
+              .string_len(100)
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(Maintenances::Description)
+              .string()
+              .string_len(255)
+              .null(),
+          )
+          .col(ColumnDef::new(Maintenances::AppIds).json().not_null())
+          .col(ColumnDef::new(Maintenances::GroupIds).json().not_null())
+          .col(ColumnDef::new(Maintenances::StartsAt).date_time().not_null())
+          .col(ColumnDef::new(Maintenances::EndsAt).date_time().null())
+          .col(
+            ColumnDef::new(Maintenances::Cron)
+              .string()
+              .string_len(100)
+              .null(),
+          )
+          .col(ColumnDef::new(Maintenances::Duration).integer().null())
+          .col(
+            ColumnDef::new(Maintenances::Enabled)
+              .boolean()
+              .not_null()
+              .default(true),
+          )
+          .col(
+            ColumnDef::new(Maintenances::OwnerId)
+              .big_integer()
+              .not_null(),
+          )
+          .col(
+            ColumnDef::new(Maintenances::CreatedAt)
+              .date_time()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(Maintenances::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum Maintenances {
+  Table,
+  Id,
+  Title,
+  Description,
+  AppIds,
+  GroupIds,
+  StartsAt,
+  EndsAt,
+  Cron,
+  Duration,
+  Enabled,
+  OwnerId,
+  CreatedAt,
+}
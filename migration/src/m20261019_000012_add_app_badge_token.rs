@@ -0,0 +1,60 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Apps::Table)
+          .add_column(
+            ColumnDef::new(Apps::BadgeToken)
+              .string()
+              .string_len(64)
+              .null(),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_apps_badge_token")
+          .table(Apps::Table)
+          .col(Apps::BadgeToken)
+          .unique()
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_index(
+        Index::drop()
+          .name("idx_apps_badge_token")
+          .table(Apps::Table)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Apps::Table)
+          .drop_column(Apps::BadgeToken)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum Apps {
+  Table,
+  BadgeToken,
+}
@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(AppClicks::Table)
+          .if_not_exists()
+          .col(
+            ColumnDef::new(AppClicks::Id)
+              .big_integer()
+              .auto_increment()
+              .primary_key()
+              .not_null(),
+          )
+          .col(ColumnDef::new(AppClicks::AppId).big_integer().not_null())
+          .col(ColumnDef::new(AppClicks::UserId).big_integer().not_null())
+          .col(ColumnDef::new(AppClicks::DashboardId).big_integer().null())
+          .col(
+            ColumnDef::new(AppClicks::ClickedAt)
+              .date_time()
+              .not_null()
+              .default(Expr::current_timestamp()),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_app_clicks_user_id_clicked_at")
+          .table(AppClicks::Table)
+          .col(AppClicks::UserId)
+          .col(AppClicks::ClickedAt)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx_app_clicks_app_id")
+          .table(AppClicks::Table)
+          .col(AppClicks::AppId)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(AppClicks::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(Iden)]
+enum AppClicks {
+  Table,
+  Id,
+  AppId,
+  UserId,
+  DashboardId,
+  ClickedAt,
+}
@@ -0,0 +1,128 @@
+use sea_orm_migration::{
+  prelude::*,
+  sea_orm::{ConnectionTrait, DatabaseBackend, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// 应用名称、URL 与描述的全文索引，不同数据库使用各自的全文检索功能
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+
+    match manager.get_database_backend() {
+      // 使用 trigram 分词，支持任意位置的子串匹配。SQLite 未启用 FTS5 时不创建索引，
+      // 搜索时在内存中匹配全部应用
+      DatabaseBackend::Sqlite => {
+        if !sqlite_supports_trigram(db).await? {
+          return Ok(());
+        }
+        db.execute_unprepared(
+          "CREATE VIRTUAL TABLE IF NOT EXISTS apps_fts USING fts5(
+            name, url, description,
+            content = 'apps', content_rowid = 'id', tokenize = 'trigram'
+          )",
+        )
+        .await?;
+        db.execute_unprepared(
+          "CREATE TRIGGER IF NOT EXISTS apps_fts_insert AFTER INSERT ON apps BEGIN
+            INSERT INTO apps_fts (rowid, name, url, description)
+            VALUES (new.id, new.name, new.url, new.description);
+          END",
+        )
+        .await?;
+        db.execute_unprepared(
+          "CREATE TRIGGER IF NOT EXISTS apps_fts_delete AFTER DELETE ON apps BEGIN
+            INSERT INTO apps_fts (apps_fts, rowid, name, url, description)
+            VALUES ('delete', old.id, old.name, old.url, old.description);
+          END",
+        )
+        .await?;
+        db.execute_unprepared(
+          "CREATE TRIGGER IF NOT EXISTS apps_fts_update AFTER UPDATE ON apps BEGIN
+            INSERT INTO apps_fts (apps_fts, rowid, name, url, description)
+            VALUES ('delete', old.id, old.name, old.url, old.description);
+            INSERT INTO apps_fts (rowid, name, url, description)
+            VALUES (new.id, new.name, new.url, new.description);
+          END",
+        )
+        .await?;
+        db.execute_unprepared("INSERT INTO apps_fts (apps_fts) VALUES ('rebuild')")
+          .await?;
+      }
+      DatabaseBackend::Postgres => {
+        db.execute_unprepared(
+          "ALTER TABLE apps ADD COLUMN IF NOT EXISTS search_vector tsvector
+          GENERATED ALWAYS AS (
+            to_tsvector('simple', coalesce(name, '') || ' ' || coalesce(url, '') || ' ' || coalesce(description, ''))
+          ) STORED",
+        )
+        .await?;
+        db.execute_unprepared(
+          "CREATE INDEX IF NOT EXISTS idx_apps_search_vector ON apps USING GIN (search_vector)",
+        )
+        .await?;
+      }
+      DatabaseBackend::MySql => {
+        db.execute_unprepared(
+          "CREATE FULLTEXT INDEX idx_apps_fulltext ON apps (name, url, description)",
+        )
+        .await?;
+      }
+    }
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+
+    match manager.get_database_backend() {
+      DatabaseBackend::Sqlite => {
+        for trigger in ["apps_fts_insert", "apps_fts_delete", "apps_fts_update"] {
+          db.execute_unprepared(&format!("DROP TRIGGER IF EXISTS {}", trigger))
+            .await?;
+        }
+        db.execute_unprepared("DROP TABLE IF EXISTS apps_fts")
+          .await?;
+      }
+      DatabaseBackend::Postgres => {
+        db.execute_unprepared("DROP INDEX IF EXISTS idx_apps_search_vector")
+          .await?;
+        db.execute_unprepared("ALTER TABLE apps DROP COLUMN IF EXISTS search_vector")
+          .await?;
+      }
+      DatabaseBackend::MySql => {
+        db.execute_unprepared("DROP INDEX idx_apps_fulltext ON apps")
+          .await?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+// trigram 分词器需要 SQLite 3.34 及以上版本
+async fn sqlite_supports_trigram<C: ConnectionTrait>(db: &C) -> Result<bool, DbErr> {
+  let row = db
+    .query_one(Statement::from_string(
+      DatabaseBackend::Sqlite,
+      "SELECT sqlite_version() AS version,
+        EXISTS (SELECT 1 FROM pragma_compile_options WHERE compile_options = 'ENABLE_FTS5') AS fts5",
+    ))
+    .await?;
+  let Some(row) = row else {
+    return Ok(false);
+  };
+
+  let version = row.try_get::<String>("", "version")?;
+  let fts5 = row.try_get::<bool>("", "fts5")?;
+  let mut parts = version
+    .split('.')
+    .map(|part| part.parse::<u32>().unwrap_or(0));
+  let version = (parts.next().unwrap_or(0), parts.next().unwrap_or(0));
+
+  Ok(fts5 && version >= (3, 34))
+}
@@ -1,27 +1,64 @@
 use crate::{
-  core::app::{self, SortAppData},
+  core::{
+    app::{self, SortAppData},
+    badge, board, bulk, click, global_app, icon, import, metadata, monitor, network, search,
+  },
   errors::Result,
 };
 
 use actix_identity::Identity;
-use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use actix_web::{delete, get, http::header, post, put, web, HttpRequest, HttpResponse, Responder};
 use sea_orm::DbConn;
+use validator::Validate;
 
 #[get("/all")]
-async fn all(identity: Identity, db: web::Data<DbConn>) -> Result<impl Responder> {
+async fn all(
+  req: HttpRequest,
+  identity: Identity,
+  db: web::Data<DbConn>,
+  query: web::Query<app::GetUserAllAppQuery>,
+) -> Result<impl Responder> {
   let id = identity.id().map(|id| id.parse::<i64>())??;
 
-  let apps = app::get_user_all_app(&db, id).await?;
+  let apps = app::get_user_all_app(&db, id, &query, network::client_ip(&req)).await?;
 
   Ok(HttpResponse::Ok().json(apps))
 }
 
+#[get("/search")]
+async fn search_app(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  query: web::Query<search::SearchAppQuery>,
+) -> Result<impl Responder> {
+  query.validate()?;
+  let id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let results = search::search_app(&db, id, &query).await?;
+
+  Ok(HttpResponse::Ok().json(results))
+}
+
+#[get("/status")]
+async fn status(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  query: web::Query<monitor::GetAppStatusQuery>,
+) -> Result<impl Responder> {
+  let id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let apps_status = monitor::get_user_app_status(&db, id, &query).await?;
+
+  Ok(HttpResponse::Ok().json(apps_status))
+}
+
 #[post("/create")]
 async fn create(
   identity: Identity,
   db: web::Data<DbConn>,
   data: web::Json<app::CreateAppData>,
 ) -> Result<impl Responder> {
+  data.validate()?;
   let operator_id = identity.id().map(|id| id.parse::<i64>())??;
 
   let created_app = app::create_app(&db, operator_id, &data).await?;
@@ -35,6 +72,7 @@ async fn update(
   db: web::Data<DbConn>,
   data: web::Json<app::UpdateAppData>,
 ) -> Result<impl Responder> {
+  data.validate()?;
   let operator_id = identity.id().map(|id| id.parse::<i64>())??;
 
   let updated_app = app::update_app(&db, operator_id, &data).await?;
@@ -46,11 +84,12 @@ async fn update(
 async fn sort(
   identity: Identity,
   db: web::Data<DbConn>,
+  query: web::Query<app::SortAppQuery>,
   data: web::Json<Vec<SortAppData>>,
 ) -> Result<impl Responder> {
   let operator_id = identity.id().map(|id| id.parse::<i64>())??;
 
-  app::sort_app(&db, operator_id, &data).await?;
+  app::sort_app(&db, operator_id, &query, &data).await?;
 
   Ok(HttpResponse::Ok())
 }
@@ -67,3 +106,257 @@ async fn delete(
 
   Ok(HttpResponse::Ok())
 }
+
+#[post("/bulk/delete")]
+async fn bulk_delete(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<bulk::BulkAppIds>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let result = bulk::bulk_delete_app(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(result))
+}
+
+#[post("/bulk/move")]
+async fn bulk_move(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<bulk::BulkMoveAppData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let result = bulk::bulk_move_app(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(result))
+}
+
+#[post("/bulk/tag")]
+async fn bulk_tag(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<bulk::BulkTagAppData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let result = bulk::bulk_tag_app(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(result))
+}
+
+#[post("/bulk/icon")]
+async fn bulk_icon(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<bulk::BulkIconAppData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let result = bulk::bulk_icon_app(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(result))
+}
+
+#[post("/bulk/duplicate")]
+async fn bulk_duplicate(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<bulk::BulkAppIds>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let result = bulk::bulk_duplicate_app(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(result))
+}
+
+#[get("/push-token/{app_id}")]
+async fn push_token(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  app_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let push_info = monitor::get_push_info(&db, operator_id, *app_id).await?;
+
+  Ok(HttpResponse::Ok().json(push_info))
+}
+
+#[put("/push-token/{app_id}")]
+async fn reset_push_token(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  app_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let push_info = monitor::reset_push_token(&db, operator_id, *app_id).await?;
+
+  Ok(HttpResponse::Ok().json(push_info))
+}
+
+#[get("/badge-token/{app_id}")]
+async fn badge_token(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  app_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let badge_info = badge::get_badge_info(&db, operator_id, *app_id).await?;
+
+  Ok(HttpResponse::Ok().json(badge_info))
+}
+
+#[put("/badge-token/{app_id}")]
+async fn reset_badge_token(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  app_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let badge_info = badge::reset_badge_token(&db, operator_id, *app_id).await?;
+
+  Ok(HttpResponse::Ok().json(badge_info))
+}
+
+#[put("/icon/{app_id}")]
+async fn refresh_icon(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  app_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let app = icon::refresh_icon(&db, operator_id, *app_id).await?;
+
+  Ok(HttpResponse::Ok().json(app))
+}
+
+#[put("/icon")]
+async fn refresh_all_icon(identity: Identity, db: web::Data<DbConn>) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let results = icon::refresh_all_icon(&db, operator_id).await?;
+
+  Ok(HttpResponse::Ok().json(results))
+}
+
+#[get("/metadata")]
+async fn url_metadata(
+  _identity: Identity,
+  query: web::Query<metadata::GetUrlMetadataQuery>,
+) -> Result<impl Responder> {
+  query.validate()?;
+
+  let metadata = metadata::get_url_metadata(&query).await?;
+
+  Ok(HttpResponse::Ok().json(metadata))
+}
+
+#[get("/export")]
+async fn export_board(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  query: web::Query<board::ExportBoardQuery>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let board = board::export_board(&db, operator_id, &query).await?;
+
+  Ok(
+    HttpResponse::Ok()
+      .content_type(board.content_type)
+      .insert_header((
+        header::CONTENT_DISPOSITION,
+        format!("attachment; filename=\"{}\"", board.filename),
+      ))
+      .body(board.content),
+  )
+}
+
+#[post("/import")]
+async fn import_apps(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<import::ImportData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let result = import::import_apps(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(result))
+}
+
+#[get("/launch/{app_id}")]
+async fn launch(
+  req: HttpRequest,
+  identity: Identity,
+  db: web::Data<DbConn>,
+  app_id: web::Path<i64>,
+  query: web::Query<click::LaunchAppQuery>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let url = click::launch_app(&db, operator_id, *app_id, &query, network::client_ip(&req)).await?;
+
+  Ok(
+    HttpResponse::Found()
+      .insert_header((header::LOCATION, url))
+      .finish(),
+  )
+}
+
+#[get("/stats")]
+async fn stats(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  query: web::Query<click::GetClickStatsQuery>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let stats = click::get_click_stats(&db, operator_id, &query).await?;
+
+  Ok(HttpResponse::Ok().json(stats))
+}
+
+#[delete("/clicks")]
+async fn clear_clicks(identity: Identity, db: web::Data<DbConn>) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  click::clear_clicks(&db, operator_id).await?;
+
+  Ok(HttpResponse::Ok())
+}
+
+#[get("/hidden")]
+async fn hidden(identity: Identity, db: web::Data<DbConn>) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let apps = global_app::get_user_hidden_global_app(&db, operator_id).await?;
+
+  Ok(HttpResponse::Ok().json(apps))
+}
+
+#[put("/hide")]
+async fn hide(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<global_app::HideGlobalAppData>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  global_app::hide_global_app(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok())
+}
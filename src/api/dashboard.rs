@@ -0,0 +1,85 @@
+use crate::{
+  core::dashboard::{self, SortDashboardData},
+  errors::Result,
+};
+
+use actix_identity::Identity;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use sea_orm::DbConn;
+use validator::Validate;
+
+#[get("/all")]
+async fn all(identity: Identity, db: web::Data<DbConn>) -> Result<impl Responder> {
+  let id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let dashboards = dashboard::get_user_all_dashboard(&db, id).await?;
+
+  Ok(HttpResponse::Ok().json(dashboards))
+}
+
+#[post("/create")]
+async fn create(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<dashboard::CreateDashboardData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let created_dashboard = dashboard::create_dashboard(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(created_dashboard))
+}
+
+#[put("/update")]
+async fn update(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<dashboard::UpdateDashboardData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let updated_dashboard = dashboard::update_dashboard(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(updated_dashboard))
+}
+
+#[put("/sort")]
+async fn sort(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<Vec<SortDashboardData>>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  dashboard::sort_dashboard(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok())
+}
+
+#[put("/default/{dashboard_id}")]
+async fn default(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  dashboard_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  dashboard::set_default_dashboard(&db, operator_id, *dashboard_id).await?;
+
+  Ok(HttpResponse::Ok())
+}
+
+#[delete("/delete/{dashboard_id}")]
+async fn delete(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  dashboard_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  dashboard::delete_dashboard(&db, operator_id, *dashboard_id).await?;
+
+  Ok(HttpResponse::Ok())
+}
@@ -0,0 +1,72 @@
+use crate::{
+  core::global_app::{self, SortGlobalAppData},
+  errors::Result,
+};
+
+use actix_identity::Identity;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use sea_orm::DbConn;
+use validator::Validate;
+
+#[get("/all")]
+async fn all(identity: Identity, db: web::Data<DbConn>) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let apps = global_app::get_all_global_app(&db, operator_id).await?;
+
+  Ok(HttpResponse::Ok().json(apps))
+}
+
+#[post("/create")]
+async fn create(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<global_app::CreateGlobalAppData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let created_app = global_app::create_global_app(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(created_app))
+}
+
+#[put("/update")]
+async fn update(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<global_app::UpdateGlobalAppData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let updated_app = global_app::update_global_app(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(updated_app))
+}
+
+#[put("/sort")]
+async fn sort(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<Vec<SortGlobalAppData>>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  global_app::sort_global_app(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok())
+}
+
+#[delete("/delete/{app_id}")]
+async fn delete(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  app_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  global_app::delete_global_app(&db, operator_id, *app_id).await?;
+
+  Ok(HttpResponse::Ok())
+}
@@ -0,0 +1,76 @@
+use crate::{
+  core::group::{self, SortGroupData},
+  errors::Result,
+};
+
+use actix_identity::Identity;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use sea_orm::DbConn;
+use validator::Validate;
+
+#[get("/all")]
+async fn all(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  query: web::Query<group::GetUserAllGroupQuery>,
+) -> Result<impl Responder> {
+  let id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let groups = group::get_user_all_group(&db, id, &query).await?;
+
+  Ok(HttpResponse::Ok().json(groups))
+}
+
+#[post("/create")]
+async fn create(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<group::CreateGroupData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let created_group = group::create_group(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(created_group))
+}
+
+#[put("/update")]
+async fn update(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<group::UpdateGroupData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let updated_group = group::update_group(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(updated_group))
+}
+
+#[put("/sort")]
+async fn sort(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<Vec<SortGroupData>>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  group::sort_group(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok())
+}
+
+#[delete("/delete/{group_id}")]
+async fn delete(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  group_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  group::delete_group(&db, operator_id, *group_id).await?;
+
+  Ok(HttpResponse::Ok())
+}
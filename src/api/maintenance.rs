@@ -0,0 +1,56 @@
+use crate::{core::maintenance, errors::Result};
+
+use actix_identity::Identity;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use sea_orm::DbConn;
+use validator::Validate;
+
+#[get("/all")]
+async fn all(identity: Identity, db: web::Data<DbConn>) -> Result<impl Responder> {
+  let id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let maintenances = maintenance::get_user_all_maintenance(&db, id).await?;
+
+  Ok(HttpResponse::Ok().json(maintenances))
+}
+
+#[post("/create")]
+async fn create(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<maintenance::MaintenanceData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let created_maintenance = maintenance::create_maintenance(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(created_maintenance))
+}
+
+#[put("/update")]
+async fn update(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<maintenance::UpdateMaintenanceData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let updated_maintenance = maintenance::update_maintenance(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(updated_maintenance))
+}
+
+#[delete("/delete/{maintenance_id}")]
+async fn delete(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  maintenance_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  maintenance::delete_maintenance(&db, operator_id, *maintenance_id).await?;
+
+  Ok(HttpResponse::Ok())
+}
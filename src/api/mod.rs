@@ -1,9 +1,18 @@
 mod app;
 mod auth;
+mod dashboard;
 mod proxy;
 mod file;
+mod global_app;
+mod group;
+mod maintenance;
+mod notification;
 mod password;
+mod push;
 mod setting;
+mod share;
+mod status_page;
+mod tag;
 mod user;
 
 use actix_web::web;
@@ -29,10 +38,99 @@ pub fn init(cfg: &mut web::ServiceConfig) {
     .service(
       web::scope("/app")
         .service(app::all)
+        .service(app::search_app)
+        .service(app::status)
         .service(app::create)
         .service(app::update)
         .service(app::sort)
-        .service(app::delete),
+        .service(app::delete)
+        .service(app::bulk_delete)
+        .service(app::bulk_move)
+        .service(app::bulk_tag)
+        .service(app::bulk_icon)
+        .service(app::bulk_duplicate)
+        .service(app::hidden)
+        .service(app::hide)
+        .service(app::push_token)
+        .service(app::reset_push_token)
+        .service(app::badge_token)
+        .service(app::reset_badge_token)
+        .service(app::refresh_icon)
+        .service(app::refresh_all_icon)
+        .service(app::url_metadata)
+        .service(app::import_apps)
+        .service(app::export_board)
+        .service(app::launch)
+        .service(app::stats)
+        .service(app::clear_clicks),
+    )
+    .service(web::scope("/push").service(push::push))
+    .service(
+      web::scope("/global-app")
+        .service(global_app::all)
+        .service(global_app::create)
+        .service(global_app::update)
+        .service(global_app::sort)
+        .service(global_app::delete),
+    )
+    .service(
+      web::scope("/dashboard")
+        .service(dashboard::all)
+        .service(dashboard::create)
+        .service(dashboard::update)
+        .service(dashboard::sort)
+        .service(dashboard::default)
+        .service(dashboard::delete),
+    )
+    .service(
+      web::scope("/group")
+        .service(group::all)
+        .service(group::create)
+        .service(group::update)
+        .service(group::sort)
+        .service(group::delete),
+    )
+    .service(
+      web::scope("/share")
+        .service(share::all)
+        .service(share::received)
+        .service(share::create)
+        .service(share::update)
+        .service(share::delete),
+    )
+    .service(
+      web::scope("/maintenance")
+        .service(maintenance::all)
+        .service(maintenance::create)
+        .service(maintenance::update)
+        .service(maintenance::delete),
+    )
+    .service(
+      web::scope("/notification")
+        .service(notification::all)
+        .service(notification::create)
+        .service(notification::update)
+        .service(notification::delete)
+        .service(notification::test),
+    )
+    .service(
+      web::scope("/status-page")
+        .service(status_page::component_all)
+        .service(status_page::component_create)
+        .service(status_page::component_update)
+        .service(status_page::component_sort)
+        .service(status_page::component_delete)
+        .service(status_page::incident_all)
+        .service(status_page::incident_create)
+        .service(status_page::incident_update)
+        .service(status_page::incident_delete),
+    )
+    .service(
+      web::scope("/tag")
+        .service(tag::all)
+        .service(tag::create)
+        .service(tag::update)
+        .service(tag::delete),
     )
     .service(web::scope("/setting").service(setting::update));
 }
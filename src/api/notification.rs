@@ -0,0 +1,69 @@
+use crate::{core::notification, errors::Result};
+
+use actix_identity::Identity;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use sea_orm::DbConn;
+use validator::Validate;
+
+#[get("/all")]
+async fn all(identity: Identity, db: web::Data<DbConn>) -> Result<impl Responder> {
+  let id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let notifications = notification::get_user_all_notification(&db, id).await?;
+
+  Ok(HttpResponse::Ok().json(notifications))
+}
+
+#[post("/create")]
+async fn create(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<notification::NotificationData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let created_notification = notification::create_notification(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(created_notification))
+}
+
+#[put("/update")]
+async fn update(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<notification::UpdateNotificationData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let updated_notification = notification::update_notification(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(updated_notification))
+}
+
+#[delete("/delete/{notification_id}")]
+async fn delete(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  notification_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  notification::delete_notification(&db, operator_id, *notification_id).await?;
+
+  Ok(HttpResponse::Ok())
+}
+
+#[post("/test/{notification_id}")]
+async fn test(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  notification_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  notification::test_notification(&db, operator_id, *notification_id).await?;
+
+  Ok(HttpResponse::Ok())
+}
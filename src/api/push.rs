@@ -0,0 +1,16 @@
+use crate::{core::monitor, errors::Result};
+
+use actix_web::{route, web, HttpResponse, Responder};
+use sea_orm::DbConn;
+
+// 推送地址不需要登录，通过令牌识别应用
+#[route("/{push_token}", method = "GET", method = "POST")]
+async fn push(
+  db: web::Data<DbConn>,
+  push_token: web::Path<String>,
+  query: web::Query<monitor::PushQuery>,
+) -> Result<impl Responder> {
+  monitor::push_heartbeat(&db, &push_token, &query).await?;
+
+  Ok(HttpResponse::Ok())
+}
@@ -0,0 +1,62 @@
+use crate::{core::share, errors::Result};
+
+use actix_identity::Identity;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use sea_orm::DbConn;
+
+#[get("/all")]
+async fn all(identity: Identity, db: web::Data<DbConn>) -> Result<impl Responder> {
+  let id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let shares = share::get_user_all_share(&db, id).await?;
+
+  Ok(HttpResponse::Ok().json(shares))
+}
+
+#[get("/received")]
+async fn received(identity: Identity, db: web::Data<DbConn>) -> Result<impl Responder> {
+  let id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let shares = share::get_shared_with_user(db.as_ref(), id).await?;
+
+  Ok(HttpResponse::Ok().json(shares))
+}
+
+#[post("/create")]
+async fn create(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<share::CreateShareData>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let created_share = share::create_share(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(created_share))
+}
+
+#[put("/update")]
+async fn update(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<share::UpdateShareData>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let updated_share = share::update_share(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(updated_share))
+}
+
+#[delete("/delete/{share_id}")]
+async fn delete(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  share_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  share::delete_share(&db, operator_id, *share_id).await?;
+
+  Ok(HttpResponse::Ok())
+}
@@ -0,0 +1,122 @@
+use crate::{
+  core::status_page::{self, SortStatusComponentData},
+  errors::Result,
+};
+
+use actix_identity::Identity;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use sea_orm::DbConn;
+use validator::Validate;
+
+#[get("/component/all")]
+async fn component_all(identity: Identity, db: web::Data<DbConn>) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let components = status_page::get_all_status_component(&db, operator_id).await?;
+
+  Ok(HttpResponse::Ok().json(components))
+}
+
+#[post("/component/create")]
+async fn component_create(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<status_page::StatusComponentData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let created_component = status_page::create_status_component(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(created_component))
+}
+
+#[put("/component/update")]
+async fn component_update(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<status_page::UpdateStatusComponentData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let updated_component = status_page::update_status_component(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(updated_component))
+}
+
+#[put("/component/sort")]
+async fn component_sort(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<Vec<SortStatusComponentData>>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  status_page::sort_status_component(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok())
+}
+
+#[delete("/component/delete/{component_id}")]
+async fn component_delete(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  component_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  status_page::delete_status_component(&db, operator_id, *component_id).await?;
+
+  Ok(HttpResponse::Ok())
+}
+
+#[get("/incident/all")]
+async fn incident_all(identity: Identity, db: web::Data<DbConn>) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let incidents = status_page::get_all_status_incident(&db, operator_id).await?;
+
+  Ok(HttpResponse::Ok().json(incidents))
+}
+
+#[post("/incident/create")]
+async fn incident_create(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<status_page::StatusIncidentData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let created_incident = status_page::create_status_incident(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(created_incident))
+}
+
+#[put("/incident/update")]
+async fn incident_update(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<status_page::UpdateStatusIncidentData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let updated_incident = status_page::update_status_incident(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(updated_incident))
+}
+
+#[delete("/incident/delete/{incident_id}")]
+async fn incident_delete(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  incident_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  status_page::delete_status_incident(&db, operator_id, *incident_id).await?;
+
+  Ok(HttpResponse::Ok())
+}
@@ -0,0 +1,56 @@
+use crate::{core::tag, errors::Result};
+
+use actix_identity::Identity;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use sea_orm::DbConn;
+use validator::Validate;
+
+#[get("/all")]
+async fn all(identity: Identity, db: web::Data<DbConn>) -> Result<impl Responder> {
+  let id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let tags = tag::get_user_all_tag(&db, id).await?;
+
+  Ok(HttpResponse::Ok().json(tags))
+}
+
+#[post("/create")]
+async fn create(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<tag::CreateTagData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let created_tag = tag::create_tag(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(created_tag))
+}
+
+#[put("/update")]
+async fn update(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  data: web::Json<tag::UpdateTagData>,
+) -> Result<impl Responder> {
+  data.validate()?;
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let updated_tag = tag::update_tag(&db, operator_id, &data).await?;
+
+  Ok(HttpResponse::Ok().json(updated_tag))
+}
+
+#[delete("/delete/{tag_id}")]
+async fn delete(
+  identity: Identity,
+  db: web::Data<DbConn>,
+  tag_id: web::Path<i64>,
+) -> Result<impl Responder> {
+  let operator_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  tag::delete_tag(&db, operator_id, *tag_id).await?;
+
+  Ok(HttpResponse::Ok())
+}
@@ -0,0 +1,127 @@
+use crate::{
+  core::badge::{self, BadgeQuery, BadgeUptimeQuery},
+  errors::Result,
+};
+
+use actix_web::{
+  http::header::{CacheControl, CacheDirective},
+  web, HttpResponse, Responder,
+};
+use entity::app_checks::CheckStatus;
+use sea_orm::DbConn;
+use utils::html::escape;
+
+const GREEN: &str = "#4c1";
+const YELLOW_GREEN: &str = "#97ca00";
+const YELLOW: &str = "#dfb317";
+const ORANGE: &str = "#fe7d37";
+const RED: &str = "#e05d44";
+const BLUE: &str = "#007ec6";
+const GREY: &str = "#9f9f9f";
+
+// 估算文字宽度，非 ASCII 字符按全角计算
+fn text_width(text: &str) -> usize {
+  text
+    .chars()
+    .map(|char| if char.is_ascii() { 7 } else { 12 })
+    .sum::<usize>()
+    + 10
+}
+
+// 生成 shields.io 风格的扁平徽章
+fn render(label: &str, message: &str, color: &str) -> String {
+  let label = label.chars().take(30).collect::<String>();
+  let label_width = text_width(&label);
+  let message_width = text_width(message);
+  let label = escape(&label);
+  let message = escape(message);
+  let width = label_width + message_width;
+  let label_x = label_width * 5;
+  let message_x = label_width * 10 + message_width * 5;
+
+  format!(
+    r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}"><title>{label}: {message}</title><linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient><clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath><g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/><rect width="{width}" height="20" fill="url(#s)"/></g><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="110"><text x="{label_x}" y="150" fill="#010101" fill-opacity=".3" transform="scale(.1)">{label}</text><text x="{label_x}" y="140" transform="scale(.1)">{label}</text><text x="{message_x}" y="150" fill="#010101" fill-opacity=".3" transform="scale(.1)">{message}</text><text x="{message_x}" y="140" transform="scale(.1)">{message}</text></g></svg>"##,
+  )
+}
+
+// 徽章内容随检查结果变化，避免被长时间缓存
+fn svg_response(svg: String) -> HttpResponse {
+  HttpResponse::Ok()
+    .content_type("image/svg+xml")
+    .insert_header(CacheControl(vec![
+      CacheDirective::NoCache,
+      CacheDirective::MaxAge(0),
+    ]))
+    .body(svg)
+}
+
+// 徽章不需要登录，通过令牌识别应用，不包含应用地址
+pub async fn status(
+  db: web::Data<DbConn>,
+  badge_token: web::Path<String>,
+  query: web::Query<BadgeQuery>,
+) -> Result<impl Responder> {
+  let status = badge::get_badge_status(&db, &badge_token).await?;
+
+  let (message, color) = match status {
+    Some(CheckStatus::Up) => ("up", GREEN),
+    Some(CheckStatus::Down) => ("down", RED),
+    Some(CheckStatus::Maintenance) => ("maintenance", BLUE),
+    None => ("unknown", GREY),
+  };
+  let label = query.label.as_deref().unwrap_or("status");
+
+  Ok(svg_response(render(label, message, color)))
+}
+
+pub async fn uptime(
+  db: web::Data<DbConn>,
+  badge_token: web::Path<String>,
+  query: web::Query<BadgeUptimeQuery>,
+) -> Result<impl Responder> {
+  let uptime = badge::get_badge_uptime(&db, &badge_token, &query).await?;
+
+  let (message, color) = match uptime {
+    Some(uptime) => {
+      let percent = uptime * 100.0;
+      let color = match percent {
+        percent if percent >= 99.9 => GREEN,
+        percent if percent >= 99.0 => YELLOW_GREEN,
+        percent if percent >= 95.0 => YELLOW,
+        _ => RED,
+      };
+      (format!("{:.2}%", (percent * 100.0).floor() / 100.0), color)
+    }
+    None => ("unknown".to_string(), GREY),
+  };
+  let label = query
+    .label
+    .clone()
+    .unwrap_or_else(|| format!("uptime {}d", query.days()));
+
+  Ok(svg_response(render(&label, &message, color)))
+}
+
+pub async fn latency(
+  db: web::Data<DbConn>,
+  badge_token: web::Path<String>,
+  query: web::Query<BadgeQuery>,
+) -> Result<impl Responder> {
+  let latency = badge::get_badge_latency(&db, &badge_token).await?;
+
+  let (message, color) = match latency {
+    Some(latency) => {
+      let color = match latency {
+        0..200 => GREEN,
+        200..500 => YELLOW_GREEN,
+        500..1000 => YELLOW,
+        _ => ORANGE,
+      };
+      (format!("{}ms", latency), color)
+    }
+    None => ("unknown".to_string(), GREY),
+  };
+  let label = query.label.as_deref().unwrap_or("latency");
+
+  Ok(svg_response(render(label, &message, color)))
+}
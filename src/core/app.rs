@@ -1,23 +1,474 @@
+use super::{
+  cert::{get_apps_cert, CertInfo},
+  click::sort_by_usage,
+  dashboard::{get_default_dashboard, resolve_dashboard},
+  global_app::{get_user_global_app, save_app_pref},
+  group::{check_group_owner, get_dashboard_all_group},
+  icon::{save_remote_icon, spawn_discover_icon},
+  link::{to_app_links, AppLinkData},
+  monitor::{ensure_push_token, MonitorData},
+  network::{is_internal, NetworkMode},
+  share::{check_app_permission, get_shared_with_user},
+  short_link::{check_slug, SLUG_REGEX},
+  tag::{get_apps_tags, set_app_tags},
+};
 use crate::errors::AppError;
 
 use actix_web::http::StatusCode;
-use entity::apps;
+use entity::{
+  app_alerts, app_certs, app_checks, app_clicks, app_prefs, app_tags,
+  apps::{self, Monitor},
+  dashboards, groups,
+  maintenances::{self, TargetIds},
+  shares::{self, SharePermission},
+  status_components::{self, ComponentApps},
+  tags, users,
+};
 use sea_orm::{
-  entity::Set, ActiveModelTrait, ColumnTrait, DbConn, DbErr, DeleteResult, EntityTrait,
-  IntoActiveModel, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder,
+  entity::Set, sea_query::CaseStatement, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn,
+  DeleteResult, EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder,
   TransactionTrait,
 };
 use serde::{Deserialize, Serialize};
-use utils::deserialize::str_to_i64;
+use std::{
+  collections::{HashMap, HashSet},
+  net::IpAddr,
+};
+use utils::{
+  deserialize::{
+    double_option, double_option_str_to_i64, option_str_to_i64, option_vec_str_to_i64, str_to_i64,
+  },
+  serialize::vec_i64_to_str,
+};
 use validator::Validate;
 
-pub async fn get_user_all_app(db: &DbConn, user_id: i64) -> Result<Vec<apps::Model>, AppError> {
-  apps::Entity::find()
+// 应用的内网地址与监控配置，只返回给可以管理应用的用户，其他用户使用 open_url 打开应用
+#[derive(Debug, Serialize)]
+pub struct AppManageInfo {
+  pub internal_url: Option<String>,
+  pub monitor: Option<Monitor>,
+}
+
+impl From<&apps::Model> for AppManageInfo {
+  fn from(app: &apps::Model) -> Self {
+    Self {
+      internal_url: app.internal_url.clone(),
+      monitor: app.monitor.clone(),
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ManagedApp {
+  #[serde(flatten)]
+  pub app: apps::Model,
+  #[serde(flatten)]
+  pub manage: AppManageInfo,
+}
+
+impl From<apps::Model> for ManagedApp {
+  fn from(app: apps::Model) -> Self {
+    Self {
+      manage: (&app).into(),
+      app,
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AppInfo {
+  #[serde(flatten)]
+  pub app: apps::Model,
+  #[serde(flatten)]
+  pub manage: Option<AppManageInfo>,
+  pub tags: Vec<tags::Model>,
+  // HTTPS 应用最近一次检查到的证书
+  pub cert: Option<CertInfo>,
+  // 按客户端所在网络选择的打开地址，只在查询面板应用时返回
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub open_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GroupApps {
+  #[serde(flatten)]
+  pub group: groups::Model,
+  pub apps: Vec<AppInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SharedApps {
+  #[serde(flatten)]
+  pub share: shares::Model,
+  pub owner_name: String,
+  pub groups: Vec<GroupApps>,
+  pub apps: Vec<AppInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserApps {
+  pub dashboard: dashboards::Model,
+  pub groups: Vec<GroupApps>,
+  // 未分组的应用
+  pub apps: Vec<AppInfo>,
+  // 其他用户分享给当前用户的应用，只在默认面板中显示
+  pub shared: Vec<SharedApps>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMatchMode {
+  // 包含全部标签
+  And,
+  // 包含任意一个标签
+  #[default]
+  Or,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AppOrder {
+  // 按用户手动调整的顺序
+  #[default]
+  Index,
+  // 按打开次数
+  Frequency,
+  // 按最近打开时间
+  Recency,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetUserAllAppQuery {
+  // 为空时查询默认面板
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  dashboard_id: Option<i64>,
+  // 多个标签名称使用英文逗号分隔
+  tag: Option<String>,
+  mode: Option<TagMatchMode>,
+  order: Option<AppOrder>,
+  // 为空时按客户端地址选择应用的内网或外网地址
+  network: Option<NetworkMode>,
+}
+
+impl GetUserAllAppQuery {
+  fn tag_names(&self) -> Vec<&str> {
+    self
+      .tag
+      .as_deref()
+      .map(|tag| {
+        tag
+          .split(',')
+          .map(|name| name.trim())
+          .filter(|name| !name.is_empty())
+          .collect()
+      })
+      .unwrap_or_default()
+  }
+
+  fn is_match(&self, app_tags: &[tags::Model]) -> bool {
+    let tag_names = self.tag_names();
+    if tag_names.is_empty() {
+      return true;
+    }
+
+    let has_tag = |name: &&str| app_tags.iter().any(|tag| tag.name == *name);
+    match self.mode.unwrap_or_default() {
+      TagMatchMode::And => tag_names.iter().all(has_tag),
+      TagMatchMode::Or => tag_names.iter().any(has_tag),
+    }
+  }
+}
+
+// 将应用按分组归类，未找到对应分组的应用放入未分组中
+fn group_apps(
+  groups: Vec<groups::Model>,
+  apps: Vec<apps::Model>,
+  apps_tags: &mut HashMap<i64, Vec<tags::Model>>,
+  apps_certs: &mut HashMap<i64, CertInfo>,
+  query: &GetUserAllAppQuery,
+  internal: bool,
+  can_manage: impl Fn(&apps::Model) -> bool,
+) -> (Vec<GroupApps>, Vec<AppInfo>) {
+  let mut groups = groups
+    .into_iter()
+    .map(|group| GroupApps {
+      group,
+      apps: Vec::new(),
+    })
+    .collect::<Vec<GroupApps>>();
+  let mut ungrouped_apps = Vec::new();
+
+  for app in apps {
+    let app_info = AppInfo {
+      manage: can_manage(&app).then(|| (&app).into()),
+      tags: apps_tags.remove(&app.id).unwrap_or_default(),
+      cert: apps_certs.remove(&app.id),
+      open_url: Some(app.client_url(internal).to_string()),
+      app,
+    };
+
+    if !query.is_match(&app_info.tags) {
+      continue;
+    }
+
+    let group_apps = app_info
+      .app
+      .group_id
+      .and_then(|group_id| groups.iter_mut().find(|item| item.group.id == group_id));
+
+    match group_apps {
+      Some(group_apps) => group_apps.apps.push(app_info),
+      None => ungrouped_apps.push(app_info),
+    }
+  }
+
+  (groups, ungrouped_apps)
+}
+
+enum ShareScope {
+  Group(i64),
+  Dashboard(i64),
+}
+
+async fn get_share_scope(db: &DbConn, share: &shares::Model) -> Result<ShareScope, AppError> {
+  match (share.group_id, share.dashboard_id) {
+    (Some(group_id), _) => Ok(ShareScope::Group(group_id)),
+    (None, Some(dashboard_id)) => Ok(ShareScope::Dashboard(dashboard_id)),
+    (None, None) => {
+      let dashboard = get_default_dashboard(db, share.owner_id).await?;
+      Ok(ShareScope::Dashboard(dashboard.id))
+    }
+  }
+}
+
+async fn get_shared_apps(
+  db: &DbConn,
+  user_id: i64,
+  query: &GetUserAllAppQuery,
+  internal: bool,
+) -> Result<Vec<SharedApps>, AppError> {
+  let user_shares = get_shared_with_user(db, user_id).await?;
+
+  let mut shared = Vec::new();
+  for share in user_shares {
+    let owner = match users::Entity::find_by_id(share.owner_id).one(db).await? {
+      Some(owner) => owner,
+      None => continue,
+    };
+
+    let mut group_select = groups::Entity::find().filter(groups::Column::OwnerId.eq(owner.id));
+    let mut app_select = apps::Entity::find()
+      .filter(apps::Column::OwnerId.eq(owner.id))
+      .filter(apps::Column::IsGlobal.eq(false));
+
+    match get_share_scope(db, &share).await? {
+      ShareScope::Group(group_id) => {
+        group_select = group_select.filter(groups::Column::Id.eq(group_id));
+        app_select = app_select.filter(apps::Column::GroupId.eq(group_id));
+      }
+      ShareScope::Dashboard(dashboard_id) => {
+        group_select = group_select.filter(groups::Column::DashboardId.eq(dashboard_id));
+        app_select = app_select.filter(apps::Column::DashboardId.eq(dashboard_id));
+      }
+    }
+
+    let owner_groups = group_select
+      .order_by_asc(groups::Column::Index)
+      .all(db)
+      .await?;
+    let mut owner_apps = app_select.order_by_asc(apps::Column::Index).all(db).await?;
+    sort_by_usage(
+      db,
+      user_id,
+      &mut owner_apps,
+      query.order.unwrap_or_default(),
+    )
+    .await?;
+
+    let owner_app_ids = owner_apps.iter().map(|app| app.id).collect::<Vec<i64>>();
+    let mut apps_tags = get_apps_tags(db, owner_app_ids.clone()).await?;
+    let mut apps_certs = get_apps_cert(db, owner_app_ids).await?;
+    // 有编辑权限的用户可以管理分享的应用
+    let can_manage = share.permission == SharePermission::Edit;
+    let (groups, apps) = group_apps(
+      owner_groups,
+      owner_apps,
+      &mut apps_tags,
+      &mut apps_certs,
+      query,
+      internal,
+      |_| can_manage,
+    );
+
+    shared.push(SharedApps {
+      share,
+      owner_name: owner.username,
+      groups,
+      apps,
+    });
+  }
+
+  Ok(shared)
+}
+
+// 查询用户可以访问的全部应用，包括所有面板中的个人应用、全站应用与分享给用户的应用
+pub async fn get_user_visible_app(db: &DbConn, user_id: i64) -> Result<Vec<apps::Model>, AppError> {
+  let user = users::Entity::find_by_id(user_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户不存在"))?;
+
+  let mut visible_apps = apps::Entity::find()
     .filter(apps::Column::OwnerId.eq(user_id))
+    .filter(apps::Column::IsGlobal.eq(false))
     .order_by_asc(apps::Column::Index)
     .all(db)
-    .await
-    .map_err(Into::into)
+    .await?;
+
+  for (app, pref) in get_user_global_app(db, &user).await? {
+    if !pref.is_some_and(|pref| pref.hidden) {
+      visible_apps.push(app);
+    }
+  }
+
+  for share in get_shared_with_user(db, user_id).await? {
+    let app_select = apps::Entity::find()
+      .filter(apps::Column::OwnerId.eq(share.owner_id))
+      .filter(apps::Column::IsGlobal.eq(false));
+
+    let app_select = match get_share_scope(db, &share).await? {
+      ShareScope::Group(group_id) => app_select.filter(apps::Column::GroupId.eq(group_id)),
+      ShareScope::Dashboard(dashboard_id) => {
+        app_select.filter(apps::Column::DashboardId.eq(dashboard_id))
+      }
+    };
+
+    visible_apps.extend(app_select.order_by_asc(apps::Column::Index).all(db).await?);
+  }
+
+  let mut app_ids = HashSet::new();
+  visible_apps.retain(|app| app_ids.insert(app.id));
+
+  Ok(visible_apps)
+}
+
+pub async fn get_user_all_app(
+  db: &DbConn,
+  user_id: i64,
+  query: &GetUserAllAppQuery,
+  client_ip: Option<IpAddr>,
+) -> Result<UserApps, AppError> {
+  let user = users::Entity::find_by_id(user_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户不存在"))?;
+
+  let dashboard = resolve_dashboard(db, user_id, query.dashboard_id).await?;
+  let internal = is_internal(query.network, client_ip);
+
+  let user_groups = get_dashboard_all_group(db, user_id, dashboard.id).await?;
+
+  let mut user_apps = apps::Entity::find()
+    .filter(apps::Column::OwnerId.eq(user_id))
+    .filter(apps::Column::IsGlobal.eq(false))
+    .filter(apps::Column::DashboardId.eq(dashboard.id))
+    .order_by_asc(apps::Column::Index)
+    .all(db)
+    .await?;
+
+  // 全站应用只在默认面板中显示
+  let global_apps = match dashboard.is_default {
+    true => get_user_global_app(db, &user).await?,
+    false => Vec::new(),
+  };
+
+  // 全站应用使用用户自己的排序与分组，未排序过的全站应用排在个人应用之后
+  let next_index = user_apps.iter().map(|app| app.index + 1).max().unwrap_or(0);
+  for (mut app, pref) in global_apps {
+    if pref.as_ref().is_some_and(|pref| pref.hidden) {
+      continue;
+    }
+
+    app.index = pref
+      .as_ref()
+      .and_then(|pref| pref.index)
+      .unwrap_or(next_index + app.index);
+    app.group_id = pref.and_then(|pref| pref.group_id);
+    user_apps.push(app);
+  }
+  user_apps.sort_by_key(|app| app.index);
+  sort_by_usage(db, user_id, &mut user_apps, query.order.unwrap_or_default()).await?;
+
+  let user_app_ids = user_apps.iter().map(|app| app.id).collect::<Vec<i64>>();
+  let mut apps_tags = get_apps_tags(db, user_app_ids.clone()).await?;
+  let mut apps_certs = get_apps_cert(db, user_app_ids).await?;
+  // 全站应用只有管理员可以管理
+  let (groups, apps) = group_apps(
+    user_groups,
+    user_apps,
+    &mut apps_tags,
+    &mut apps_certs,
+    query,
+    internal,
+    |app| !app.is_global || user.is_admin,
+  );
+
+  let shared = match dashboard.is_default {
+    true => get_shared_apps(db, user_id, query, internal).await?,
+    false => Vec::new(),
+  };
+
+  Ok(UserApps {
+    dashboard,
+    groups,
+    apps,
+    shared,
+  })
+}
+
+// 创建、编辑与导入应用时共用的附加信息
+#[derive(Debug, Default, Validate, Serialize, Deserialize)]
+pub struct AppExtraData {
+  // 编辑时缺失则不修改内网地址，为 null 时删除内网地址
+  #[validate(
+    length(min = 1, max = 255, message = "内网 URL 长度不得超过 255 个字符"),
+    url(message = "内网 URL 格式不合法")
+  )]
+  #[serde(
+    default,
+    deserialize_with = "double_option",
+    skip_serializing_if = "Option::is_none"
+  )]
+  pub(super) internal_url: Option<Option<String>>,
+  // 编辑时缺失则不修改短链接，为 null 时删除短链接
+  #[validate(regex(
+    path = "*SLUG_REGEX",
+    message = "短链接必须为 1-50 个小写字母、数字、连字符或下划线，且只能由字母或数字开头"
+  ))]
+  #[serde(
+    default,
+    deserialize_with = "double_option",
+    skip_serializing_if = "Option::is_none"
+  )]
+  pub(super) slug: Option<Option<String>>,
+  // 编辑时缺失则不修改附加链接，为空数组时删除全部附加链接
+  #[validate(length(max = 20, message = "附加链接不得超过 20 个"), nested)]
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(super) links: Option<Vec<AppLinkData>>,
+}
+
+impl AppExtraData {
+  pub(super) fn internal_url(&self) -> Option<&str> {
+    self.internal_url.as_ref().and_then(Option::as_deref)
+  }
+
+  pub(super) fn slug(&self) -> Option<&str> {
+    self.slug.as_ref().and_then(Option::as_deref)
+  }
+
+  pub(super) fn links(&self) -> &[AppLinkData] {
+    self.links.as_deref().unwrap_or_default()
+  }
 }
 
 #[derive(Debug, Validate, Serialize, Deserialize)]
@@ -26,22 +477,47 @@ pub struct CreateAppData {
     length(min = 1, max = 255, message = "应用 URL 长度不得超过 255 个字符"),
     url(message = "应用 URL 格式不合法")
   )]
-  url: String,
+  pub(super) url: String,
   #[validate(length(min = 1, max = 30, message = "应用名称长度不得超过 30 个字符"))]
-  name: String,
+  pub(super) name: String,
   #[validate(length(min = 1, max = 255, message = "应用描述长度不得超过 255 个字符"))]
-  description: Option<String>,
+  pub(super) description: Option<String>,
+  // 图标为远程地址时在创建应用时下载保存
   #[validate(length(min = 1, max = 255, message = "应用图标长度不得超过 255 个字符"))]
-  icon: Option<String>,
+  pub(super) icon: Option<String>,
+  #[validate(nested)]
+  #[serde(flatten)]
+  pub(super) extra: AppExtraData,
+  #[validate(nested)]
+  pub(super) monitor: Option<MonitorData>,
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  pub(super) group_id: Option<i64>,
+  // 为空时创建在默认面板中，指定分组时使用分组所在的面板
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  pub(super) dashboard_id: Option<i64>,
+  // 为空时不修改应用的标签
+  #[serde(default, deserialize_with = "option_vec_str_to_i64")]
+  pub(super) tag_ids: Option<Vec<i64>>,
 }
 
 pub async fn create_app(
   db: &DbConn,
   operator_id: i64,
   data: &CreateAppData,
-) -> Result<apps::Model, AppError> {
+) -> Result<AppInfo, AppError> {
+  let dashboard_id = match data.group_id {
+    Some(group_id) => check_group_owner(db, operator_id, group_id)
+      .await?
+      .dashboard_id,
+    None => None,
+  };
+  let dashboard = resolve_dashboard(db, operator_id, dashboard_id.or(data.dashboard_id)).await?;
+  check_slug(db, operator_id, data.extra.slug(), false, None).await?;
+  let icon = save_remote_icon(operator_id, data.icon.as_deref()).await;
+
   let last_index_app = apps::Entity::find()
     .filter(apps::Column::OwnerId.eq(operator_id))
+    .filter(apps::Column::IsGlobal.eq(false))
     .order_by_desc(apps::Column::Index)
     .one(db)
     .await?;
@@ -51,6 +527,7 @@ pub async fn create_app(
     _ => {
       let app_pages = apps::Entity::find()
         .filter(apps::Column::OwnerId.eq(operator_id))
+        .filter(apps::Column::IsGlobal.eq(false))
         .paginate(db, 1);
 
       let total = app_pages.num_items().await?;
@@ -58,18 +535,48 @@ pub async fn create_app(
     }
   };
 
-  apps::ActiveModel {
+  let txn = db.begin().await?;
+
+  let app = apps::ActiveModel {
     name: Set(data.name.clone()),
     url: Set(data.url.clone()),
-    icon: Set(data.icon.clone()),
+    internal_url: Set(data.extra.internal_url().map(Into::into)),
+    icon: Set(icon),
+    slug: Set(data.extra.slug().map(Into::into)),
     description: Set(data.description.clone()),
+    links: Set(to_app_links(data.extra.links())),
+    monitor: Set(data.monitor.as_ref().map(Into::into)),
+    push_token: Set(ensure_push_token(data.monitor.as_ref(), None)?),
     index: Set(index),
     owner_id: Set(operator_id),
+    group_id: Set(data.group_id),
+    dashboard_id: Set(Some(dashboard.id)),
     ..Default::default()
   }
-  .insert(db)
-  .await
-  .map_err(Into::into)
+  .insert(&txn)
+  .await?;
+
+  if let Some(tag_ids) = &data.tag_ids {
+    set_app_tags(&txn, operator_id, app.id, tag_ids).await?;
+  }
+
+  let tags = get_apps_tags(&txn, vec![app.id])
+    .await?
+    .remove(&app.id)
+    .unwrap_or_default();
+  let cert = get_apps_cert(&txn, vec![app.id]).await?.remove(&app.id);
+
+  txn.commit().await?;
+
+  spawn_discover_icon(db, &app);
+
+  Ok(AppInfo {
+    manage: Some((&app).into()),
+    app,
+    tags,
+    cert,
+    open_url: None,
+  })
 }
 
 #[derive(Debug, Validate, Serialize, Deserialize)]
@@ -87,75 +594,589 @@ pub struct UpdateAppData {
   description: Option<String>,
   #[validate(length(min = 1, max = 255, message = "应用图标长度不得超过 255 个字符"))]
   icon: Option<String>,
+  #[validate(nested)]
+  #[serde(flatten)]
+  extra: AppExtraData,
+  // 缺失时不修改应用的监控配置，为 null 时关闭监控
+  #[validate(nested)]
+  #[serde(default, deserialize_with = "double_option")]
+  monitor: Option<Option<MonitorData>>,
+  // 缺失时不修改应用的分组，为 null 时移动到未分组中
+  #[serde(default, deserialize_with = "double_option_str_to_i64")]
+  group_id: Option<Option<i64>>,
+  // 为空时不修改应用的标签
+  #[serde(default, deserialize_with = "option_vec_str_to_i64")]
+  tag_ids: Option<Vec<i64>>,
 }
 
 pub async fn update_app(
   db: &DbConn,
   operator_id: i64,
   data: &UpdateAppData,
-) -> Result<apps::Model, AppError> {
-  let mut app = apps::Entity::find_by_id(data.id)
-    .filter(apps::Column::OwnerId.eq(operator_id))
-    .one(db)
-    .await?
-    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "应用不存在"))?
-    .into_active_model();
+) -> Result<AppInfo, AppError> {
+  let app = check_app_permission(db, operator_id, data.id, SharePermission::Edit).await?;
+  // 被分享的用户只能修改应用的基本信息，不能修改分组与标签
+  let is_owner = app.owner_id == operator_id;
+
+  let group = match data.group_id.flatten().filter(|_| is_owner) {
+    Some(group_id) => Some(check_group_owner(db, operator_id, group_id).await?),
+    None => None,
+  };
+
+  // 短链接属于应用所有者的命名空间
+  if let Some(slug) = &data.extra.slug {
+    check_slug(db, app.owner_id, slug.as_deref(), false, Some(app.id)).await?;
+  }
+
+  let push_token = match &data.monitor {
+    Some(monitor) => Some(ensure_push_token(monitor.as_ref(), app.push_token.clone())?),
+    None => None,
+  };
+  let mut app = app.into_active_model();
 
   app.name = Set(data.name.clone());
   app.url = Set(data.url.clone());
+  if let Some(internal_url) = &data.extra.internal_url {
+    app.internal_url = Set(internal_url.clone());
+  }
   app.description = Set(data.description.clone());
   app.icon = Set(data.icon.clone());
+  if let Some(slug) = &data.extra.slug {
+    app.slug = Set(slug.clone());
+  }
+  if let Some(links) = &data.extra.links {
+    app.links = Set(to_app_links(links));
+  }
+  if let Some(monitor) = &data.monitor {
+    app.monitor = Set(monitor.as_ref().map(Into::into));
+  }
+  if let Some(push_token) = push_token {
+    app.push_token = Set(push_token);
+  }
+  if let Some(group_id) = data.group_id.filter(|_| is_owner) {
+    app.group_id = Set(group_id);
+  }
+  // 移动到其他面板的分组时，应用也随之移动到该面板
+  if let Some(dashboard_id) = group.and_then(|group| group.dashboard_id) {
+    app.dashboard_id = Set(Some(dashboard_id));
+  }
+
+  let txn = db.begin().await?;
+
+  let app = app.update(&txn).await?;
+
+  if let Some(tag_ids) = data.tag_ids.as_ref().filter(|_| is_owner) {
+    set_app_tags(&txn, operator_id, app.id, tag_ids).await?;
+  }
+
+  let tags = get_apps_tags(&txn, vec![app.id])
+    .await?
+    .remove(&app.id)
+    .unwrap_or_default();
+  let cert = get_apps_cert(&txn, vec![app.id]).await?.remove(&app.id);
+
+  txn.commit().await?;
 
-  app.update(db).await.map_err(Into::into)
+  Ok(AppInfo {
+    manage: Some((&app).into()),
+    app,
+    tags,
+    cert,
+    open_url: None,
+  })
 }
 
+// 每条更新语句最多包含的应用数量
+const SORT_BATCH_SIZE: usize = 200;
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct SortAppData {
   #[serde(deserialize_with = "str_to_i64")]
   id: i64,
+  // 应用排序后所在的分组，为空时移动到未分组中
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  group_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SortAppQuery {
+  // 为空时排序默认面板
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  dashboard_id: Option<i64>,
+}
+
+fn join_ids(ids: &[i64]) -> String {
+  ids
+    .iter()
+    .map(|id| id.to_string())
+    .collect::<Vec<String>>()
+    .join(", ")
+}
+
+// 排序数据与面板不一致的应用与分组，作为错误详情返回给客户端
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SortMismatch {
+  #[serde(serialize_with = "vec_i64_to_str")]
+  duplicate_ids: Vec<i64>,
+  #[serde(serialize_with = "vec_i64_to_str")]
+  unknown_ids: Vec<i64>,
+  #[serde(serialize_with = "vec_i64_to_str")]
+  missing_ids: Vec<i64>,
+  // 不属于当前面板的分组
+  #[serde(serialize_with = "vec_i64_to_str")]
+  unknown_group_ids: Vec<i64>,
+}
+
+impl SortMismatch {
+  fn is_empty(&self) -> bool {
+    self.duplicate_ids.is_empty()
+      && self.unknown_ids.is_empty()
+      && self.missing_ids.is_empty()
+      && self.unknown_group_ids.is_empty()
+  }
+
+  fn message(&self) -> String {
+    let problems = [
+      ("重复的应用", &self.duplicate_ids),
+      ("面板中不存在的应用", &self.unknown_ids),
+      ("缺少的应用", &self.missing_ids),
+      ("面板中不存在的分组", &self.unknown_group_ids),
+    ]
+    .into_iter()
+    .filter(|(_, ids)| !ids.is_empty())
+    .map(|(label, ids)| format!("{}：{}", label, join_ids(ids)))
+    .collect::<Vec<String>>();
+
+    format!("排序的应用与面板中的应用不一致，{}", problems.join("；"))
+  }
 }
 
+// 检查排序的应用是否为面板中应用的一个排列，且分组都属于当前面板
+fn check_sort_permutation(
+  data: &[SortAppData],
+  app_ids: &HashSet<i64>,
+  group_ids: &HashSet<i64>,
+) -> SortMismatch {
+  let mut mismatch = SortMismatch::default();
+
+  let mut seen_ids = HashSet::new();
+  for item in data {
+    if !seen_ids.insert(item.id) {
+      mismatch.duplicate_ids.push(item.id);
+    } else if !app_ids.contains(&item.id) {
+      mismatch.unknown_ids.push(item.id);
+    }
+  }
+  mismatch.missing_ids = app_ids
+    .iter()
+    .filter(|id| !seen_ids.contains(id))
+    .copied()
+    .collect();
+  mismatch.missing_ids.sort();
+
+  mismatch.unknown_group_ids = data
+    .iter()
+    .filter_map(|item| item.group_id)
+    .filter(|group_id| !group_ids.contains(group_id))
+    .collect::<HashSet<i64>>()
+    .into_iter()
+    .collect();
+  mismatch.unknown_group_ids.sort();
+
+  mismatch
+}
+
+// 排序的应用必须与面板中的应用完全一致，包括默认面板中未隐藏的全站应用
 pub async fn sort_app(
   db: &DbConn,
   operator_id: i64,
-  data: &Vec<SortAppData>,
+  query: &SortAppQuery,
+  data: &[SortAppData],
 ) -> Result<(), AppError> {
-  let sort_app_data = data.clone();
-  db.transaction::<_, (), DbErr>(|txn| {
-    Box::pin(async move {
-      for (index, &item) in sort_app_data.iter().enumerate() {
-        let mut app = apps::Entity::find_by_id(item.id)
-          .filter(apps::Column::OwnerId.eq(operator_id))
-          .one(txn)
-          .await?
-          .ok_or(DbErr::Custom("未查找到对应数据".to_string()))?
-          .into_active_model();
-
-        app.index = Set(index as i32);
-
-        app.update(txn).await?;
+  let user = users::Entity::find_by_id(operator_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户不存在"))?;
+  let dashboard = resolve_dashboard(db, operator_id, query.dashboard_id).await?;
+
+  let user_apps = apps::Entity::find()
+    .filter(apps::Column::OwnerId.eq(operator_id))
+    .filter(apps::Column::IsGlobal.eq(false))
+    .filter(apps::Column::DashboardId.eq(dashboard.id))
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|app| (app.id, app))
+    .collect::<HashMap<i64, apps::Model>>();
+  let global_apps = match dashboard.is_default {
+    true => get_user_global_app(db, &user).await?,
+    false => Vec::new(),
+  }
+  .into_iter()
+  .filter(|(_, pref)| !pref.as_ref().is_some_and(|pref| pref.hidden))
+  .map(|(app, pref)| (app.id, pref))
+  .collect::<HashMap<i64, Option<app_prefs::Model>>>();
+
+  let app_ids = user_apps
+    .keys()
+    .chain(global_apps.keys())
+    .copied()
+    .collect::<HashSet<i64>>();
+  let group_ids = get_dashboard_all_group(db, operator_id, dashboard.id)
+    .await?
+    .into_iter()
+    .map(|group| group.id)
+    .collect::<HashSet<i64>>();
+  let mismatch = check_sort_permutation(data, &app_ids, &group_ids);
+  if !mismatch.is_empty() {
+    return Err(
+      AppError::new(StatusCode::UNPROCESSABLE_ENTITY, 422, mismatch.message())
+        .with_details(mismatch),
+    );
+  }
+
+  // 只更新位置或分组发生变化的应用
+  let mut changed_apps = Vec::new();
+  let mut changed_prefs = Vec::new();
+  for (index, item) in data.iter().enumerate() {
+    let index = index as i32;
+    match user_apps.get(&item.id) {
+      Some(app) if app.index != index || app.group_id != item.group_id => {
+        changed_apps.push((item.id, index, item.group_id))
       }
+      Some(_) => {}
+      None => {
+        let pref = global_apps.get(&item.id).and_then(Option::as_ref);
+        if pref.is_none_or(|pref| pref.index != Some(index) || pref.group_id != item.group_id) {
+          changed_prefs.push((item.id, index, item.group_id));
+        }
+      }
+    }
+  }
+
+  let txn = db.begin().await?;
 
-      Ok(())
+  for chunk in changed_apps.chunks(SORT_BATCH_SIZE) {
+    let mut index_case = CaseStatement::new();
+    let mut group_case = CaseStatement::new();
+    for &(app_id, index, group_id) in chunk {
+      index_case = index_case.case(apps::Column::Id.eq(app_id), index);
+      group_case = group_case.case(apps::Column::Id.eq(app_id), group_id);
+    }
+
+    apps::Entity::update_many()
+      .col_expr(apps::Column::Index, index_case.into())
+      .col_expr(apps::Column::GroupId, group_case.into())
+      .filter(apps::Column::Id.is_in(chunk.iter().map(|(app_id, ..)| *app_id)))
+      .exec(&txn)
+      .await?;
+  }
+
+  // 全站应用的排序保存在用户的个人设置中
+  for (app_id, index, group_id) in changed_prefs {
+    save_app_pref(&txn, operator_id, app_id, |pref| {
+      pref.index = Set(Some(index));
+      pref.group_id = Set(group_id);
     })
-  })
-  .await
-  .map_err(AppError::from_err)?;
+    .await?;
+  }
+
+  txn.commit().await?;
 
   Ok(())
 }
 
-pub async fn delete_app(
-  db: &DbConn,
+// 将应用移动到面板或分组中，移动后的应用按给定顺序排在目标面板的最后，调用方负责开启事务
+pub(super) async fn move_apps<C: ConnectionTrait>(
+  db: &C,
   operator_id: i64,
-  app_id: i64,
-) -> Result<DeleteResult, AppError> {
-  apps::Entity::find_by_id(app_id)
+  user_apps: Vec<apps::Model>,
+  dashboard_id: i64,
+  group_id: Option<i64>,
+) -> Result<(), AppError> {
+  let next_index = apps::Entity::find()
     .filter(apps::Column::OwnerId.eq(operator_id))
+    .filter(apps::Column::IsGlobal.eq(false))
+    .filter(apps::Column::DashboardId.eq(dashboard_id))
+    .order_by_desc(apps::Column::Index)
     .one(db)
     .await?
-    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "应用不存在"))?
-    .delete(db)
+    .map_or(0, |app| app.index + 1);
+
+  for (index, app) in user_apps.into_iter().enumerate() {
+    let mut app = app.into_active_model();
+
+    app.dashboard_id = Set(Some(dashboard_id));
+    app.group_id = Set(group_id);
+    app.index = Set(next_index + index as i32);
+
+    app.update(db).await?;
+  }
+
+  Ok(())
+}
+
+// 删除应用及其关联数据，并从状态页组件与维护计划中移除，调用方负责开启事务
+pub(super) async fn delete_apps<C: ConnectionTrait>(
+  db: &C,
+  app_ids: Vec<i64>,
+) -> Result<DeleteResult, AppError> {
+  if app_ids.is_empty() {
+    return Ok(DeleteResult { rows_affected: 0 });
+  }
+
+  app_prefs::Entity::delete_many()
+    .filter(app_prefs::Column::AppId.is_in(app_ids.clone()))
+    .exec(db)
+    .await?;
+
+  app_tags::Entity::delete_many()
+    .filter(app_tags::Column::AppId.is_in(app_ids.clone()))
+    .exec(db)
+    .await?;
+
+  app_checks::Entity::delete_many()
+    .filter(app_checks::Column::AppId.is_in(app_ids.clone()))
+    .exec(db)
+    .await?;
+
+  app_certs::Entity::delete_many()
+    .filter(app_certs::Column::AppId.is_in(app_ids.clone()))
+    .exec(db)
+    .await?;
+
+  app_alerts::Entity::delete_many()
+    .filter(app_alerts::Column::AppId.is_in(app_ids.clone()))
+    .exec(db)
+    .await?;
+
+  app_clicks::Entity::delete_many()
+    .filter(app_clicks::Column::AppId.is_in(app_ids.clone()))
+    .exec(db)
+    .await?;
+
+  // 组件与维护计划中的应用保存在 JSON 中，无法按应用查询，数量较少时逐条更新
+  let deleted_ids = app_ids.iter().copied().collect::<HashSet<i64>>();
+  let is_deleted = |id: &i64| deleted_ids.contains(id);
+
+  for component in status_components::Entity::find().all(db).await? {
+    if !component.app_ids.0.iter().any(is_deleted) {
+      continue;
+    }
+    let remained_ids = component
+      .app_ids
+      .0
+      .iter()
+      .copied()
+      .filter(|id| !is_deleted(id))
+      .collect();
+    let mut component = component.into_active_model();
+    component.app_ids = Set(ComponentApps(remained_ids));
+    component.update(db).await?;
+  }
+
+  for maintenance in maintenances::Entity::find().all(db).await? {
+    if !maintenance.app_ids.0.iter().any(is_deleted) {
+      continue;
+    }
+    let remained_ids = maintenance
+      .app_ids
+      .0
+      .iter()
+      .copied()
+      .filter(|id| !is_deleted(id))
+      .collect();
+    let mut maintenance = maintenance.into_active_model();
+    maintenance.app_ids = Set(TargetIds(remained_ids));
+    maintenance.update(db).await?;
+  }
+
+  apps::Entity::delete_many()
+    .filter(apps::Column::Id.is_in(app_ids))
+    .exec(db)
     .await
     .map_err(Into::into)
 }
+
+pub async fn delete_app(
+  db: &DbConn,
+  operator_id: i64,
+  app_id: i64,
+) -> Result<DeleteResult, AppError> {
+  let app = check_app_permission(db, operator_id, app_id, SharePermission::Edit).await?;
+
+  let txn = db.begin().await?;
+
+  let result = delete_apps(&txn, vec![app.id]).await?;
+
+  txn.commit().await?;
+
+  Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::testing::{self, create_user, setup_db};
+  use chrono::Utc;
+
+  fn sort_data(items: &[(i64, Option<i64>)]) -> Vec<SortAppData> {
+    items
+      .iter()
+      .map(|&(id, group_id)| SortAppData { id, group_id })
+      .collect()
+  }
+
+  #[test]
+  fn sort_permutation_accepts_any_order() {
+    let app_ids = HashSet::from([1, 2, 3]);
+    let group_ids = HashSet::from([10]);
+    let data = sort_data(&[(3, Some(10)), (1, None), (2, Some(10))]);
+    assert!(check_sort_permutation(&data, &app_ids, &group_ids).is_empty());
+    assert!(check_sort_permutation(&[], &HashSet::new(), &HashSet::new()).is_empty());
+  }
+
+  #[test]
+  fn sort_permutation_reports_duplicates() {
+    let app_ids = HashSet::from([1, 2]);
+    let data = sort_data(&[(1, None), (2, None), (1, None), (2, None)]);
+    assert_eq!(
+      check_sort_permutation(&data, &app_ids, &HashSet::new()),
+      SortMismatch {
+        duplicate_ids: vec![1, 2],
+        ..Default::default()
+      }
+    );
+  }
+
+  #[test]
+  fn sort_permutation_reports_unknown_and_missing() {
+    let app_ids = HashSet::from([1, 2, 3, 4]);
+    let data = sort_data(&[(2, None), (9, None), (8, None)]);
+    assert_eq!(
+      check_sort_permutation(&data, &app_ids, &HashSet::new()),
+      SortMismatch {
+        unknown_ids: vec![9, 8],
+        missing_ids: vec![1, 3, 4],
+        ..Default::default()
+      }
+    );
+  }
+
+  #[test]
+  fn sort_permutation_reports_unknown_groups() {
+    let app_ids = HashSet::from([1, 2, 3]);
+    let group_ids = HashSet::from([10]);
+    let data = sort_data(&[(1, Some(12)), (2, Some(10)), (3, Some(11)), (4, Some(12))]);
+    let mismatch = check_sort_permutation(&data, &app_ids, &group_ids);
+    assert_eq!(
+      mismatch,
+      SortMismatch {
+        unknown_ids: vec![4],
+        unknown_group_ids: vec![11, 12],
+        ..Default::default()
+      }
+    );
+    assert_eq!(
+      mismatch.message(),
+      "排序的应用与面板中的应用不一致，面板中不存在的应用：4；面板中不存在的分组：11, 12"
+    );
+  }
+
+  #[test]
+  fn sort_mismatch_serializes_ids_as_strings() {
+    let app_ids = HashSet::from([1, 2]);
+    let data = sort_data(&[(1, None), (1, None), (5, None)]);
+    let mismatch = check_sort_permutation(&data, &app_ids, &HashSet::new());
+    assert_eq!(
+      serde_json::to_value(&mismatch).unwrap(),
+      serde_json::json!({
+        "duplicate_ids": ["1"],
+        "unknown_ids": ["5"],
+        "missing_ids": ["2"],
+        "unknown_group_ids": [],
+      })
+    );
+  }
+
+  #[actix_web::test]
+  async fn delete_apps_removes_them_from_components_and_maintenances() {
+    let db = setup_db().await;
+    let alice = create_user(&db, "alice").await;
+    let first = testing::create_app(&db, alice.id, "Grafana", "https://grafana.example.com").await;
+    let second = testing::create_app(&db, alice.id, "Jellyfin", "https://jf.example.com").await;
+    let component = status_components::ActiveModel {
+      name: Set("监控".to_string()),
+      app_ids: Set(ComponentApps(vec![first.id, second.id])),
+      index: Set(0),
+      ..Default::default()
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+    let maintenance = maintenances::ActiveModel {
+      title: Set("升级".to_string()),
+      app_ids: Set(TargetIds(vec![first.id])),
+      group_ids: Set(TargetIds::default()),
+      starts_at: Set(Utc::now().naive_utc()),
+      enabled: Set(true),
+      owner_id: Set(alice.id),
+      ..Default::default()
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+
+    let result = delete_apps(&db, vec![first.id]).await.unwrap();
+
+    assert_eq!(result.rows_affected, 1);
+    let component = status_components::Entity::find_by_id(component.id)
+      .one(&db)
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(component.app_ids.0, vec![second.id]);
+    let maintenance = maintenances::Entity::find_by_id(maintenance.id)
+      .one(&db)
+      .await
+      .unwrap()
+      .unwrap();
+    assert!(maintenance.app_ids.0.is_empty());
+  }
+
+  #[actix_web::test]
+  async fn update_app_keeps_group_when_group_id_is_omitted() {
+    let db = setup_db().await;
+    let alice = create_user(&db, "alice").await;
+    let app = testing::create_app(&db, alice.id, "Grafana", "https://grafana.example.com").await;
+    let group = groups::ActiveModel {
+      name: Set("监控".to_string()),
+      index: Set(0),
+      owner_id: Set(alice.id),
+      dashboard_id: Set(app.dashboard_id),
+      ..Default::default()
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+
+    let update = |group_id: &str| {
+      serde_json::from_str::<UpdateAppData>(&format!(
+        r#"{{"id": "{}", "url": "https://grafana.example.com", "name": "Grafana"{}}}"#,
+        app.id, group_id
+      ))
+      .unwrap()
+    };
+
+    let data = update(&format!(r#", "group_id": "{}""#, group.id));
+    let info = update_app(&db, alice.id, &data).await.unwrap();
+    assert_eq!(info.app.group_id, Some(group.id));
+
+    let info = update_app(&db, alice.id, &update("")).await.unwrap();
+    assert_eq!(info.app.group_id, Some(group.id));
+
+    let data = update(r#", "group_id": null"#);
+    let info = update_app(&db, alice.id, &data).await.unwrap();
+    assert_eq!(info.app.group_id, None);
+  }
+}
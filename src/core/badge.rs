@@ -0,0 +1,165 @@
+use super::{maintenance::get_apps_maintenance, monitor::check_manage_app};
+use crate::{errors::AppError, settings::SETTINGS};
+
+use actix_web::http::StatusCode;
+use chrono::{Duration as ChronoDuration, Utc};
+use entity::{
+  app_checks::{self, CheckStatus},
+  apps,
+};
+use sea_orm::{
+  entity::Set, ActiveModelTrait, ColumnTrait, DbConn, EntityTrait, IntoActiveModel,
+  PaginatorTrait, QueryFilter, QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use utils::crypto::random_token;
+
+#[derive(Debug, Serialize)]
+pub struct BadgeInfo {
+  pub badge_token: String,
+  pub status_url: String,
+  pub uptime_url: String,
+  pub latency_url: String,
+}
+
+impl From<String> for BadgeInfo {
+  fn from(badge_token: String) -> Self {
+    BadgeInfo {
+      status_url: format!("/badge/{}/status.svg", badge_token),
+      uptime_url: format!("/badge/{}/uptime.svg", badge_token),
+      latency_url: format!("/badge/{}/latency.svg", badge_token),
+      badge_token,
+    }
+  }
+}
+
+// 查询徽章地址，第一次查询时生成徽章令牌
+pub async fn get_badge_info(
+  db: &DbConn,
+  operator_id: i64,
+  app_id: i64,
+) -> Result<BadgeInfo, AppError> {
+  let app = check_manage_app(db, operator_id, app_id).await?;
+
+  if let Some(badge_token) = app.badge_token {
+    return Ok(badge_token.into());
+  }
+
+  let badge_token = random_token(16).map_err(AppError::from_err)?;
+
+  let mut app = app.into_active_model();
+  app.badge_token = Set(Some(badge_token.clone()));
+  app.update(db).await?;
+
+  Ok(badge_token.into())
+}
+
+// 重新生成徽章令牌，已嵌入的旧徽章随即失效
+pub async fn reset_badge_token(
+  db: &DbConn,
+  operator_id: i64,
+  app_id: i64,
+) -> Result<BadgeInfo, AppError> {
+  let app = check_manage_app(db, operator_id, app_id).await?;
+
+  let badge_token = random_token(16).map_err(AppError::from_err)?;
+
+  let mut app = app.into_active_model();
+  app.badge_token = Set(Some(badge_token.clone()));
+  app.update(db).await?;
+
+  Ok(badge_token.into())
+}
+
+async fn get_badge_app(db: &DbConn, badge_token: &str) -> Result<apps::Model, AppError> {
+  apps::Entity::find()
+    .filter(apps::Column::BadgeToken.eq(badge_token))
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "徽章不存在"))
+}
+
+// 应用当前的状态，维护中的应用显示为维护状态，没有检查记录时为空
+pub async fn get_badge_status(
+  db: &DbConn,
+  badge_token: &str,
+) -> Result<Option<CheckStatus>, AppError> {
+  let app = get_badge_app(db, badge_token).await?;
+
+  if !get_apps_maintenance(db, std::slice::from_ref(&app))
+    .await?
+    .is_empty()
+  {
+    return Ok(Some(CheckStatus::Maintenance));
+  }
+
+  Ok(
+    app_checks::Entity::find()
+      .filter(app_checks::Column::AppId.eq(app.id))
+      .order_by_desc(app_checks::Column::CheckedAt)
+      .one(db)
+      .await?
+      .map(|check| check.status),
+  )
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BadgeQuery {
+  // 自定义徽章左侧的文字
+  pub label: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BadgeUptimeQuery {
+  // 统计最近多少天的可用率，默认 30 天
+  days: Option<i64>,
+  // 自定义徽章左侧的文字
+  pub label: Option<String>,
+}
+
+impl BadgeUptimeQuery {
+  pub fn days(&self) -> i64 {
+    self
+      .days
+      .unwrap_or(30)
+      .clamp(1, SETTINGS.monitor.retention_days)
+  }
+}
+
+// 统计时间范围内的可用率，维护期间的检查不计入，没有检查记录时为空
+pub async fn get_badge_uptime(
+  db: &DbConn,
+  badge_token: &str,
+  query: &BadgeUptimeQuery,
+) -> Result<Option<f64>, AppError> {
+  let app = get_badge_app(db, badge_token).await?;
+  let since = Utc::now().naive_utc() - ChronoDuration::days(query.days());
+
+  let count = |status: CheckStatus| {
+    app_checks::Entity::find()
+      .filter(app_checks::Column::AppId.eq(app.id))
+      .filter(app_checks::Column::CheckedAt.gte(since))
+      .filter(app_checks::Column::Status.eq(status))
+      .count(db)
+  };
+  let up_count = count(CheckStatus::Up).await?;
+  let down_count = count(CheckStatus::Down).await?;
+
+  Ok((up_count + down_count > 0).then(|| up_count as f64 / (up_count + down_count) as f64))
+}
+
+// 最近一次正常检查的响应时间，单位毫秒
+pub async fn get_badge_latency(db: &DbConn, badge_token: &str) -> Result<Option<i32>, AppError> {
+  let app = get_badge_app(db, badge_token).await?;
+
+  Ok(
+    app_checks::Entity::find()
+      .filter(app_checks::Column::AppId.eq(app.id))
+      .filter(app_checks::Column::Status.eq(CheckStatus::Up))
+      .filter(app_checks::Column::Latency.is_not_null())
+      .order_by_desc(app_checks::Column::CheckedAt)
+      .one(db)
+      .await?
+      .and_then(|check| check.latency),
+  )
+}
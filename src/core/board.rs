@@ -0,0 +1,779 @@
+use super::{
+  app::{delete_apps, AppExtraData},
+  bookmark::{render_netscape, render_xbel},
+  dashboard::{get_default_dashboard, BackgroundData, LayoutData},
+  file,
+  icon::sniff_extension,
+  import::{ImportApp, ImportGroup, ImportMode, ImportResult},
+  link::{to_app_links, to_links_data},
+  monitor::{ensure_push_token, MonitorData},
+  setting::UpdateSettingData,
+  short_link::check_slug,
+  tag::{get_apps_tags, set_app_tags},
+};
+use crate::{errors::AppError, settings::SETTINGS};
+
+use actix_web::http::StatusCode;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{NaiveDateTime, Utc};
+use entity::{
+  app_tags, apps,
+  dashboards::{self, Background},
+  groups, shares, tags, users,
+};
+use sea_orm::{
+  entity::Set, sea_query::Expr, ActiveModelTrait, ColumnTrait, DatabaseTransaction, DbConn,
+  EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, TransactionTrait, TryIntoModel,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+  collections::{BTreeMap, HashMap, HashSet},
+  fs,
+};
+use utils::yaml;
+use validator::Validate;
+
+// 导出文件的结构版本，结构发生不兼容的变化时递增
+pub const SCHEMA_VERSION: u64 = 1;
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct BoardTag {
+  #[validate(length(min = 1, max = 20, message = "标签名称长度不得超过 20 个字符"))]
+  name: String,
+  #[validate(length(min = 1, max = 20, message = "标签颜色长度不得超过 20 个字符"))]
+  color: Option<String>,
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct BoardApp {
+  #[validate(length(min = 1, max = 30, message = "应用名称长度不得超过 30 个字符"))]
+  pub(super) name: String,
+  #[validate(
+    length(min = 1, max = 255, message = "应用 URL 长度不得超过 255 个字符"),
+    url(message = "应用 URL 格式不合法")
+  )]
+  pub(super) url: String,
+  #[validate(length(min = 1, max = 255, message = "应用描述长度不得超过 255 个字符"))]
+  pub(super) description: Option<String>,
+  #[validate(length(min = 1, max = 255, message = "应用图标长度不得超过 255 个字符"))]
+  pub(super) icon: Option<String>,
+  #[validate(nested)]
+  #[serde(flatten)]
+  pub(super) extra: AppExtraData,
+  #[validate(nested)]
+  pub(super) monitor: Option<MonitorData>,
+  // 标签名称
+  #[serde(default)]
+  pub(super) tags: Vec<String>,
+  // 只在新建应用时使用
+  #[serde(default)]
+  pub(super) created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct BoardGroup {
+  #[validate(length(min = 1, max = 30, message = "分组名称长度不得超过 30 个字符"))]
+  pub(super) name: String,
+  #[validate(length(min = 1, max = 255, message = "分组图标长度不得超过 255 个字符"))]
+  pub(super) icon: Option<String>,
+  #[serde(default)]
+  pub(super) collapsed: bool,
+  #[serde(default)]
+  #[validate(nested)]
+  pub(super) apps: Vec<BoardApp>,
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct BoardDashboard {
+  #[validate(length(min = 1, max = 30, message = "面板名称长度不得超过 30 个字符"))]
+  pub(super) name: String,
+  #[serde(default)]
+  pub(super) is_default: bool,
+  #[validate(nested)]
+  pub(super) layout: Option<LayoutData>,
+  #[validate(nested)]
+  pub(super) background: Option<BackgroundData>,
+  #[serde(default)]
+  #[validate(nested)]
+  pub(super) groups: Vec<BoardGroup>,
+  // 未分组的应用
+  #[serde(default)]
+  #[validate(nested)]
+  pub(super) apps: Vec<BoardApp>,
+}
+
+// 用户面板的导出文件，面板、分组与应用按数组中的顺序排列，不包含数据库 ID
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct Board {
+  pub(super) schema_version: u64,
+  #[serde(default)]
+  pub(super) exported_at: Option<NaiveDateTime>,
+  #[validate(nested)]
+  pub(super) setting: Option<UpdateSettingData>,
+  #[serde(default)]
+  #[validate(nested)]
+  pub(super) tags: Vec<BoardTag>,
+  #[serde(default)]
+  #[validate(nested)]
+  pub(super) dashboards: Vec<BoardDashboard>,
+  // 内嵌的上传文件，键为文件地址，值为 base64 编码的文件内容
+  #[serde(default)]
+  pub(super) files: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+  #[default]
+  Json,
+  Yaml,
+  // 浏览器可以导入的 Netscape 书签 HTML
+  Html,
+  // XML 书签交换格式
+  Xbel,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportBoardQuery {
+  #[serde(default)]
+  format: ExportFormat,
+  // 是否内嵌上传的图标与背景图片，书签格式中只内嵌应用图标
+  #[serde(default)]
+  files: bool,
+}
+
+pub struct ExportedBoard {
+  pub content: String,
+  pub content_type: &'static str,
+  pub filename: String,
+}
+
+// 读取上传的文件，地址不在文件目录中时忽略
+fn read_file(path: &str) -> Option<Vec<u8>> {
+  if path.contains("://") || path.split('/').any(|part| part == ".." || part.is_empty()) {
+    return None;
+  }
+
+  fs::read(SETTINGS.files_dir.join(path)).ok()
+}
+
+fn board_app(app: &apps::Model, app_tags: &HashMap<i64, Vec<tags::Model>>) -> BoardApp {
+  BoardApp {
+    name: app.name.clone(),
+    url: app.url.clone(),
+    description: app.description.clone(),
+    icon: app.icon.clone(),
+    extra: AppExtraData {
+      internal_url: app.internal_url.clone().map(Some),
+      slug: app.slug.clone().map(Some),
+      links: Some(to_links_data(app.links.as_ref())),
+    },
+    monitor: app.monitor.as_ref().map(Into::into),
+    tags: app_tags
+      .get(&app.id)
+      .map(|tags| tags.iter().map(|tag| tag.name.clone()).collect())
+      .unwrap_or_default(),
+    created_at: Some(app.created_at),
+  }
+}
+
+pub async fn export_board(
+  db: &DbConn,
+  operator_id: i64,
+  query: &ExportBoardQuery,
+) -> Result<ExportedBoard, AppError> {
+  let user = users::Entity::find_by_id(operator_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户不存在"))?;
+  let default_dashboard = get_default_dashboard(db, operator_id).await?;
+
+  let user_dashboards = dashboards::Entity::find()
+    .filter(dashboards::Column::OwnerId.eq(operator_id))
+    .order_by_asc(dashboards::Column::Index)
+    .all(db)
+    .await?;
+  let user_groups = groups::Entity::find()
+    .filter(groups::Column::OwnerId.eq(operator_id))
+    .order_by_asc(groups::Column::Index)
+    .all(db)
+    .await?;
+  let user_apps = apps::Entity::find()
+    .filter(apps::Column::OwnerId.eq(operator_id))
+    .filter(apps::Column::IsGlobal.eq(false))
+    .order_by_asc(apps::Column::Index)
+    .all(db)
+    .await?;
+  let user_tags = tags::Entity::find()
+    .filter(tags::Column::OwnerId.eq(operator_id))
+    .order_by_asc(tags::Column::Id)
+    .all(db)
+    .await?;
+  let app_tags = get_apps_tags(db, user_apps.iter().map(|app| app.id).collect()).await?;
+
+  // 未指定面板的分组与应用属于默认面板
+  let dashboard_of = |dashboard_id: Option<i64>| dashboard_id.unwrap_or(default_dashboard.id);
+  let group_ids = user_groups
+    .iter()
+    .map(|group| group.id)
+    .collect::<HashSet<i64>>();
+
+  let dashboards = user_dashboards
+    .iter()
+    .map(|dashboard| BoardDashboard {
+      name: dashboard.name.clone(),
+      is_default: dashboard.is_default,
+      layout: dashboard.layout.as_ref().map(Into::into),
+      background: dashboard.background.as_ref().map(Into::into),
+      groups: user_groups
+        .iter()
+        .filter(|group| dashboard_of(group.dashboard_id) == dashboard.id)
+        .map(|group| BoardGroup {
+          name: group.name.clone(),
+          icon: group.icon.clone(),
+          collapsed: group.collapsed,
+          apps: user_apps
+            .iter()
+            .filter(|app| app.group_id == Some(group.id))
+            .map(|app| board_app(app, &app_tags))
+            .collect(),
+        })
+        .collect(),
+      apps: user_apps
+        .iter()
+        .filter(|app| dashboard_of(app.dashboard_id) == dashboard.id)
+        .filter(|app| {
+          app
+            .group_id
+            .is_none_or(|group_id| !group_ids.contains(&group_id))
+        })
+        .map(|app| board_app(app, &app_tags))
+        .collect(),
+    })
+    .collect::<Vec<BoardDashboard>>();
+
+  let mut files = BTreeMap::new();
+  if query.files {
+    let paths = user
+      .setting
+      .as_ref()
+      .and_then(|setting| setting.bg_image.clone())
+      .into_iter()
+      .chain(
+        user_dashboards
+          .iter()
+          .filter_map(|dashboard| dashboard.background.as_ref()?.bg_image.clone()),
+      )
+      .chain(user_groups.iter().filter_map(|group| group.icon.clone()))
+      .chain(user_apps.iter().filter_map(|app| app.icon.clone()))
+      .chain(
+        user_apps
+          .iter()
+          .flat_map(|app| app.links.iter().flat_map(|links| &links.0))
+          .filter_map(|link| link.icon.clone()),
+      );
+    for path in paths {
+      if let Some(bytes) = read_file(&path) {
+        files.insert(path, STANDARD.encode(bytes));
+      }
+    }
+  }
+
+  let board = Board {
+    schema_version: SCHEMA_VERSION,
+    exported_at: Some(Utc::now().naive_utc()),
+    setting: user.setting.as_ref().map(Into::into),
+    tags: user_tags
+      .into_iter()
+      .map(|tag| BoardTag {
+        name: tag.name,
+        color: tag.color,
+      })
+      .collect(),
+    dashboards,
+    files,
+  };
+
+  let (content, content_type, extension) = match query.format {
+    ExportFormat::Json => (
+      serde_json::to_value(&board)
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .map_err(AppError::from_err)?,
+      "application/json",
+      "json",
+    ),
+    ExportFormat::Yaml => (
+      yaml::to_string(&serde_json::to_value(&board).map_err(AppError::from_err)?)
+        .map_err(AppError::from_err)?,
+      "application/yaml",
+      "yaml",
+    ),
+    ExportFormat::Html => (render_netscape(&board), "text/html; charset=utf-8", "html"),
+    ExportFormat::Xbel => (
+      render_xbel(&board),
+      "application/xml; charset=utf-8",
+      "xbel",
+    ),
+  };
+
+  Ok(ExportedBoard {
+    content,
+    content_type,
+    filename: format!(
+      "dash-{}-{}.{}",
+      user.username,
+      Utc::now().format("%Y%m%d"),
+      extension
+    ),
+  })
+}
+
+fn invalid_board<T: std::fmt::Display>(err: T) -> AppError {
+  AppError::new(
+    StatusCode::UNPROCESSABLE_ENTITY,
+    422,
+    format!("导入内容格式不正确：{}", err),
+  )
+}
+
+// 先检查结构版本，再按当前版本解析，未知的字段会被忽略
+fn parse_board(content: &str) -> Result<Board, AppError> {
+  let value = match serde_json::from_str::<Value>(content) {
+    Ok(value) => value,
+    Err(_) => yaml::from_str(content).map_err(invalid_board)?,
+  };
+
+  let schema_version = value
+    .get("schema_version")
+    .and_then(Value::as_u64)
+    .ok_or_else(|| invalid_board("缺少 schema_version"))?;
+  if schema_version > SCHEMA_VERSION {
+    return Err(AppError::new(
+      StatusCode::UNPROCESSABLE_ENTITY,
+      422,
+      format!(
+        "导入文件的结构版本为 {}，当前只支持到版本 {}，请升级后再导入",
+        schema_version, SCHEMA_VERSION
+      ),
+    ));
+  }
+
+  serde_json::from_value::<Board>(value).map_err(invalid_board)
+}
+
+fn preview_app(app: &BoardApp) -> ImportApp {
+  ImportApp {
+    name: app.name.clone(),
+    url: app.url.clone(),
+    description: app.description.clone(),
+    icon_url: app.icon.clone(),
+    icon_data: None,
+    tags: app.tags.clone(),
+    created_at: app.created_at,
+  }
+}
+
+// 导入过程中的状态，已匹配的数据不会被再次匹配，替换模式下未匹配的数据会被删除
+struct BoardImporter<'a> {
+  txn: &'a DatabaseTransaction,
+  operator_id: i64,
+  // 原文件地址与导入后的文件地址
+  files: HashMap<String, String>,
+  tag_ids: HashMap<String, i64>,
+  groups: Vec<groups::Model>,
+  apps: Vec<apps::Model>,
+  kept_group_ids: HashSet<i64>,
+  kept_app_ids: HashSet<i64>,
+  mode: ImportMode,
+  // 每个面板与分组中下一个应用的序号
+  app_indexes: HashMap<(i64, Option<i64>), i32>,
+}
+
+impl BoardImporter<'_> {
+  fn file(&self, path: &Option<String>) -> Option<String> {
+    path
+      .as_ref()
+      .map(|path| self.files.get(path).unwrap_or(path).clone())
+  }
+
+  async fn import_app(
+    &mut self,
+    app: &BoardApp,
+    dashboard_id: i64,
+    group_id: Option<i64>,
+  ) -> Result<(), AppError> {
+    // 合并模式下排在分组中已有应用之后，替换模式下已有应用会被重新排序或删除
+    let index = self
+      .app_indexes
+      .entry((dashboard_id, group_id))
+      .or_insert_with(|| match self.mode {
+        ImportMode::Merge => self
+          .apps
+          .iter()
+          .filter(|model| model.dashboard_id == Some(dashboard_id) && model.group_id == group_id)
+          .map(|model| model.index + 1)
+          .max()
+          .unwrap_or(0),
+        ImportMode::Replace => 0,
+      });
+    let index = std::mem::replace(index, *index + 1);
+
+    // 按 URL 匹配已有的应用
+    let existing = self
+      .apps
+      .iter()
+      .find(|model| model.url == app.url && !self.kept_app_ids.contains(&model.id))
+      .cloned();
+    let push_token = ensure_push_token(
+      app.monitor.as_ref(),
+      existing.as_ref().and_then(|model| model.push_token.clone()),
+    )?;
+    // 短链接已被其他应用使用时不导入短链接
+    let app_id = existing.as_ref().map(|model| model.id);
+    let slug = match check_slug(self.txn, self.operator_id, app.extra.slug(), false, app_id).await {
+      Ok(()) => app.extra.slug().map(Into::into),
+      Err(err) if err.code == 409 => None,
+      Err(err) => return Err(err),
+    };
+
+    let mut model = match existing {
+      Some(model) => model.into_active_model(),
+      None => {
+        let mut model = apps::ActiveModel {
+          owner_id: Set(self.operator_id),
+          is_global: Set(false),
+          ..Default::default()
+        };
+        if let Some(created_at) = app.created_at {
+          model.created_at = Set(created_at);
+        }
+        model
+      }
+    };
+    model.name = Set(app.name.clone());
+    model.url = Set(app.url.clone());
+    model.internal_url = Set(app.extra.internal_url().map(Into::into));
+    model.description = Set(app.description.clone());
+    model.icon = Set(self.file(&app.icon));
+    model.slug = Set(slug);
+    model.links = Set(to_app_links(app.extra.links()).map(|mut links| {
+      for link in &mut links.0 {
+        link.icon = self.file(&link.icon);
+      }
+      links
+    }));
+    model.monitor = Set(app.monitor.as_ref().map(Into::into));
+    model.push_token = Set(push_token);
+    model.index = Set(index);
+    model.group_id = Set(group_id);
+    model.dashboard_id = Set(Some(dashboard_id));
+    let model = model.save(self.txn).await?.try_into_model()?;
+
+    let tag_ids = app
+      .tags
+      .iter()
+      .filter_map(|name| self.tag_ids.get(name).copied())
+      .collect::<Vec<i64>>();
+    set_app_tags(self.txn, self.operator_id, model.id, &tag_ids).await?;
+
+    self.kept_app_ids.insert(model.id);
+    Ok(())
+  }
+
+  async fn import_group(
+    &mut self,
+    group: &BoardGroup,
+    dashboard: &dashboards::Model,
+    index: i32,
+  ) -> Result<(), AppError> {
+    // 按名称匹配面板中已有的分组
+    let existing = self
+      .groups
+      .iter()
+      .find(|model| {
+        model.name == group.name
+          && !self.kept_group_ids.contains(&model.id)
+          && (model.dashboard_id == Some(dashboard.id)
+            || (model.dashboard_id.is_none() && dashboard.is_default))
+      })
+      .cloned();
+
+    let mut model = match existing {
+      Some(model) => model.into_active_model(),
+      None => groups::ActiveModel {
+        owner_id: Set(self.operator_id),
+        ..Default::default()
+      },
+    };
+    model.name = Set(group.name.clone());
+    model.icon = Set(self.file(&group.icon));
+    model.collapsed = Set(group.collapsed);
+    model.index = Set(index);
+    model.dashboard_id = Set(Some(dashboard.id));
+    let model = model.save(self.txn).await?.try_into_model()?;
+    self.kept_group_ids.insert(model.id);
+
+    for app in &group.apps {
+      self.import_app(app, dashboard.id, Some(model.id)).await?;
+    }
+
+    Ok(())
+  }
+}
+
+// 导入面板导出文件，按名称匹配面板、分组与标签，按 URL 匹配应用
+pub(super) async fn import_board(
+  db: &DbConn,
+  operator_id: i64,
+  content: &str,
+  mode: ImportMode,
+  dry_run: bool,
+) -> Result<ImportResult, AppError> {
+  let board = parse_board(content)?;
+  board.validate()?;
+
+  let groups = board
+    .dashboards
+    .iter()
+    .flat_map(|dashboard| {
+      let ungrouped = ImportGroup {
+        dashboard: Some(dashboard.name.clone()),
+        name: None,
+        apps: dashboard.apps.iter().map(preview_app).collect(),
+      };
+      dashboard
+        .groups
+        .iter()
+        .map(|group| ImportGroup {
+          dashboard: Some(dashboard.name.clone()),
+          name: Some(group.name.clone()),
+          apps: group.apps.iter().map(preview_app).collect(),
+        })
+        .chain((!ungrouped.apps.is_empty()).then_some(ungrouped))
+    })
+    .collect::<Vec<ImportGroup>>();
+  let result = ImportResult {
+    dry_run,
+    group_count: groups.iter().filter(|group| group.name.is_some()).count(),
+    app_count: groups.iter().map(|group| group.apps.len()).sum(),
+    icon_failed_count: 0,
+    groups,
+    skipped: Vec::new(),
+  };
+  if dry_run {
+    return Ok(result);
+  }
+
+  // 文件路径来自导入内容，按文件头判断格式，全部校验通过后再保存
+  let mut decoded_files = Vec::new();
+  for (path, content) in &board.files {
+    let bytes = STANDARD
+      .decode(content)
+      .map_err(|_| invalid_board(format!("文件 {} 不是有效的 base64 编码", path)))?;
+    let extension = sniff_extension(&bytes)
+      .ok_or_else(|| invalid_board(format!("文件 {} 的格式不受支持", path)))?;
+    decoded_files.push((path, bytes, extension));
+  }
+  let mut files = HashMap::new();
+  for (path, bytes, extension) in decoded_files {
+    let file_type = match path.split('/').next() {
+      Some("icon") => "icon",
+      _ => "image",
+    };
+    files.insert(
+      path.clone(),
+      file::save(operator_id, &bytes, file_type, extension)?,
+    );
+  }
+
+  // 导入失败时删除已保存的文件
+  let saved_files = files.values().cloned().collect::<Vec<String>>();
+  async {
+    let txn = db.begin().await?;
+    save_board(&txn, operator_id, &board, files, mode).await?;
+    txn.commit().await?;
+    Ok::<_, AppError>(())
+  }
+  .await
+  .inspect_err(|_| file::remove(&saved_files))?;
+
+  Ok(result)
+}
+
+// 在事务中保存导入的面板，文件已提前保存，files 为原文件地址与导入后的文件地址
+async fn save_board(
+  txn: &DatabaseTransaction,
+  operator_id: i64,
+  board: &Board,
+  files: HashMap<String, String>,
+  mode: ImportMode,
+) -> Result<(), AppError> {
+  let default_dashboard = get_default_dashboard(txn, operator_id).await?;
+
+  if let Some(setting) = &board.setting {
+    let mut setting = users::Setting::from(setting);
+    setting.bg_image = setting
+      .bg_image
+      .map(|path| files.get(&path).cloned().unwrap_or(path));
+
+    users::Entity::update_many()
+      .col_expr(users::Column::Setting, Expr::value(setting))
+      .filter(users::Column::Id.eq(operator_id))
+      .exec(txn)
+      .await?;
+  }
+
+  let mut tag_ids = HashMap::new();
+  let user_tags = tags::Entity::find()
+    .filter(tags::Column::OwnerId.eq(operator_id))
+    .all(txn)
+    .await?;
+  for tag in &board.tags {
+    let mut model = match user_tags.iter().find(|model| model.name == tag.name) {
+      Some(model) => model.clone().into_active_model(),
+      None => tags::ActiveModel {
+        name: Set(tag.name.clone()),
+        owner_id: Set(operator_id),
+        ..Default::default()
+      },
+    };
+    model.color = Set(tag.color.clone());
+    let model = model.save(txn).await?.try_into_model()?;
+    tag_ids.insert(model.name, model.id);
+  }
+  // 应用引用了文件中未列出的标签时，使用同名的已有标签
+  for tag in &user_tags {
+    tag_ids.entry(tag.name.clone()).or_insert(tag.id);
+  }
+
+  let user_dashboards = dashboards::Entity::find()
+    .filter(dashboards::Column::OwnerId.eq(operator_id))
+    .all(txn)
+    .await?;
+
+  let mut importer = BoardImporter {
+    txn,
+    operator_id,
+    files,
+    tag_ids,
+    groups: groups::Entity::find()
+      .filter(groups::Column::OwnerId.eq(operator_id))
+      .all(txn)
+      .await?,
+    apps: apps::Entity::find()
+      .filter(apps::Column::OwnerId.eq(operator_id))
+      .filter(apps::Column::IsGlobal.eq(false))
+      .all(txn)
+      .await?,
+    kept_group_ids: HashSet::new(),
+    kept_app_ids: HashSet::new(),
+    mode,
+    app_indexes: HashMap::new(),
+  };
+
+  let mut kept_dashboard_ids = HashSet::new();
+  let mut group_index = 0;
+  for (index, dashboard) in board.dashboards.iter().enumerate() {
+    // 默认面板对应用户的默认面板，其他面板按名称匹配
+    let existing = match dashboard.is_default && !kept_dashboard_ids.contains(&default_dashboard.id)
+    {
+      true => Some(default_dashboard.clone()),
+      false => user_dashboards
+        .iter()
+        .find(|model| {
+          model.name == dashboard.name
+            && !model.is_default
+            && !kept_dashboard_ids.contains(&model.id)
+        })
+        .cloned(),
+    };
+
+    let mut model = match existing {
+      Some(model) => model.into_active_model(),
+      None => dashboards::ActiveModel {
+        is_default: Set(false),
+        owner_id: Set(operator_id),
+        ..Default::default()
+      },
+    };
+    model.name = Set(dashboard.name.clone());
+    model.index = Set(index as i32);
+    model.layout = Set(dashboard.layout.as_ref().map(Into::into));
+    model.background = Set(dashboard.background.as_ref().map(|background| {
+      let background = Background::from(background);
+      Background {
+        bg_image: importer.file(&background.bg_image),
+        ..background
+      }
+    }));
+    let model = model.save(txn).await?.try_into_model()?;
+    kept_dashboard_ids.insert(model.id);
+
+    for group in &dashboard.groups {
+      importer.import_group(group, &model, group_index).await?;
+      group_index += 1;
+    }
+    for app in &dashboard.apps {
+      importer.import_app(app, model.id, None).await?;
+    }
+  }
+
+  if let ImportMode::Replace = mode {
+    let removed_app_ids = importer
+      .apps
+      .iter()
+      .map(|app| app.id)
+      .filter(|id| !importer.kept_app_ids.contains(id))
+      .collect::<Vec<i64>>();
+    let removed_group_ids = importer
+      .groups
+      .iter()
+      .map(|group| group.id)
+      .filter(|id| !importer.kept_group_ids.contains(id))
+      .collect::<Vec<i64>>();
+    let removed_dashboard_ids = user_dashboards
+      .iter()
+      .filter(|dashboard| !dashboard.is_default && !kept_dashboard_ids.contains(&dashboard.id))
+      .map(|dashboard| dashboard.id)
+      .collect::<Vec<i64>>();
+    let removed_tag_ids = user_tags
+      .iter()
+      .filter(|tag| {
+        board
+          .tags
+          .iter()
+          .all(|board_tag| board_tag.name != tag.name)
+      })
+      .map(|tag| tag.id)
+      .collect::<Vec<i64>>();
+
+    delete_apps(txn, removed_app_ids).await?;
+    app_tags::Entity::delete_many()
+      .filter(app_tags::Column::TagId.is_in(removed_tag_ids.clone()))
+      .exec(txn)
+      .await?;
+    shares::Entity::delete_many()
+      .filter(shares::Column::GroupId.is_in(removed_group_ids.clone()))
+      .exec(txn)
+      .await?;
+    groups::Entity::delete_many()
+      .filter(groups::Column::Id.is_in(removed_group_ids))
+      .exec(txn)
+      .await?;
+    shares::Entity::delete_many()
+      .filter(shares::Column::DashboardId.is_in(removed_dashboard_ids.clone()))
+      .exec(txn)
+      .await?;
+    dashboards::Entity::delete_many()
+      .filter(dashboards::Column::Id.is_in(removed_dashboard_ids))
+      .exec(txn)
+      .await?;
+    tags::Entity::delete_many()
+      .filter(tags::Column::Id.is_in(removed_tag_ids))
+      .exec(txn)
+      .await?;
+  }
+
+  Ok(())
+}
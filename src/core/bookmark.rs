@@ -0,0 +1,440 @@
+use super::{
+  board::{Board, BoardApp},
+  import::{FolderMode, RawApp, RawGroup},
+};
+
+use chrono::{DateTime, NaiveDateTime};
+use scraper::{ElementRef, Html, Selector};
+use std::fmt::Write;
+use utils::html::escape;
+
+// 书签的添加时间为 Unix 时间戳，部分浏览器使用毫秒或微秒
+fn parse_add_date(add_date: &str) -> Option<NaiveDateTime> {
+  let timestamp = add_date.trim().parse::<i64>().ok()?;
+  let seconds = match timestamp {
+    timestamp if timestamp > 100_000_000_000_000 => timestamp / 1_000_000,
+    timestamp if timestamp > 100_000_000_000 => timestamp / 1_000,
+    timestamp => timestamp,
+  };
+
+  DateTime::from_timestamp(seconds, 0).map(|time| time.naive_utc())
+}
+
+fn text(element: ElementRef) -> String {
+  element.text().collect::<String>()
+}
+
+struct BookmarkParser {
+  folders: FolderMode,
+  groups: Vec<RawGroup>,
+  // 文件夹模式为标签时，全部书签放在同一个未分组的列表中
+  ungrouped: Vec<RawApp>,
+}
+
+impl BookmarkParser {
+  // 书签按出现的顺序放入所在文件夹的分组，或未分组的列表中
+  fn apps(&mut self, group_index: usize) -> &mut Vec<RawApp> {
+    match self.folders {
+      FolderMode::Group => &mut self.groups[group_index].apps,
+      FolderMode::Tag => &mut self.ungrouped,
+    }
+  }
+
+  // 解析 <DL> 列表，列表中的 <DT> 为书签或文件夹，<DD> 为上一个书签的描述
+  fn parse_list(&mut self, list: ElementRef, path: &mut Vec<String>) {
+    // 先占位，保证上级文件夹排在子文件夹之前
+    let group_index = self.groups.len();
+    if let FolderMode::Group = self.folders {
+      self.groups.push(RawGroup {
+        name: path.last().cloned(),
+        apps: Vec::new(),
+      });
+    }
+    // 部分解析结果中，文件夹的 <DL> 是 <DT> 的兄弟节点
+    let mut pending_folder = None;
+
+    for child in list.child_elements() {
+      match child.value().name() {
+        "dt" => {
+          for item in child.child_elements() {
+            match item.value().name() {
+              "a" => {
+                let element = item.value();
+                let mut tags = match self.folders {
+                  FolderMode::Tag => path.clone(),
+                  FolderMode::Group => Vec::new(),
+                };
+                tags.extend(
+                  element
+                    .attr("tags")
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(String::from),
+                );
+
+                self.apps(group_index).push(RawApp {
+                  name: Some(text(item)),
+                  url: element.attr("href").map(String::from),
+                  description: None,
+                  icon: element
+                    .attr("icon")
+                    .or(element.attr("icon_uri"))
+                    .map(String::from),
+                  tags,
+                  created_at: element.attr("add_date").and_then(parse_add_date),
+                });
+              }
+              "h3" => pending_folder = Some(text(item)),
+              "dl" => {
+                if let Some(folder) = pending_folder.take() {
+                  self.parse_folder(item, folder, path);
+                }
+              }
+              _ => {}
+            }
+          }
+        }
+        "dd" => {
+          if let Some(app) = self.apps(group_index).last_mut() {
+            app.description = Some(text(child));
+          }
+        }
+        "dl" => {
+          if let Some(folder) = pending_folder.take() {
+            self.parse_folder(child, folder, path);
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  fn parse_folder(&mut self, list: ElementRef, folder: String, path: &mut Vec<String>) {
+    path.push(folder);
+    self.parse_list(list, path);
+    path.pop();
+  }
+}
+
+// 解析浏览器导出的书签，嵌套的文件夹按最内层的文件夹名称分组
+pub(super) fn parse_bookmarks(content: &str, folders: FolderMode) -> Vec<RawGroup> {
+  let document = Html::parse_document(content);
+  let selector = Selector::parse("dl").unwrap();
+
+  let mut parser = BookmarkParser {
+    folders,
+    groups: Vec::new(),
+    ungrouped: Vec::new(),
+  };
+  if let Some(list) = document.select(&selector).next() {
+    parser.parse_list(list, &mut Vec::new());
+  }
+
+  if !parser.ungrouped.is_empty() {
+    parser.groups.push(RawGroup {
+      name: None,
+      apps: parser.ungrouped,
+    });
+  }
+
+  parser.groups
+}
+
+// 根据扩展名生成内嵌图标的 data URI
+fn icon_data_uri(board: &Board, icon: &str) -> Option<String> {
+  let data = board.files.get(icon)?;
+  let mime_type = match icon.rsplit_once('.')?.1.to_ascii_lowercase().as_str() {
+    "png" => "image/png",
+    "jpg" | "jpeg" => "image/jpeg",
+    "gif" => "image/gif",
+    "ico" => "image/x-icon",
+    "webp" => "image/webp",
+    _ => return None,
+  };
+
+  Some(format!("data:{};base64,{}", mime_type, data))
+}
+
+fn write_netscape_app(output: &mut String, board: &Board, app: &BoardApp, indent: usize) {
+  let indent = "    ".repeat(indent);
+  let _ = write!(output, r#"{}<DT><A HREF="{}""#, indent, escape(&app.url));
+  if let Some(created_at) = app.created_at {
+    let _ = write!(
+      output,
+      r#" ADD_DATE="{}""#,
+      created_at.and_utc().timestamp()
+    );
+  }
+  if let Some(icon) = app
+    .icon
+    .as_deref()
+    .and_then(|icon| icon_data_uri(board, icon))
+  {
+    let _ = write!(output, r#" ICON="{}""#, icon);
+  }
+  if !app.tags.is_empty() {
+    let _ = write!(output, r#" TAGS="{}""#, escape(&app.tags.join(",")));
+  }
+  let _ = writeln!(output, ">{}</A>", escape(&app.name));
+  if let Some(description) = &app.description {
+    let _ = writeln!(output, "{}<DD>{}", indent, escape(description));
+  }
+}
+
+// 导出为 Netscape 书签格式，面板与分组导出为文件夹
+pub(super) fn render_netscape(board: &Board) -> String {
+  let mut output = String::from(
+    r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<!-- This is an automatically generated file.
+     It will be read and overwritten.
+     DO NOT EDIT! -->
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+"#,
+  );
+
+  for dashboard in &board.dashboards {
+    let _ = writeln!(output, "    <DT><H3>{}</H3>", escape(&dashboard.name));
+    output.push_str("    <DL><p>\n");
+    for group in &dashboard.groups {
+      let _ = writeln!(output, "        <DT><H3>{}</H3>", escape(&group.name));
+      output.push_str("        <DL><p>\n");
+      for app in &group.apps {
+        write_netscape_app(&mut output, board, app, 3);
+      }
+      output.push_str("        </DL><p>\n");
+    }
+    for app in &dashboard.apps {
+      write_netscape_app(&mut output, board, app, 2);
+    }
+    output.push_str("    </DL><p>\n");
+  }
+
+  output.push_str("</DL><p>\n");
+  output
+}
+
+fn write_xbel_app(output: &mut String, app: &BoardApp, indent: usize) {
+  let indent = "  ".repeat(indent);
+  let _ = write!(output, r#"{}<bookmark href="{}""#, indent, escape(&app.url));
+  if let Some(created_at) = app.created_at {
+    let _ = write!(
+      output,
+      r#" added="{}""#,
+      created_at.and_utc().format("%Y-%m-%dT%H:%M:%SZ")
+    );
+  }
+  let _ = writeln!(output, ">");
+  let _ = writeln!(output, "{}  <title>{}</title>", indent, escape(&app.name));
+  if let Some(description) = &app.description {
+    let _ = writeln!(output, "{}  <desc>{}</desc>", indent, escape(description));
+  }
+  let _ = writeln!(output, "{}</bookmark>", indent);
+}
+
+// 导出为 XBEL 书签格式，面板与分组导出为文件夹
+pub(super) fn render_xbel(board: &Board) -> String {
+  let mut output = String::from(
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE xbel PUBLIC "+//IDN python.org//DTD XML Bookmark Exchange Language 1.0//EN//XML" "http://pyxml.sourceforge.net/topics/dtds/xbel.dtd">
+<xbel version="1.0">
+"#,
+  );
+
+  for dashboard in &board.dashboards {
+    output.push_str("  <folder>\n");
+    let _ = writeln!(output, "    <title>{}</title>", escape(&dashboard.name));
+    for group in &dashboard.groups {
+      output.push_str("    <folder>\n");
+      let _ = writeln!(output, "      <title>{}</title>", escape(&group.name));
+      for app in &group.apps {
+        write_xbel_app(&mut output, app, 3);
+      }
+      output.push_str("    </folder>\n");
+    }
+    for app in &dashboard.apps {
+      write_xbel_app(&mut output, app, 2);
+    }
+    output.push_str("  </folder>\n");
+  }
+
+  output.push_str("</xbel>\n");
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::NaiveDate;
+
+  const BOOKMARKS: &str = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+    <DT><A HREF="https://example.com" ADD_DATE="1700000000">Example</A>
+    <DD>示例网站
+    <DT><H3>工具</H3>
+    <DL><p>
+        <DT><A HREF="https://grafana.example.com" TAGS="监控,运维">Grafana</A>
+        <DT><H3>媒体</H3>
+        <DL><p>
+            <DT><A HREF="https://jf.example.com" ICON="data:image/png;base64,AAAA">Jellyfin</A>
+        </DL><p>
+    </DL><p>
+    <DT><H3>文档</H3></DT>
+    <DL><p>
+        <DT><A HREF="https://docs.example.com">Docs</A>
+    </DL><p>
+</DL><p>
+"#;
+
+  fn non_empty(groups: &[RawGroup]) -> Vec<(Option<&str>, Vec<&str>)> {
+    groups
+      .iter()
+      .filter(|group| !group.apps.is_empty())
+      .map(|group| {
+        (
+          group.name.as_deref(),
+          group
+            .apps
+            .iter()
+            .map(|app| app.name.as_deref().unwrap_or_default())
+            .collect(),
+        )
+      })
+      .collect()
+  }
+
+  // 描述中的空白在导入时统一处理
+  fn description(app: &RawApp) -> Option<&str> {
+    app.description.as_deref().map(str::trim)
+  }
+
+  fn tags(app: &RawApp) -> Vec<&str> {
+    app
+      .tags
+      .iter()
+      .map(String::as_str)
+      .filter(|tag| !tag.is_empty())
+      .collect()
+  }
+
+  #[test]
+  fn parse_add_date_accepts_seconds_milliseconds_and_microseconds() {
+    let expected = DateTime::from_timestamp(1_700_000_000, 0).map(|time| time.naive_utc());
+    assert_eq!(parse_add_date("1700000000"), expected);
+    assert_eq!(parse_add_date("1700000000123"), expected);
+    assert_eq!(parse_add_date("1700000000123456"), expected);
+    assert_eq!(parse_add_date(" 1700000000 "), expected);
+    assert_eq!(parse_add_date("yesterday"), None);
+  }
+
+  #[test]
+  fn parse_bookmarks_groups_by_innermost_folder() {
+    let groups = parse_bookmarks(BOOKMARKS, FolderMode::Group);
+
+    assert_eq!(
+      non_empty(&groups),
+      vec![
+        (None, vec!["Example"]),
+        (Some("工具"), vec!["Grafana"]),
+        (Some("媒体"), vec!["Jellyfin"]),
+        // <DL> 是 <DT> 的兄弟节点
+        (Some("文档"), vec!["Docs"]),
+      ]
+    );
+    let example = &groups[0].apps[0];
+    assert_eq!(example.url.as_deref(), Some("https://example.com"));
+    assert_eq!(description(example), Some("示例网站"));
+    assert!(example.created_at.is_some());
+    let grafana = &groups[1].apps[0];
+    assert_eq!(tags(grafana), vec!["监控", "运维"]);
+    assert_eq!(grafana.description, None);
+    assert_eq!(
+      groups[2].apps[0].icon.as_deref(),
+      Some("data:image/png;base64,AAAA")
+    );
+  }
+
+  #[test]
+  fn parse_bookmarks_uses_folders_as_tags() {
+    let groups = parse_bookmarks(BOOKMARKS, FolderMode::Tag);
+
+    assert_eq!(
+      non_empty(&groups),
+      vec![(None, vec!["Example", "Grafana", "Jellyfin", "Docs"])]
+    );
+    let apps = &groups[0].apps;
+    assert!(tags(&apps[0]).is_empty());
+    assert_eq!(tags(&apps[1]), vec!["工具", "监控", "运维"]);
+    assert_eq!(tags(&apps[2]), vec!["工具", "媒体"]);
+    assert_eq!(tags(&apps[3]), vec!["文档"]);
+    assert_eq!(description(&apps[0]), Some("示例网站"));
+  }
+
+  fn board() -> Board {
+    serde_json::from_value(serde_json::json!({
+      "schema_version": 1,
+      "dashboards": [{
+        "name": "主页",
+        "groups": [{
+          "name": "监控 & 运维",
+          "apps": [{
+            "name": "Grafana <prod>",
+            "url": "https://grafana.example.com/?a=1&b=2",
+            "description": "指标 \"仪表盘\"",
+            "icon": "/uploads/grafana.png",
+            "tags": ["监控", "运维"],
+            "created_at": "2026-10-19T08:00:00"
+          }]
+        }],
+        "apps": [{ "name": "Example", "url": "https://example.com" }]
+      }],
+      "files": { "/uploads/grafana.png": "AAAA" }
+    }))
+    .unwrap()
+  }
+
+  #[test]
+  fn render_netscape_round_trips_through_parse_bookmarks() {
+    let groups = parse_bookmarks(&render_netscape(&board()), FolderMode::Group);
+
+    assert_eq!(
+      non_empty(&groups),
+      vec![
+        (Some("主页"), vec!["Example"]),
+        (Some("监控 & 运维"), vec!["Grafana <prod>"]),
+      ]
+    );
+    let grafana = &groups[2].apps[0];
+    assert_eq!(
+      grafana.url.as_deref(),
+      Some("https://grafana.example.com/?a=1&b=2")
+    );
+    assert_eq!(description(grafana), Some("指标 \"仪表盘\""));
+    assert_eq!(grafana.icon.as_deref(), Some("data:image/png;base64,AAAA"));
+    assert_eq!(tags(grafana), vec!["监控", "运维"]);
+    assert_eq!(
+      grafana.created_at,
+      NaiveDate::from_ymd_opt(2026, 10, 19).and_then(|date| date.and_hms_opt(8, 0, 0))
+    );
+  }
+
+  #[test]
+  fn render_xbel_nests_groups_in_dashboard_folders() {
+    let output = render_xbel(&board());
+
+    assert!(output.starts_with("<?xml"));
+    assert!(output.contains("<title>主页</title>"));
+    assert!(output.contains("<title>监控 &amp; 运维</title>"));
+    assert!(output.contains(
+      r#"<bookmark href="https://grafana.example.com/?a=1&amp;b=2" added="2026-10-19T08:00:00Z">"#
+    ));
+    assert!(output.contains("<title>Grafana &lt;prod&gt;</title>"));
+    assert!(output.contains(r#"<bookmark href="https://example.com">"#));
+    assert_eq!(output.matches("<folder>").count(), 2);
+    assert_eq!(output.matches("</folder>").count(), 2);
+  }
+}
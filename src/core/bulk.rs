@@ -0,0 +1,521 @@
+use super::{
+  app::{delete_apps, move_apps},
+  dashboard::resolve_dashboard,
+  group::check_group_owner,
+  monitor::{ensure_push_token, MonitorData},
+  tag::get_apps_tags,
+};
+use crate::errors::AppError;
+
+use actix_web::http::StatusCode;
+use entity::{app_tags, apps, tags};
+use sea_orm::{
+  entity::Set, sea_query::Expr, ActiveModelTrait, ColumnTrait, DatabaseTransaction, DbConn,
+  EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utils::{
+  deserialize::{option_str_to_i64, vec_str_to_i64},
+  serialize::i64_to_str,
+};
+use validator::Validate;
+
+// 与 CreateAppData 中的长度限制保持一致
+const MAX_NAME_LENGTH: usize = 30;
+const COPY_SUFFIX: &str = " 副本";
+
+#[derive(Debug, Serialize)]
+pub struct BulkItemResult {
+  #[serde(serialize_with = "i64_to_str")]
+  pub app_id: i64,
+  pub success: bool,
+  pub message: Option<String>,
+  // 复制操作新建的应用
+  pub app: Option<apps::Model>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkResult {
+  // 任意一个应用校验失败时不会修改任何应用
+  pub applied: bool,
+  pub succeeded: usize,
+  pub failed: usize,
+  pub items: Vec<BulkItemResult>,
+}
+
+impl BulkResult {
+  fn new(items: Vec<BulkItemResult>) -> Self {
+    let succeeded = items.iter().filter(|item| item.success).count();
+    BulkResult {
+      applied: succeeded == items.len(),
+      succeeded,
+      failed: items.len() - succeeded,
+      items,
+    }
+  }
+}
+
+// 在事务中按请求的顺序查询用户自己的应用，未找到的应用记录在结果中
+async fn find_owned_apps(
+  txn: &DatabaseTransaction,
+  operator_id: i64,
+  app_ids: &[i64],
+) -> Result<Result<Vec<apps::Model>, BulkResult>, AppError> {
+  let mut seen = HashSet::new();
+  let app_ids = app_ids
+    .iter()
+    .copied()
+    .filter(|app_id| seen.insert(*app_id))
+    .collect::<Vec<i64>>();
+
+  let mut owned_apps = apps::Entity::find()
+    .filter(apps::Column::Id.is_in(app_ids.clone()))
+    .filter(apps::Column::OwnerId.eq(operator_id))
+    .filter(apps::Column::IsGlobal.eq(false))
+    .all(txn)
+    .await?
+    .into_iter()
+    .map(|app| (app.id, app))
+    .collect::<HashMap<i64, apps::Model>>();
+
+  if owned_apps.len() == app_ids.len() {
+    return Ok(Ok(
+      app_ids
+        .iter()
+        .filter_map(|app_id| owned_apps.remove(app_id))
+        .collect(),
+    ));
+  }
+
+  Ok(Err(BulkResult::new(
+    app_ids
+      .into_iter()
+      .map(|app_id| BulkItemResult {
+        app_id,
+        success: false,
+        message: Some(
+          match owned_apps.contains_key(&app_id) {
+            true => "其他应用校验失败，未执行操作",
+            false => "应用不存在",
+          }
+          .to_string(),
+        ),
+        app: None,
+      })
+      .collect(),
+  )))
+}
+
+fn succeeded(app_ids: impl IntoIterator<Item = i64>) -> BulkResult {
+  BulkResult::new(
+    app_ids
+      .into_iter()
+      .map(|app_id| BulkItemResult {
+        app_id,
+        success: true,
+        message: None,
+        app: None,
+      })
+      .collect(),
+  )
+}
+
+async fn next_index(txn: &DatabaseTransaction, operator_id: i64) -> Result<i32, AppError> {
+  Ok(
+    apps::Entity::find()
+      .filter(apps::Column::OwnerId.eq(operator_id))
+      .filter(apps::Column::IsGlobal.eq(false))
+      .order_by_desc(apps::Column::Index)
+      .one(txn)
+      .await?
+      .map_or(0, |app| app.index + 1),
+  )
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct BulkAppIds {
+  #[validate(length(min = 1, max = 500, message = "每次操作的应用数量必须为 1 ~ 500 个"))]
+  #[serde(deserialize_with = "vec_str_to_i64")]
+  app_ids: Vec<i64>,
+}
+
+pub async fn bulk_delete_app(
+  db: &DbConn,
+  operator_id: i64,
+  data: &BulkAppIds,
+) -> Result<BulkResult, AppError> {
+  let txn = db.begin().await?;
+
+  let user_apps = match find_owned_apps(&txn, operator_id, &data.app_ids).await? {
+    Ok(user_apps) => user_apps,
+    Err(result) => return Ok(result),
+  };
+  let app_ids = user_apps.iter().map(|app| app.id).collect::<Vec<i64>>();
+
+  delete_apps(&txn, app_ids.clone()).await?;
+
+  txn.commit().await?;
+
+  Ok(succeeded(app_ids))
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct BulkMoveAppData {
+  #[validate(length(min = 1, max = 500, message = "每次操作的应用数量必须为 1 ~ 500 个"))]
+  #[serde(deserialize_with = "vec_str_to_i64")]
+  app_ids: Vec<i64>,
+  // 为空时移动到面板的未分组中
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  group_id: Option<i64>,
+  // 为空时移动到默认面板，指定分组时使用分组所在的面板
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  dashboard_id: Option<i64>,
+}
+
+// 移动后的应用排在最后，并保持请求中的顺序
+pub async fn bulk_move_app(
+  db: &DbConn,
+  operator_id: i64,
+  data: &BulkMoveAppData,
+) -> Result<BulkResult, AppError> {
+  let dashboard_id = match data.group_id {
+    Some(group_id) => {
+      check_group_owner(db, operator_id, group_id)
+        .await?
+        .dashboard_id
+    }
+    None => data.dashboard_id,
+  };
+  let dashboard = resolve_dashboard(db, operator_id, dashboard_id).await?;
+
+  let txn = db.begin().await?;
+
+  let user_apps = match find_owned_apps(&txn, operator_id, &data.app_ids).await? {
+    Ok(user_apps) => user_apps,
+    Err(result) => return Ok(result),
+  };
+  let app_ids = user_apps.iter().map(|app| app.id).collect::<Vec<i64>>();
+
+  move_apps(&txn, operator_id, user_apps, dashboard.id, data.group_id).await?;
+
+  txn.commit().await?;
+
+  Ok(succeeded(app_ids))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkTagMode {
+  // 替换为指定的标签，标签为空时清空
+  #[default]
+  Set,
+  Add,
+  Remove,
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct BulkTagAppData {
+  #[validate(length(min = 1, max = 500, message = "每次操作的应用数量必须为 1 ~ 500 个"))]
+  #[serde(deserialize_with = "vec_str_to_i64")]
+  app_ids: Vec<i64>,
+  #[serde(default, deserialize_with = "vec_str_to_i64")]
+  tag_ids: Vec<i64>,
+  #[serde(default)]
+  mode: BulkTagMode,
+}
+
+pub async fn bulk_tag_app(
+  db: &DbConn,
+  operator_id: i64,
+  data: &BulkTagAppData,
+) -> Result<BulkResult, AppError> {
+  let tag_ids = data.tag_ids.iter().copied().collect::<HashSet<i64>>();
+  let tag_count = tags::Entity::find()
+    .filter(tags::Column::Id.is_in(tag_ids.clone()))
+    .filter(tags::Column::OwnerId.eq(operator_id))
+    .count(db)
+    .await?;
+  if tag_count != tag_ids.len() as u64 {
+    return Err(AppError::new(StatusCode::NOT_FOUND, 404, "标签不存在"));
+  }
+
+  let txn = db.begin().await?;
+
+  let user_apps = match find_owned_apps(&txn, operator_id, &data.app_ids).await? {
+    Ok(user_apps) => user_apps,
+    Err(result) => return Ok(result),
+  };
+  let app_ids = user_apps.iter().map(|app| app.id).collect::<Vec<i64>>();
+
+  let mut delete =
+    app_tags::Entity::delete_many().filter(app_tags::Column::AppId.is_in(app_ids.clone()));
+  if data.mode != BulkTagMode::Set {
+    delete = delete.filter(app_tags::Column::TagId.is_in(tag_ids.clone()));
+  }
+  delete.exec(&txn).await?;
+
+  if data.mode != BulkTagMode::Remove && !tag_ids.is_empty() {
+    app_tags::Entity::insert_many(app_ids.iter().flat_map(|&app_id| {
+      tag_ids.iter().map(move |&tag_id| app_tags::ActiveModel {
+        app_id: Set(app_id),
+        tag_id: Set(tag_id),
+      })
+    }))
+    .exec(&txn)
+    .await?;
+  }
+
+  txn.commit().await?;
+
+  Ok(succeeded(app_ids))
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct BulkIconAppData {
+  #[validate(length(min = 1, max = 500, message = "每次操作的应用数量必须为 1 ~ 500 个"))]
+  #[serde(deserialize_with = "vec_str_to_i64")]
+  app_ids: Vec<i64>,
+  // 为空时清除图标
+  #[validate(length(min = 1, max = 255, message = "应用图标长度不得超过 255 个字符"))]
+  icon: Option<String>,
+}
+
+pub async fn bulk_icon_app(
+  db: &DbConn,
+  operator_id: i64,
+  data: &BulkIconAppData,
+) -> Result<BulkResult, AppError> {
+  let txn = db.begin().await?;
+
+  let user_apps = match find_owned_apps(&txn, operator_id, &data.app_ids).await? {
+    Ok(user_apps) => user_apps,
+    Err(result) => return Ok(result),
+  };
+  let app_ids = user_apps.iter().map(|app| app.id).collect::<Vec<i64>>();
+
+  apps::Entity::update_many()
+    .col_expr(apps::Column::Icon, Expr::value(data.icon.clone()))
+    .filter(apps::Column::Id.is_in(app_ids.clone()))
+    .exec(&txn)
+    .await?;
+
+  txn.commit().await?;
+
+  Ok(succeeded(app_ids))
+}
+
+// 复制的应用与原应用在同一分组中，排在最后，不复制短链接与令牌
+pub async fn bulk_duplicate_app(
+  db: &DbConn,
+  operator_id: i64,
+  data: &BulkAppIds,
+) -> Result<BulkResult, AppError> {
+  let txn = db.begin().await?;
+
+  let user_apps = match find_owned_apps(&txn, operator_id, &data.app_ids).await? {
+    Ok(user_apps) => user_apps,
+    Err(result) => return Ok(result),
+  };
+  let mut apps_tags = get_apps_tags(&txn, user_apps.iter().map(|app| app.id).collect()).await?;
+
+  let next_index = next_index(&txn, operator_id).await?;
+  let mut items = Vec::new();
+  for (index, app) in user_apps.into_iter().enumerate() {
+    let name_length = MAX_NAME_LENGTH - COPY_SUFFIX.chars().count();
+    let monitor = app.monitor.as_ref().map(MonitorData::from);
+
+    let copied = apps::ActiveModel {
+      name: Set(format!(
+        "{}{}",
+        app.name.chars().take(name_length).collect::<String>(),
+        COPY_SUFFIX
+      )),
+      url: Set(app.url.clone()),
+      internal_url: Set(app.internal_url.clone()),
+      description: Set(app.description.clone()),
+      icon: Set(app.icon.clone()),
+      links: Set(app.links.clone()),
+      monitor: Set(app.monitor.clone()),
+      push_token: Set(ensure_push_token(monitor.as_ref(), None)?),
+      index: Set(next_index + index as i32),
+      owner_id: Set(operator_id),
+      group_id: Set(app.group_id),
+      dashboard_id: Set(app.dashboard_id),
+      ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+
+    let tag_ids = apps_tags
+      .remove(&app.id)
+      .unwrap_or_default()
+      .into_iter()
+      .map(|tag| tag.id)
+      .collect::<Vec<i64>>();
+    if !tag_ids.is_empty() {
+      app_tags::Entity::insert_many(tag_ids.into_iter().map(|tag_id| app_tags::ActiveModel {
+        app_id: Set(copied.id),
+        tag_id: Set(tag_id),
+      }))
+      .exec(&txn)
+      .await?;
+    }
+
+    items.push(BulkItemResult {
+      app_id: app.id,
+      success: true,
+      message: None,
+      app: Some(copied),
+    });
+  }
+
+  txn.commit().await?;
+
+  Ok(BulkResult::new(items))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::testing::{create_app, create_user, setup_db};
+  use sea_orm::IntoActiveModel;
+
+  fn app_ids(app_ids: &[i64]) -> BulkAppIds {
+    BulkAppIds {
+      app_ids: app_ids.to_vec(),
+    }
+  }
+
+  #[actix_web::test]
+  async fn rejects_apps_of_other_users_without_changes() {
+    let db = setup_db().await;
+    let alice = create_user(&db, "alice").await;
+    let bob = create_user(&db, "bob").await;
+    let own_app = create_app(&db, alice.id, "Grafana", "https://grafana.example.com").await;
+    let other_app = create_app(&db, bob.id, "Jellyfin", "https://jf.example.com").await;
+
+    let result = bulk_delete_app(&db, alice.id, &app_ids(&[own_app.id, other_app.id, 999]))
+      .await
+      .unwrap();
+
+    assert!(!result.applied);
+    assert_eq!((result.succeeded, result.failed), (0, 3));
+    let items = result
+      .items
+      .iter()
+      .map(|item| (item.app_id, item.success, item.message.as_deref()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      items,
+      vec![
+        (own_app.id, false, Some("其他应用校验失败，未执行操作")),
+        (other_app.id, false, Some("应用不存在")),
+        (999, false, Some("应用不存在")),
+      ]
+    );
+    assert_eq!(apps::Entity::find().count(&db).await.unwrap(), 2);
+  }
+
+  #[actix_web::test]
+  async fn skips_global_apps() {
+    let db = setup_db().await;
+    let alice = create_user(&db, "alice").await;
+    let own_app = create_app(&db, alice.id, "Grafana", "https://grafana.example.com").await;
+    let mut global_app = create_app(&db, alice.id, "Wiki", "https://wiki.example.com")
+      .await
+      .into_active_model();
+    global_app.is_global = Set(true);
+    let global_app = global_app.update(&db).await.unwrap();
+
+    let result = bulk_icon_app(
+      &db,
+      alice.id,
+      &BulkIconAppData {
+        app_ids: vec![own_app.id, global_app.id],
+        icon: Some("icon/1/a.png".to_string()),
+      },
+    )
+    .await
+    .unwrap();
+
+    assert!(!result.applied);
+    assert_eq!(result.items[1].message.as_deref(), Some("应用不存在"));
+    let icons = apps::Entity::find()
+      .all(&db)
+      .await
+      .unwrap()
+      .into_iter()
+      .map(|app| app.icon)
+      .collect::<Vec<_>>();
+    assert_eq!(icons, vec![None, None]);
+  }
+
+  #[actix_web::test]
+  async fn applies_to_every_app_once() {
+    let db = setup_db().await;
+    let alice = create_user(&db, "alice").await;
+    let first = create_app(&db, alice.id, "Grafana", "https://grafana.example.com").await;
+    let second = create_app(&db, alice.id, "Jellyfin", "https://jf.example.com").await;
+
+    let result = bulk_icon_app(
+      &db,
+      alice.id,
+      &BulkIconAppData {
+        app_ids: vec![second.id, first.id, second.id],
+        icon: Some("icon/1/a.png".to_string()),
+      },
+    )
+    .await
+    .unwrap();
+
+    assert!(result.applied);
+    assert_eq!((result.succeeded, result.failed), (2, 0));
+    let ids = result
+      .items
+      .iter()
+      .map(|item| item.app_id)
+      .collect::<Vec<i64>>();
+    assert_eq!(ids, vec![second.id, first.id]);
+    for app in apps::Entity::find().all(&db).await.unwrap() {
+      assert_eq!(app.icon.as_deref(), Some("icon/1/a.png"));
+    }
+  }
+
+  #[actix_web::test]
+  async fn duplicates_apps_with_tags() {
+    let db = setup_db().await;
+    let alice = create_user(&db, "alice").await;
+    let app = create_app(&db, alice.id, "Grafana", "https://grafana.example.com").await;
+    let tag = tags::ActiveModel {
+      name: Set("ops".to_string()),
+      owner_id: Set(alice.id),
+      ..Default::default()
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+    bulk_tag_app(
+      &db,
+      alice.id,
+      &BulkTagAppData {
+        app_ids: vec![app.id],
+        tag_ids: vec![tag.id],
+        mode: BulkTagMode::Add,
+      },
+    )
+    .await
+    .unwrap();
+
+    let result = bulk_duplicate_app(&db, alice.id, &app_ids(&[app.id]))
+      .await
+      .unwrap();
+
+    assert!(result.applied);
+    let copied = result.items[0].app.as_ref().unwrap();
+    assert_eq!(copied.name, "Grafana 副本");
+    assert_eq!(copied.index, app.index + 1);
+    let copied_tags = get_apps_tags(&db, vec![copied.id]).await.unwrap();
+    assert_eq!(copied_tags[&copied.id][0].name, "ops");
+  }
+}
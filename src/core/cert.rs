@@ -0,0 +1,375 @@
+use super::monitor::CONCURRENCY;
+use crate::settings::SETTINGS;
+
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDateTime, Utc};
+use entity::{
+  app_certs::{self, SubjectAltNames},
+  apps,
+};
+use futures_util::{stream, StreamExt};
+use lazy_static::lazy_static;
+use reqwest::Url;
+use sea_orm::{
+  entity::Set, sea_query::OnConflict, ColumnTrait, ConnectionTrait, DbConn, DbErr, EntityTrait,
+  QueryFilter,
+};
+use serde::Serialize;
+use std::{collections::HashMap, net::IpAddr, sync::Arc, time::Duration};
+use tokio::net::TcpStream;
+use tokio_rustls::{
+  rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{ring, verify_tls12_signature, verify_tls13_signature, CryptoProvider},
+    pki_types::{CertificateDer, ServerName, UnixTime},
+    ClientConfig, DigitallySignedStruct, Error, SignatureScheme,
+  },
+  TlsConnector,
+};
+use x509_parser::{extensions::GeneralName, parse_x509_certificate};
+
+// 证书检查间隔，单位分钟
+const CERT_CHECK_INTERVAL: i64 = 60;
+
+// 接受任意证书，用于读取自签名或已过期的证书信息
+#[derive(Debug)]
+struct AcceptAnyCert(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCert {
+  fn verify_server_cert(
+    &self,
+    _end_entity: &CertificateDer<'_>,
+    _intermediates: &[CertificateDer<'_>],
+    _server_name: &ServerName<'_>,
+    _ocsp_response: &[u8],
+    _now: UnixTime,
+  ) -> Result<ServerCertVerified, Error> {
+    Ok(ServerCertVerified::assertion())
+  }
+
+  fn verify_tls12_signature(
+    &self,
+    message: &[u8],
+    cert: &CertificateDer<'_>,
+    dss: &DigitallySignedStruct,
+  ) -> Result<HandshakeSignatureValid, Error> {
+    verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+  }
+
+  fn verify_tls13_signature(
+    &self,
+    message: &[u8],
+    cert: &CertificateDer<'_>,
+    dss: &DigitallySignedStruct,
+  ) -> Result<HandshakeSignatureValid, Error> {
+    verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+  }
+
+  fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+    self.0.signature_verification_algorithms.supported_schemes()
+  }
+}
+
+lazy_static! {
+  static ref TLS_CONNECTOR: TlsConnector = {
+    let provider = Arc::new(ring::default_provider());
+    let config = ClientConfig::builder_with_provider(provider.clone())
+      .with_safe_default_protocol_versions()
+      .expect("TLS config build failed")
+      .dangerous()
+      .with_custom_certificate_verifier(Arc::new(AcceptAnyCert(provider)))
+      .with_no_client_auth();
+    TlsConnector::from(Arc::new(config))
+  };
+}
+
+#[derive(Debug, Serialize)]
+pub struct CertInfo {
+  #[serde(flatten)]
+  pub cert: app_certs::Model,
+  pub expired: bool,
+  // 证书将在配置的天数内过期
+  pub expiring: bool,
+}
+
+impl From<app_certs::Model> for CertInfo {
+  fn from(cert: app_certs::Model) -> Self {
+    let now = Utc::now().naive_utc();
+    let expiring_at = now + ChronoDuration::days(SETTINGS.monitor.cert_expiry_days);
+
+    CertInfo {
+      expired: cert.expires_at.is_some_and(|expires_at| expires_at <= now),
+      expiring: cert
+        .expires_at
+        .is_some_and(|expires_at| expires_at > now && expires_at <= expiring_at),
+      cert,
+    }
+  }
+}
+
+pub async fn get_apps_cert<C: ConnectionTrait>(
+  db: &C,
+  app_ids: Vec<i64>,
+) -> Result<HashMap<i64, CertInfo>, DbErr> {
+  Ok(
+    app_certs::Entity::find()
+      .filter(app_certs::Column::AppId.is_in(app_ids))
+      .all(db)
+      .await?
+      .into_iter()
+      .map(|cert| (cert.app_id, cert.into()))
+      .collect(),
+  )
+}
+
+fn https_host(url: &str) -> Option<(String, u16)> {
+  let url = Url::parse(url).ok().filter(|url| url.scheme() == "https")?;
+  let host = url.host_str()?.trim_matches(['[', ']']).to_string();
+  Some((host, url.port_or_known_default()?))
+}
+
+// 检查 HTTPS 应用的证书，非 HTTPS 应用不保留证书记录
+pub async fn check_all_cert(db: &DbConn, all_apps: &[apps::Model]) -> Result<(), DbErr> {
+  let (https_apps, other_apps): (Vec<&apps::Model>, Vec<&apps::Model>) = all_apps
+    .iter()
+    .partition(|app| https_host(&app.url).is_some());
+
+  app_certs::Entity::delete_many()
+    .filter(app_certs::Column::AppId.is_in(other_apps.iter().map(|app| app.id)))
+    .exec(db)
+    .await?;
+
+  let checked_at = get_apps_cert(db, https_apps.iter().map(|app| app.id).collect())
+    .await?
+    .into_iter()
+    .map(|(app_id, cert)| (app_id, cert.cert.checked_at))
+    .collect::<HashMap<i64, NaiveDateTime>>();
+  let check_before = Utc::now().naive_utc() - ChronoDuration::minutes(CERT_CHECK_INTERVAL);
+
+  let certs = stream::iter(https_apps)
+    .filter(|app| {
+      let due = checked_at
+        .get(&app.id)
+        .is_none_or(|checked_at| *checked_at < check_before);
+      async move { due }
+    })
+    .map(|app| async move { check_cert(app).await })
+    .buffer_unordered(CONCURRENCY)
+    .collect::<Vec<app_certs::ActiveModel>>()
+    .await;
+
+  for cert in certs {
+    app_certs::Entity::insert(cert)
+      .on_conflict(
+        OnConflict::column(app_certs::Column::AppId)
+          .update_columns([
+            app_certs::Column::Issuer,
+            app_certs::Column::Subject,
+            app_certs::Column::Sans,
+            app_certs::Column::NotBefore,
+            app_certs::Column::ExpiresAt,
+            app_certs::Column::HostnameMatch,
+            app_certs::Column::Error,
+            app_certs::Column::CheckedAt,
+          ])
+          .to_owned(),
+      )
+      .exec(db)
+      .await?;
+  }
+
+  Ok(())
+}
+
+async fn check_cert(app: &apps::Model) -> app_certs::ActiveModel {
+  let mut cert = app_certs::ActiveModel {
+    app_id: Set(app.id),
+    issuer: Set(None),
+    subject: Set(None),
+    sans: Set(SubjectAltNames::default()),
+    not_before: Set(None),
+    expires_at: Set(None),
+    hostname_match: Set(false),
+    error: Set(None),
+    checked_at: Set(Utc::now().naive_utc()),
+  };
+
+  if let Err(err) = fetch_cert(&app.url, &mut cert).await {
+    cert.error = Set(Some(err.chars().take(255).collect()));
+  }
+
+  cert
+}
+
+fn to_datetime(timestamp: i64) -> Option<NaiveDateTime> {
+  DateTime::from_timestamp(timestamp, 0).map(|datetime| datetime.naive_utc())
+}
+
+async fn fetch_cert(url: &str, cert: &mut app_certs::ActiveModel) -> Result<(), String> {
+  let (host, port) = https_host(url).ok_or("应用 URL 不是 HTTPS 地址".to_string())?;
+  let server_name = ServerName::try_from(host.clone()).map_err(|err| err.to_string())?;
+
+  let handshake = async {
+    let tcp = TcpStream::connect((host.as_str(), port)).await?;
+    TLS_CONNECTOR.connect(server_name, tcp).await
+  };
+  let tls = tokio::time::timeout(Duration::from_secs(SETTINGS.monitor.timeout), handshake)
+    .await
+    .map_err(|_| format!("{}:{} 连接超时", host, port))?
+    .map_err(|err| err.to_string())?;
+
+  let der = tls
+    .get_ref()
+    .1
+    .peer_certificates()
+    .and_then(|certs| certs.first())
+    .ok_or("服务器没有返回证书".to_string())?;
+  let (_, x509) = parse_x509_certificate(der.as_ref()).map_err(|err| err.to_string())?;
+
+  let mut sans = Vec::new();
+  if let Ok(Some(san)) = x509.subject_alternative_name() {
+    for name in &san.value.general_names {
+      match name {
+        GeneralName::DNSName(name) => sans.push(name.to_string()),
+        GeneralName::IPAddress(ip) => match ip.len() {
+          4 => sans.push(IpAddr::from(<[u8; 4]>::try_from(*ip).unwrap()).to_string()),
+          16 => sans.push(IpAddr::from(<[u8; 16]>::try_from(*ip).unwrap()).to_string()),
+          _ => {}
+        },
+        _ => {}
+      }
+    }
+  }
+
+  // 证书没有 SAN 时，使用 CN 作为主机名
+  if sans.is_empty() {
+    sans.extend(
+      x509
+        .subject()
+        .iter_common_name()
+        .filter_map(|cn| cn.as_str().ok().map(ToString::to_string)),
+    );
+  }
+
+  cert.issuer = Set(Some(x509.issuer().to_string()));
+  cert.subject = Set(Some(x509.subject().to_string()));
+  cert.not_before = Set(to_datetime(x509.validity().not_before.timestamp()));
+  cert.expires_at = Set(to_datetime(x509.validity().not_after.timestamp()));
+  cert.hostname_match = Set(sans.iter().any(|name| is_hostname_match(name, &host)));
+  cert.sans = Set(SubjectAltNames(sans));
+
+  Ok(())
+}
+
+// 证书主机名支持最左侧一级的通配符
+fn is_hostname_match(pattern: &str, host: &str) -> bool {
+  let pattern = pattern.to_ascii_lowercase();
+  let host = host.to_ascii_lowercase();
+
+  match pattern.strip_prefix("*.") {
+    Some(suffix) => host
+      .split_once('.')
+      .is_some_and(|(label, rest)| !label.is_empty() && rest == suffix),
+    None => pattern == host,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tokio::net::TcpListener;
+  use tokio_rustls::{
+    rustls::{
+      pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer},
+      ServerConfig,
+    },
+    TlsAcceptor,
+  };
+
+  // 使用 openssl 生成的自签名证书，CN 与 SAN 均为 localhost，有效期 100 年
+  const CERT: &[u8] = include_bytes!("testdata/localhost.crt.der");
+  const KEY: &[u8] = include_bytes!("testdata/localhost.key.der");
+
+  // 在本地启动 TLS 服务器，返回监听的端口
+  async fn serve_tls() -> u16 {
+    let config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+      .with_safe_default_protocol_versions()
+      .unwrap()
+      .with_no_client_auth()
+      .with_single_cert(
+        vec![CertificateDer::from(CERT)],
+        PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(KEY)),
+      )
+      .unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    actix_web::rt::spawn(async move {
+      while let Ok((tcp, _)) = listener.accept().await {
+        let _ = acceptor.accept(tcp).await;
+      }
+    });
+
+    port
+  }
+
+  fn empty_cert() -> app_certs::ActiveModel {
+    app_certs::ActiveModel {
+      sans: Set(SubjectAltNames::default()),
+      hostname_match: Set(false),
+      ..Default::default()
+    }
+  }
+
+  #[actix_web::test]
+  async fn reads_self_signed_cert() {
+    let port = serve_tls().await;
+    let mut cert = empty_cert();
+
+    fetch_cert(&format!("https://localhost:{}", port), &mut cert)
+      .await
+      .unwrap();
+
+    let now = Utc::now().naive_utc();
+    assert_eq!(cert.subject.unwrap(), Some("CN=localhost".to_string()));
+    assert_eq!(cert.issuer.unwrap(), Some("CN=localhost".to_string()));
+    assert_eq!(cert.sans.unwrap().0, vec!["localhost".to_string()]);
+    assert!(cert.hostname_match.unwrap());
+    let not_before = cert.not_before.unwrap().unwrap();
+    let expires_at = cert.expires_at.unwrap().unwrap();
+    assert!(not_before <= now && expires_at > now);
+  }
+
+  #[actix_web::test]
+  async fn detects_hostname_mismatch() {
+    let port = serve_tls().await;
+    let mut cert = empty_cert();
+
+    fetch_cert(&format!("https://127.0.0.1:{}", port), &mut cert)
+      .await
+      .unwrap();
+
+    assert!(!cert.hostname_match.unwrap());
+  }
+
+  #[actix_web::test]
+  async fn fails_without_server() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    let mut cert = empty_cert();
+    let result = fetch_cert(&format!("https://127.0.0.1:{}", port), &mut cert).await;
+    assert!(result.is_err());
+    assert!(fetch_cert("http://localhost", &mut cert).await.is_err());
+  }
+
+  #[test]
+  fn matches_wildcard_hostname() {
+    assert!(is_hostname_match("example.com", "EXAMPLE.com"));
+    assert!(is_hostname_match("*.example.com", "app.example.com"));
+    assert!(!is_hostname_match("*.example.com", "example.com"));
+    assert!(!is_hostname_match("*.example.com", "a.b.example.com"));
+    assert!(!is_hostname_match("app.example.com", "example.com"));
+  }
+}
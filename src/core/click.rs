@@ -0,0 +1,433 @@
+use super::{
+  app::{get_user_visible_app, AppOrder},
+  global_app::is_visible,
+  network::{is_internal, NetworkMode},
+  share::check_app_permission,
+};
+use crate::errors::AppError;
+
+use actix_web::http::StatusCode;
+use chrono::{Datelike, Duration as ChronoDuration, NaiveDate, NaiveDateTime, Utc};
+use entity::{app_clicks, apps, dashboards, shares::SharePermission, users};
+use sea_orm::{
+  entity::Set, sea_query::Expr, ActiveModelTrait, ColumnTrait, DbConn, DeleteResult, EntityTrait,
+  QueryFilter, QuerySelect,
+};
+use serde::{Deserialize, Serialize};
+use std::{cmp::Reverse, collections::HashMap, net::IpAddr};
+use utils::{deserialize::option_str_to_i64, serialize::i64_to_str};
+
+// 统计范围默认为最近 30 天，最多 365 天
+const DEFAULT_STATS_DAYS: i64 = 30;
+const MAX_STATS_DAYS: i64 = 365;
+const DEFAULT_TOP_LIMIT: u64 = 10;
+const MAX_TOP_LIMIT: u64 = 50;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LaunchAppQuery {
+  // 打开应用时所在的面板
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  dashboard_id: Option<i64>,
+  // 为空时按客户端地址选择应用的内网或外网地址
+  network: Option<NetworkMode>,
+}
+
+// 记录一次打开应用，返回应用地址用于重定向
+pub async fn launch_app(
+  db: &DbConn,
+  user_id: i64,
+  app_id: i64,
+  query: &LaunchAppQuery,
+  client_ip: Option<IpAddr>,
+) -> Result<String, AppError> {
+  let user = users::Entity::find_by_id(user_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户不存在"))?;
+
+  let app = apps::Entity::find_by_id(app_id)
+    .filter(apps::Column::IsGlobal.eq(true))
+    .one(db)
+    .await?
+    .filter(|app| is_visible(app, &user));
+  let app = match app {
+    Some(app) => app,
+    None => check_app_permission(db, user_id, app_id, SharePermission::Read).await?,
+  };
+
+  record_click(db, &user, app.id, query.dashboard_id).await?;
+
+  Ok(
+    app
+      .client_url(is_internal(query.network, client_ip))
+      .to_string(),
+  )
+}
+
+// 用户关闭统计时不做记录，只记录用户自己的面板，无效的面板不影响跳转
+pub(super) async fn record_click(
+  db: &DbConn,
+  user: &users::Model,
+  app_id: i64,
+  dashboard_id: Option<i64>,
+) -> Result<(), AppError> {
+  if user
+    .setting
+    .as_ref()
+    .is_some_and(|setting| setting.disable_tracking)
+  {
+    return Ok(());
+  }
+
+  let dashboard_id = match dashboard_id {
+    Some(dashboard_id) => dashboards::Entity::find_by_id(dashboard_id)
+      .filter(dashboards::Column::OwnerId.eq(user.id))
+      .one(db)
+      .await?
+      .map(|dashboard| dashboard.id),
+    None => None,
+  };
+
+  app_clicks::ActiveModel {
+    app_id: Set(app_id),
+    user_id: Set(user.id),
+    dashboard_id: Set(dashboard_id),
+    clicked_at: Set(Utc::now().naive_utc()),
+    ..Default::default()
+  }
+  .insert(db)
+  .await?;
+
+  Ok(())
+}
+
+struct AppUsage {
+  count: i64,
+  last_clicked_at: NaiveDateTime,
+}
+
+async fn get_apps_usage(
+  db: &DbConn,
+  user_id: i64,
+  app_ids: Vec<i64>,
+  since: Option<NaiveDateTime>,
+) -> Result<HashMap<i64, AppUsage>, AppError> {
+  let mut select = app_clicks::Entity::find()
+    .select_only()
+    .column(app_clicks::Column::AppId)
+    .column_as(app_clicks::Column::Id.count(), "count")
+    .column_as(app_clicks::Column::ClickedAt.max(), "last_clicked_at")
+    .filter(app_clicks::Column::UserId.eq(user_id))
+    .filter(app_clicks::Column::AppId.is_in(app_ids))
+    .group_by(app_clicks::Column::AppId);
+  if let Some(since) = since {
+    select = select.filter(app_clicks::Column::ClickedAt.gte(since));
+  }
+
+  Ok(
+    select
+      .into_tuple::<(i64, i64, NaiveDateTime)>()
+      .all(db)
+      .await?
+      .into_iter()
+      .map(|(app_id, count, last_clicked_at)| {
+        (
+          app_id,
+          AppUsage {
+            count,
+            last_clicked_at,
+          },
+        )
+      })
+      .collect(),
+  )
+}
+
+// 按打开次数或最近打开时间排序，次数相同或未打开过的应用保持原有顺序
+pub(super) async fn sort_by_usage(
+  db: &DbConn,
+  user_id: i64,
+  apps: &mut [apps::Model],
+  order: AppOrder,
+) -> Result<(), AppError> {
+  if order == AppOrder::Index || apps.is_empty() {
+    return Ok(());
+  }
+
+  let app_ids = apps.iter().map(|app| app.id).collect::<Vec<i64>>();
+  let usage = get_apps_usage(db, user_id, app_ids, None).await?;
+
+  match order {
+    AppOrder::Index => {}
+    AppOrder::Frequency => {
+      apps.sort_by_key(|app| Reverse(usage.get(&app.id).map_or(0, |usage| usage.count)))
+    }
+    AppOrder::Recency => {
+      apps.sort_by_key(|app| Reverse(usage.get(&app.id).map(|usage| usage.last_clicked_at)))
+    }
+  }
+
+  Ok(())
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsPeriod {
+  #[default]
+  Day,
+  // 每周从周一开始
+  Week,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetClickStatsQuery {
+  period: Option<StatsPeriod>,
+  // 统计最近多少天，默认 30 天
+  days: Option<i64>,
+  // 最常用应用的数量，默认 10 个
+  limit: Option<u64>,
+  // 为空时统计全部应用
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  app_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PeriodClicks {
+  // 统计周期的第一天，日期按 UTC 计算
+  pub date: NaiveDate,
+  pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TopApp {
+  #[serde(serialize_with = "i64_to_str")]
+  pub app_id: i64,
+  pub name: String,
+  pub icon: Option<String>,
+  pub count: i64,
+  pub last_clicked_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClickStats {
+  pub total: i64,
+  pub periods: Vec<PeriodClicks>,
+  pub top: Vec<TopApp>,
+}
+
+fn period_start(date: NaiveDate, period: StatsPeriod) -> NaiveDate {
+  match period {
+    StatsPeriod::Day => date,
+    StatsPeriod::Week => date - ChronoDuration::days(date.weekday().num_days_from_monday() as i64),
+  }
+}
+
+pub async fn get_click_stats(
+  db: &DbConn,
+  user_id: i64,
+  query: &GetClickStatsQuery,
+) -> Result<ClickStats, AppError> {
+  // 只统计用户当前仍可以访问的应用
+  let visible_apps = get_user_visible_app(db, user_id)
+    .await?
+    .into_iter()
+    .filter(|app| query.app_id.is_none_or(|app_id| app.id == app_id))
+    .collect::<Vec<apps::Model>>();
+  let app_ids = visible_apps.iter().map(|app| app.id).collect::<Vec<i64>>();
+
+  let period = query.period.unwrap_or_default();
+  let days = query
+    .days
+    .unwrap_or(DEFAULT_STATS_DAYS)
+    .clamp(1, MAX_STATS_DAYS);
+  let today = Utc::now().date_naive();
+  let first_day = today - ChronoDuration::days(days - 1);
+  let since = first_day.and_hms_opt(0, 0, 0).unwrap();
+
+  // 按日期统计后再合并为周，避免依赖不同数据库的日期函数。各数据库 DATE 的返回类型不同，
+  // 统一转为 YYYY-MM-DD 格式的字符串后再解析
+  let day = Expr::cust("CAST(DATE(clicked_at) AS CHAR(10))");
+  let mut period_counts = HashMap::<NaiveDate, i64>::new();
+  for (day, count) in app_clicks::Entity::find()
+    .select_only()
+    .column_as(day.clone(), "day")
+    .column_as(app_clicks::Column::Id.count(), "count")
+    .filter(app_clicks::Column::UserId.eq(user_id))
+    .filter(app_clicks::Column::AppId.is_in(app_ids.clone()))
+    .filter(app_clicks::Column::ClickedAt.gte(since))
+    .group_by(day)
+    .into_tuple::<(String, i64)>()
+    .all(db)
+    .await?
+  {
+    let Ok(date) = day.parse::<NaiveDate>() else {
+      log::warn!("Invalid click date: {}", day);
+      continue;
+    };
+    *period_counts.entry(period_start(date, period)).or_default() += count;
+  }
+
+  let mut periods = Vec::new();
+  let mut date = period_start(first_day, period);
+  while date <= today {
+    periods.push(PeriodClicks {
+      date,
+      count: period_counts.get(&date).copied().unwrap_or_default(),
+    });
+    date = match period {
+      StatsPeriod::Day => date + ChronoDuration::days(1),
+      StatsPeriod::Week => date + ChronoDuration::weeks(1),
+    };
+  }
+
+  let usage = get_apps_usage(db, user_id, app_ids, Some(since)).await?;
+  let mut top = visible_apps
+    .into_iter()
+    .filter_map(|app| {
+      let usage = usage.get(&app.id)?;
+      Some(TopApp {
+        app_id: app.id,
+        name: app.name,
+        icon: app.icon,
+        count: usage.count,
+        last_clicked_at: usage.last_clicked_at,
+      })
+    })
+    .collect::<Vec<TopApp>>();
+  top.sort_by_key(|app| (Reverse(app.count), Reverse(app.last_clicked_at)));
+  top.truncate(
+    query
+      .limit
+      .unwrap_or(DEFAULT_TOP_LIMIT)
+      .clamp(1, MAX_TOP_LIMIT) as usize,
+  );
+
+  Ok(ClickStats {
+    total: periods.iter().map(|period| period.count).sum(),
+    periods,
+    top,
+  })
+}
+
+// 清除用户的全部打开记录
+pub async fn clear_clicks(db: &DbConn, user_id: i64) -> Result<DeleteResult, AppError> {
+  app_clicks::Entity::delete_many()
+    .filter(app_clicks::Column::UserId.eq(user_id))
+    .exec(db)
+    .await
+    .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::testing;
+
+  fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+  }
+
+  #[test]
+  fn period_start_buckets_weeks_from_monday() {
+    // 2026-10-19 是周一
+    assert_eq!(
+      period_start(date(2026, 10, 19), StatsPeriod::Week),
+      date(2026, 10, 19)
+    );
+    assert_eq!(
+      period_start(date(2026, 10, 21), StatsPeriod::Week),
+      date(2026, 10, 19)
+    );
+    assert_eq!(
+      period_start(date(2026, 10, 25), StatsPeriod::Week),
+      date(2026, 10, 19)
+    );
+    assert_eq!(
+      period_start(date(2026, 10, 26), StatsPeriod::Week),
+      date(2026, 10, 26)
+    );
+    // 跨月与跨年
+    assert_eq!(
+      period_start(date(2026, 11, 1), StatsPeriod::Week),
+      date(2026, 10, 26)
+    );
+    assert_eq!(
+      period_start(date(2027, 1, 1), StatsPeriod::Week),
+      date(2026, 12, 28)
+    );
+    assert_eq!(
+      period_start(date(2026, 10, 21), StatsPeriod::Day),
+      date(2026, 10, 21)
+    );
+  }
+
+  async fn insert_click(db: &DbConn, user_id: i64, app_id: i64, clicked_at: NaiveDateTime) {
+    app_clicks::ActiveModel {
+      app_id: Set(app_id),
+      user_id: Set(user_id),
+      clicked_at: Set(clicked_at),
+      ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap();
+  }
+
+  #[actix_web::test]
+  async fn get_click_stats_aggregates_clicks_by_day_and_week() {
+    let db = testing::setup_db().await;
+    let user = testing::create_user(&db, "clicker").await;
+    let app = testing::create_app(&db, user.id, "Grafana", "https://grafana.example.com").await;
+
+    let today = Utc::now().date_naive();
+    let yesterday = today - ChronoDuration::days(1);
+    let last_week = today - ChronoDuration::days(7);
+    for (day, hour) in [(today, 1), (today, 23), (yesterday, 12), (last_week, 0)] {
+      insert_click(&db, user.id, app.id, day.and_hms_opt(hour, 0, 0).unwrap()).await;
+    }
+    // 超出统计范围的记录不计入
+    let expired = today - ChronoDuration::days(DEFAULT_STATS_DAYS);
+    insert_click(&db, user.id, app.id, expired.and_hms_opt(12, 0, 0).unwrap()).await;
+
+    let query = GetClickStatsQuery {
+      period: Some(StatsPeriod::Day),
+      days: None,
+      limit: None,
+      app_id: None,
+    };
+    let stats = get_click_stats(&db, user.id, &query).await.unwrap();
+    let counts = stats
+      .periods
+      .iter()
+      .map(|period| (period.date, period.count))
+      .collect::<HashMap<NaiveDate, i64>>();
+    assert_eq!(stats.periods.len(), DEFAULT_STATS_DAYS as usize);
+    assert_eq!(stats.total, 4);
+    assert_eq!(counts[&today], 2);
+    assert_eq!(counts[&yesterday], 1);
+    assert_eq!(counts[&last_week], 1);
+    assert_eq!(stats.top[0].count, 4);
+
+    let query = GetClickStatsQuery {
+      period: Some(StatsPeriod::Week),
+      ..query
+    };
+    let stats = get_click_stats(&db, user.id, &query).await.unwrap();
+    let mut expected = HashMap::<NaiveDate, i64>::new();
+    for (day, count) in [(today, 2), (yesterday, 1), (last_week, 1)] {
+      *expected
+        .entry(period_start(day, StatsPeriod::Week))
+        .or_default() += count;
+    }
+    assert!(stats
+      .periods
+      .iter()
+      .all(|period| period.date.weekday() == chrono::Weekday::Mon));
+    assert!(stats.periods.iter().all(|period| expected
+      .get(&period.date)
+      .copied()
+      .unwrap_or_default()
+      == period.count));
+    assert_eq!(stats.total, 4);
+  }
+}
@@ -0,0 +1,314 @@
+use crate::errors::AppError;
+
+use actix_web::http::StatusCode;
+use entity::{
+  apps,
+  dashboards::{self, Background, Layout},
+  groups, shares,
+};
+use sea_orm::{
+  entity::Set, sea_query::Expr, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, DbErr,
+  EntityTrait, IntoActiveModel, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder,
+  TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use utils::deserialize::str_to_i64;
+use validator::Validate;
+
+// 创建用户时一并创建默认面板
+pub async fn create_default_dashboard<C: ConnectionTrait>(
+  db: &C,
+  user_id: i64,
+) -> Result<dashboards::Model, AppError> {
+  dashboards::ActiveModel {
+    name: Set("默认".to_string()),
+    index: Set(0),
+    is_default: Set(true),
+    owner_id: Set(user_id),
+    ..Default::default()
+  }
+  .insert(db)
+  .await
+  .map_err(Into::into)
+}
+
+pub async fn get_default_dashboard<C: ConnectionTrait>(
+  db: &C,
+  user_id: i64,
+) -> Result<dashboards::Model, AppError> {
+  dashboards::Entity::find()
+    .filter(dashboards::Column::OwnerId.eq(user_id))
+    .filter(dashboards::Column::IsDefault.eq(true))
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "默认面板不存在"))
+}
+
+// 查询用户指定的面板，未指定时返回默认面板
+pub async fn resolve_dashboard<C: ConnectionTrait>(
+  db: &C,
+  user_id: i64,
+  dashboard_id: Option<i64>,
+) -> Result<dashboards::Model, AppError> {
+  let default_dashboard = get_default_dashboard(db, user_id).await?;
+
+  match dashboard_id {
+    Some(dashboard_id) if dashboard_id != default_dashboard.id => {
+      dashboards::Entity::find_by_id(dashboard_id)
+        .filter(dashboards::Column::OwnerId.eq(user_id))
+        .one(db)
+        .await?
+        .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "面板不存在"))
+    }
+    _ => Ok(default_dashboard),
+  }
+}
+
+pub async fn get_user_all_dashboard(
+  db: &DbConn,
+  user_id: i64,
+) -> Result<Vec<dashboards::Model>, AppError> {
+  dashboards::Entity::find()
+    .filter(dashboards::Column::OwnerId.eq(user_id))
+    .order_by_asc(dashboards::Column::Index)
+    .all(db)
+    .await
+    .map_err(Into::into)
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct LayoutData {
+  #[validate(range(min = 1, max = 12, message = "每行应用数量必须为 1 ~ 12 个"))]
+  columns: Option<i8>,
+  compact: Option<bool>,
+}
+
+impl From<&LayoutData> for Layout {
+  fn from(data: &LayoutData) -> Self {
+    Layout {
+      columns: data.columns,
+      compact: data.compact,
+    }
+  }
+}
+
+impl From<&Layout> for LayoutData {
+  fn from(layout: &Layout) -> Self {
+    LayoutData {
+      columns: layout.columns,
+      compact: layout.compact,
+    }
+  }
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct BackgroundData {
+  #[validate(length(min = 1, max = 255, message = "背景图片长度不得超过 255 个字符"))]
+  bg_image: Option<String>,
+  #[validate(range(min = 0, max = 20, message = "背景模糊必须为 0 ~ 20 px"))]
+  bg_blur: Option<i8>,
+}
+
+impl From<&BackgroundData> for Background {
+  fn from(data: &BackgroundData) -> Self {
+    Background {
+      bg_image: data.bg_image.clone(),
+      bg_blur: data.bg_blur,
+    }
+  }
+}
+
+impl From<&Background> for BackgroundData {
+  fn from(background: &Background) -> Self {
+    BackgroundData {
+      bg_image: background.bg_image.clone(),
+      bg_blur: background.bg_blur,
+    }
+  }
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct CreateDashboardData {
+  #[validate(length(min = 1, max = 30, message = "面板名称长度不得超过 30 个字符"))]
+  name: String,
+  #[validate(nested)]
+  layout: Option<LayoutData>,
+  #[validate(nested)]
+  background: Option<BackgroundData>,
+}
+
+pub async fn create_dashboard(
+  db: &DbConn,
+  operator_id: i64,
+  data: &CreateDashboardData,
+) -> Result<dashboards::Model, AppError> {
+  let last_index_dashboard = dashboards::Entity::find()
+    .filter(dashboards::Column::OwnerId.eq(operator_id))
+    .order_by_desc(dashboards::Column::Index)
+    .one(db)
+    .await?;
+
+  let index = match last_index_dashboard {
+    Some(dashboard) => dashboard.index + 1,
+    _ => {
+      let dashboard_pages = dashboards::Entity::find()
+        .filter(dashboards::Column::OwnerId.eq(operator_id))
+        .paginate(db, 1);
+
+      let total = dashboard_pages.num_items().await?;
+      total as i32
+    }
+  };
+
+  dashboards::ActiveModel {
+    name: Set(data.name.clone()),
+    index: Set(index),
+    is_default: Set(false),
+    layout: Set(data.layout.as_ref().map(Into::into)),
+    background: Set(data.background.as_ref().map(Into::into)),
+    owner_id: Set(operator_id),
+    ..Default::default()
+  }
+  .insert(db)
+  .await
+  .map_err(Into::into)
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct UpdateDashboardData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+  #[validate(length(min = 1, max = 30, message = "面板名称长度不得超过 30 个字符"))]
+  name: String,
+  #[validate(nested)]
+  layout: Option<LayoutData>,
+  #[validate(nested)]
+  background: Option<BackgroundData>,
+}
+
+pub async fn update_dashboard(
+  db: &DbConn,
+  operator_id: i64,
+  data: &UpdateDashboardData,
+) -> Result<dashboards::Model, AppError> {
+  let mut dashboard = resolve_dashboard(db, operator_id, Some(data.id))
+    .await?
+    .into_active_model();
+
+  dashboard.name = Set(data.name.clone());
+  dashboard.layout = Set(data.layout.as_ref().map(Into::into));
+  dashboard.background = Set(data.background.as_ref().map(Into::into));
+
+  dashboard.update(db).await.map_err(Into::into)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SortDashboardData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+}
+
+pub async fn sort_dashboard(
+  db: &DbConn,
+  operator_id: i64,
+  data: &[SortDashboardData],
+) -> Result<(), AppError> {
+  let sort_dashboard_data = data.to_vec();
+  db.transaction::<_, (), DbErr>(|txn| {
+    Box::pin(async move {
+      for (index, &item) in sort_dashboard_data.iter().enumerate() {
+        let mut dashboard = dashboards::Entity::find_by_id(item.id)
+          .filter(dashboards::Column::OwnerId.eq(operator_id))
+          .one(txn)
+          .await?
+          .ok_or(DbErr::Custom("未查找到对应数据".to_string()))?
+          .into_active_model();
+
+        dashboard.index = Set(index as i32);
+
+        dashboard.update(txn).await?;
+      }
+
+      Ok(())
+    })
+  })
+  .await
+  .map_err(AppError::from_err)?;
+
+  Ok(())
+}
+
+pub async fn set_default_dashboard(
+  db: &DbConn,
+  operator_id: i64,
+  dashboard_id: i64,
+) -> Result<(), AppError> {
+  let dashboard = resolve_dashboard(db, operator_id, Some(dashboard_id)).await?;
+
+  db.transaction::<_, (), DbErr>(|txn| {
+    Box::pin(async move {
+      dashboards::Entity::update_many()
+        .col_expr(dashboards::Column::IsDefault, Expr::value(false))
+        .filter(dashboards::Column::OwnerId.eq(operator_id))
+        .exec(txn)
+        .await?;
+
+      let mut dashboard = dashboard.into_active_model();
+      dashboard.is_default = Set(true);
+      dashboard.update(txn).await?;
+
+      Ok(())
+    })
+  })
+  .await
+  .map_err(AppError::from_err)?;
+
+  Ok(())
+}
+
+// 删除面板时，面板中的应用与分组移动到默认面板中
+pub async fn delete_dashboard(
+  db: &DbConn,
+  operator_id: i64,
+  dashboard_id: i64,
+) -> Result<(), AppError> {
+  let dashboard = resolve_dashboard(db, operator_id, Some(dashboard_id)).await?;
+
+  if dashboard.is_default {
+    return Err(AppError::new(StatusCode::FORBIDDEN, 403, "不能删除默认面板"));
+  }
+
+  let default_dashboard = get_default_dashboard(db, operator_id).await?;
+
+  db.transaction::<_, (), DbErr>(|txn| {
+    Box::pin(async move {
+      apps::Entity::update_many()
+        .col_expr(apps::Column::DashboardId, Expr::value(default_dashboard.id))
+        .filter(apps::Column::OwnerId.eq(operator_id))
+        .filter(apps::Column::DashboardId.eq(dashboard.id))
+        .exec(txn)
+        .await?;
+
+      groups::Entity::update_many()
+        .col_expr(groups::Column::DashboardId, Expr::value(default_dashboard.id))
+        .filter(groups::Column::OwnerId.eq(operator_id))
+        .filter(groups::Column::DashboardId.eq(dashboard.id))
+        .exec(txn)
+        .await?;
+
+      shares::Entity::delete_many()
+        .filter(shares::Column::OwnerId.eq(operator_id))
+        .filter(shares::Column::DashboardId.eq(dashboard.id))
+        .exec(txn)
+        .await?;
+
+      dashboard.delete(txn).await?;
+
+      Ok(())
+    })
+  })
+  .await
+  .map_err(AppError::from_err)?;
+
+  Ok(())
+}
@@ -20,8 +20,17 @@ pub fn save<T: Display, E: Display>(
 
   let dirname = SETTINGS.files_dir.join(dirname);
   fs::create_dir_all(&dirname)
-    .and_then(|_| fs::write(dirname.join(filename), &bytes))
+    .and_then(|_| fs::write(dirname.join(filename), bytes))
     .map_err(AppError::from_err)?;
 
   Ok(uri)
 }
+
+// 删除已保存的文件，用于导入失败时清理，删除失败时只记录日志
+pub fn remove(uris: &[String]) {
+  for uri in uris {
+    if let Err(err) = fs::remove_file(SETTINGS.files_dir.join(uri)) {
+      log::warn!("Remove file {} failed: {}", uri, err);
+    }
+  }
+}
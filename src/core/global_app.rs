@@ -0,0 +1,383 @@
+use super::{
+  app::{delete_apps, AppExtraData, ManagedApp},
+  icon::{save_remote_icon, spawn_discover_icon},
+  link::to_app_links,
+  monitor::{ensure_push_token, MonitorData},
+  short_link::check_slug,
+  user::check_admin,
+};
+use crate::errors::AppError;
+
+use actix_web::http::StatusCode;
+use entity::{
+  app_prefs,
+  apps::{self, Audience, AudienceRole},
+  users,
+};
+use sea_orm::{
+  entity::Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, DbErr, EntityTrait,
+  IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use utils::deserialize::{double_option, str_to_i64, vec_str_to_i64};
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudienceData {
+  #[serde(default)]
+  roles: Vec<AudienceRole>,
+  #[serde(default, deserialize_with = "vec_str_to_i64")]
+  user_ids: Vec<i64>,
+}
+
+impl From<&AudienceData> for Audience {
+  fn from(data: &AudienceData) -> Self {
+    Audience {
+      roles: data.roles.clone(),
+      user_ids: data.user_ids.clone(),
+    }
+  }
+}
+
+pub fn is_visible(app: &apps::Model, user: &users::Model) -> bool {
+  app
+    .audience
+    .as_ref()
+    .is_none_or(|audience| audience.contains(user.id, user.is_admin))
+}
+
+pub async fn get_all_global_app(
+  db: &DbConn,
+  operator_id: i64,
+) -> Result<Vec<ManagedApp>, AppError> {
+  check_admin(db, operator_id).await?;
+
+  let apps = apps::Entity::find()
+    .filter(apps::Column::IsGlobal.eq(true))
+    .order_by_asc(apps::Column::Index)
+    .all(db)
+    .await?;
+
+  Ok(apps.into_iter().map(Into::into).collect())
+}
+
+// 查询用户可见的全站应用及用户对其的个人设置
+pub async fn get_user_global_app<C: ConnectionTrait>(
+  db: &C,
+  user: &users::Model,
+) -> Result<Vec<(apps::Model, Option<app_prefs::Model>)>, AppError> {
+  let global_apps = apps::Entity::find()
+    .filter(apps::Column::IsGlobal.eq(true))
+    .order_by_asc(apps::Column::Index)
+    .all(db)
+    .await?
+    .into_iter()
+    .filter(|app| is_visible(app, user))
+    .collect::<Vec<apps::Model>>();
+
+  let user_prefs = app_prefs::Entity::find()
+    .filter(app_prefs::Column::UserId.eq(user.id))
+    .filter(app_prefs::Column::AppId.is_in(global_apps.iter().map(|app| app.id)))
+    .all(db)
+    .await?;
+
+  Ok(
+    global_apps
+      .into_iter()
+      .map(|app| {
+        let pref = user_prefs.iter().find(|pref| pref.app_id == app.id).cloned();
+        (app, pref)
+      })
+      .collect(),
+  )
+}
+
+// 校验全站应用对当前用户可见
+pub async fn check_global_app_visible<C: ConnectionTrait>(
+  db: &C,
+  user: &users::Model,
+  app_id: i64,
+) -> Result<apps::Model, AppError> {
+  apps::Entity::find_by_id(app_id)
+    .filter(apps::Column::IsGlobal.eq(true))
+    .one(db)
+    .await?
+    .filter(|app| is_visible(app, user))
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "应用不存在"))
+}
+
+// 新增或更新用户对全站应用的个人设置
+pub async fn save_app_pref<C: ConnectionTrait>(
+  db: &C,
+  user_id: i64,
+  app_id: i64,
+  update: impl FnOnce(&mut app_prefs::ActiveModel),
+) -> Result<app_prefs::Model, DbErr> {
+  let pref = app_prefs::Entity::find_by_id((user_id, app_id))
+    .one(db)
+    .await?;
+
+  match pref {
+    Some(pref) => {
+      let mut pref = pref.into_active_model();
+      update(&mut pref);
+      pref.update(db).await
+    }
+    None => {
+      let mut pref = app_prefs::ActiveModel {
+        user_id: Set(user_id),
+        app_id: Set(app_id),
+        hidden: Set(false),
+        index: Set(None),
+        group_id: Set(None),
+      };
+      update(&mut pref);
+      pref.insert(db).await
+    }
+  }
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct CreateGlobalAppData {
+  #[validate(
+    length(min = 1, max = 255, message = "应用 URL 长度不得超过 255 个字符"),
+    url(message = "应用 URL 格式不合法")
+  )]
+  url: String,
+  #[validate(length(min = 1, max = 30, message = "应用名称长度不得超过 30 个字符"))]
+  name: String,
+  #[validate(length(min = 1, max = 255, message = "应用描述长度不得超过 255 个字符"))]
+  description: Option<String>,
+  // 图标为远程地址时在创建应用时下载保存
+  #[validate(length(min = 1, max = 255, message = "应用图标长度不得超过 255 个字符"))]
+  icon: Option<String>,
+  #[validate(nested)]
+  #[serde(flatten)]
+  extra: AppExtraData,
+  audience: Option<AudienceData>,
+  #[validate(nested)]
+  monitor: Option<MonitorData>,
+}
+
+pub async fn create_global_app(
+  db: &DbConn,
+  operator_id: i64,
+  data: &CreateGlobalAppData,
+) -> Result<ManagedApp, AppError> {
+  check_admin(db, operator_id).await?;
+  check_slug(db, operator_id, data.extra.slug(), true, None).await?;
+  let icon = save_remote_icon(operator_id, data.icon.as_deref()).await;
+
+  let last_index_app = apps::Entity::find()
+    .filter(apps::Column::IsGlobal.eq(true))
+    .order_by_desc(apps::Column::Index)
+    .one(db)
+    .await?;
+
+  let index = match last_index_app {
+    Some(app) => app.index + 1,
+    _ => {
+      let app_pages = apps::Entity::find()
+        .filter(apps::Column::IsGlobal.eq(true))
+        .paginate(db, 1);
+
+      let total = app_pages.num_items().await?;
+      total as i32
+    }
+  };
+
+  let app = apps::ActiveModel {
+    name: Set(data.name.clone()),
+    url: Set(data.url.clone()),
+    internal_url: Set(data.extra.internal_url().map(Into::into)),
+    icon: Set(icon),
+    slug: Set(data.extra.slug().map(Into::into)),
+    description: Set(data.description.clone()),
+    links: Set(to_app_links(data.extra.links())),
+    index: Set(index),
+    owner_id: Set(operator_id),
+    is_global: Set(true),
+    audience: Set(data.audience.as_ref().map(Into::into)),
+    monitor: Set(data.monitor.as_ref().map(Into::into)),
+    push_token: Set(ensure_push_token(data.monitor.as_ref(), None)?),
+    ..Default::default()
+  }
+  .insert(db)
+  .await?;
+
+  spawn_discover_icon(db, &app);
+
+  Ok(app.into())
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct UpdateGlobalAppData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+  #[validate(
+    length(min = 1, max = 255, message = "应用 URL 长度不得超过 255 个字符"),
+    url(message = "应用 URL 格式不合法")
+  )]
+  url: String,
+  #[validate(length(min = 1, max = 30, message = "应用名称长度不得超过 30 个字符"))]
+  name: String,
+  #[validate(length(min = 1, max = 255, message = "应用描述长度不得超过 255 个字符"))]
+  description: Option<String>,
+  #[validate(length(min = 1, max = 255, message = "应用图标长度不得超过 255 个字符"))]
+  icon: Option<String>,
+  #[validate(nested)]
+  #[serde(flatten)]
+  extra: AppExtraData,
+  audience: Option<AudienceData>,
+  // 缺失时不修改应用的监控配置，为 null 时关闭监控
+  #[validate(nested)]
+  #[serde(default, deserialize_with = "double_option")]
+  monitor: Option<Option<MonitorData>>,
+}
+
+pub async fn update_global_app(
+  db: &DbConn,
+  operator_id: i64,
+  data: &UpdateGlobalAppData,
+) -> Result<ManagedApp, AppError> {
+  check_admin(db, operator_id).await?;
+
+  let app = apps::Entity::find_by_id(data.id)
+    .filter(apps::Column::IsGlobal.eq(true))
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "应用不存在"))?;
+  if let Some(slug) = &data.extra.slug {
+    check_slug(db, app.owner_id, slug.as_deref(), true, Some(app.id)).await?;
+  }
+
+  let push_token = match &data.monitor {
+    Some(monitor) => Some(ensure_push_token(monitor.as_ref(), app.push_token.clone())?),
+    None => None,
+  };
+  let mut app = app.into_active_model();
+
+  app.name = Set(data.name.clone());
+  app.url = Set(data.url.clone());
+  if let Some(internal_url) = &data.extra.internal_url {
+    app.internal_url = Set(internal_url.clone());
+  }
+  app.description = Set(data.description.clone());
+  app.icon = Set(data.icon.clone());
+  if let Some(slug) = &data.extra.slug {
+    app.slug = Set(slug.clone());
+  }
+  if let Some(links) = &data.extra.links {
+    app.links = Set(to_app_links(links));
+  }
+  app.audience = Set(data.audience.as_ref().map(Into::into));
+  if let Some(monitor) = &data.monitor {
+    app.monitor = Set(monitor.as_ref().map(Into::into));
+  }
+  if let Some(push_token) = push_token {
+    app.push_token = Set(push_token);
+  }
+
+  Ok(app.update(db).await?.into())
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SortGlobalAppData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+}
+
+pub async fn sort_global_app(
+  db: &DbConn,
+  operator_id: i64,
+  data: &[SortGlobalAppData],
+) -> Result<(), AppError> {
+  check_admin(db, operator_id).await?;
+
+  let sort_app_data = data.to_vec();
+  db.transaction::<_, (), DbErr>(|txn| {
+    Box::pin(async move {
+      for (index, &item) in sort_app_data.iter().enumerate() {
+        let mut app = apps::Entity::find_by_id(item.id)
+          .filter(apps::Column::IsGlobal.eq(true))
+          .one(txn)
+          .await?
+          .ok_or(DbErr::Custom("未查找到对应数据".to_string()))?
+          .into_active_model();
+
+        app.index = Set(index as i32);
+
+        app.update(txn).await?;
+      }
+
+      Ok(())
+    })
+  })
+  .await
+  .map_err(AppError::from_err)?;
+
+  Ok(())
+}
+
+pub async fn delete_global_app(db: &DbConn, operator_id: i64, app_id: i64) -> Result<(), AppError> {
+  check_admin(db, operator_id).await?;
+
+  let app = apps::Entity::find_by_id(app_id)
+    .filter(apps::Column::IsGlobal.eq(true))
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "应用不存在"))?;
+
+  let txn = db.begin().await?;
+
+  delete_apps(&txn, vec![app.id]).await?;
+
+  txn.commit().await?;
+
+  Ok(())
+}
+
+pub async fn get_user_hidden_global_app(
+  db: &DbConn,
+  operator_id: i64,
+) -> Result<Vec<apps::Model>, AppError> {
+  let user = users::Entity::find_by_id(operator_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户不存在"))?;
+
+  Ok(
+    get_user_global_app(db, &user)
+      .await?
+      .into_iter()
+      .filter(|(_, pref)| pref.as_ref().is_some_and(|pref| pref.hidden))
+      .map(|(app, _)| app)
+      .collect(),
+  )
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HideGlobalAppData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+  hidden: bool,
+}
+
+// 用户可以隐藏全站应用，但不能编辑
+pub async fn hide_global_app(
+  db: &DbConn,
+  operator_id: i64,
+  data: &HideGlobalAppData,
+) -> Result<(), AppError> {
+  let user = users::Entity::find_by_id(operator_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户不存在"))?;
+
+  check_global_app_visible(db, &user, data.id).await?;
+
+  let hidden = data.hidden;
+  save_app_pref(db, operator_id, data.id, |pref| pref.hidden = Set(hidden)).await?;
+
+  Ok(())
+}
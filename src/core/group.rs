@@ -0,0 +1,197 @@
+use super::dashboard::resolve_dashboard;
+use crate::errors::AppError;
+
+use actix_web::http::StatusCode;
+use entity::{apps, groups, shares};
+use sea_orm::{
+  entity::Set, sea_query::Expr, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, DbErr,
+  EntityTrait, IntoActiveModel, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder,
+  TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use utils::deserialize::{option_str_to_i64, str_to_i64};
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetUserAllGroupQuery {
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  dashboard_id: Option<i64>,
+}
+
+pub async fn get_user_all_group(
+  db: &DbConn,
+  user_id: i64,
+  query: &GetUserAllGroupQuery,
+) -> Result<Vec<groups::Model>, AppError> {
+  let dashboard = resolve_dashboard(db, user_id, query.dashboard_id).await?;
+
+  get_dashboard_all_group(db, user_id, dashboard.id).await
+}
+
+pub async fn get_dashboard_all_group<C: ConnectionTrait>(
+  db: &C,
+  user_id: i64,
+  dashboard_id: i64,
+) -> Result<Vec<groups::Model>, AppError> {
+  groups::Entity::find()
+    .filter(groups::Column::OwnerId.eq(user_id))
+    .filter(groups::Column::DashboardId.eq(dashboard_id))
+    .order_by_asc(groups::Column::Index)
+    .all(db)
+    .await
+    .map_err(Into::into)
+}
+
+// 校验分组是否属于当前用户
+pub async fn check_group_owner<C: ConnectionTrait>(
+  db: &C,
+  operator_id: i64,
+  group_id: i64,
+) -> Result<groups::Model, AppError> {
+  groups::Entity::find_by_id(group_id)
+    .filter(groups::Column::OwnerId.eq(operator_id))
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "分组不存在"))
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct CreateGroupData {
+  #[validate(length(min = 1, max = 30, message = "分组名称长度不得超过 30 个字符"))]
+  pub(super) name: String,
+  #[validate(length(min = 1, max = 255, message = "分组图标长度不得超过 255 个字符"))]
+  pub(super) icon: Option<String>,
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  pub(super) dashboard_id: Option<i64>,
+}
+
+pub async fn create_group(
+  db: &DbConn,
+  operator_id: i64,
+  data: &CreateGroupData,
+) -> Result<groups::Model, AppError> {
+  let dashboard = resolve_dashboard(db, operator_id, data.dashboard_id).await?;
+
+  let last_index_group = groups::Entity::find()
+    .filter(groups::Column::OwnerId.eq(operator_id))
+    .order_by_desc(groups::Column::Index)
+    .one(db)
+    .await?;
+
+  let index = match last_index_group {
+    Some(group) => group.index + 1,
+    _ => {
+      let group_pages = groups::Entity::find()
+        .filter(groups::Column::OwnerId.eq(operator_id))
+        .paginate(db, 1);
+
+      let total = group_pages.num_items().await?;
+      total as i32
+    }
+  };
+
+  groups::ActiveModel {
+    name: Set(data.name.clone()),
+    icon: Set(data.icon.clone()),
+    collapsed: Set(false),
+    index: Set(index),
+    owner_id: Set(operator_id),
+    dashboard_id: Set(Some(dashboard.id)),
+    ..Default::default()
+  }
+  .insert(db)
+  .await
+  .map_err(Into::into)
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct UpdateGroupData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+  #[validate(length(min = 1, max = 30, message = "分组名称长度不得超过 30 个字符"))]
+  name: String,
+  #[validate(length(min = 1, max = 255, message = "分组图标长度不得超过 255 个字符"))]
+  icon: Option<String>,
+  collapsed: bool,
+}
+
+pub async fn update_group(
+  db: &DbConn,
+  operator_id: i64,
+  data: &UpdateGroupData,
+) -> Result<groups::Model, AppError> {
+  let mut group = check_group_owner(db, operator_id, data.id)
+    .await?
+    .into_active_model();
+
+  group.name = Set(data.name.clone());
+  group.icon = Set(data.icon.clone());
+  group.collapsed = Set(data.collapsed);
+
+  group.update(db).await.map_err(Into::into)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SortGroupData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+}
+
+pub async fn sort_group(
+  db: &DbConn,
+  operator_id: i64,
+  data: &[SortGroupData],
+) -> Result<(), AppError> {
+  let sort_group_data = data.to_vec();
+  db.transaction::<_, (), DbErr>(|txn| {
+    Box::pin(async move {
+      for (index, &item) in sort_group_data.iter().enumerate() {
+        let mut group = groups::Entity::find_by_id(item.id)
+          .filter(groups::Column::OwnerId.eq(operator_id))
+          .one(txn)
+          .await?
+          .ok_or(DbErr::Custom("未查找到对应数据".to_string()))?
+          .into_active_model();
+
+        group.index = Set(index as i32);
+
+        group.update(txn).await?;
+      }
+
+      Ok(())
+    })
+  })
+  .await
+  .map_err(AppError::from_err)?;
+
+  Ok(())
+}
+
+pub async fn delete_group(db: &DbConn, operator_id: i64, group_id: i64) -> Result<(), AppError> {
+  let group = check_group_owner(db, operator_id, group_id).await?;
+
+  db.transaction::<_, (), DbErr>(|txn| {
+    Box::pin(async move {
+      // 删除分组时，分组内的应用移动到未分组中
+      apps::Entity::update_many()
+        .col_expr(apps::Column::GroupId, Expr::value(Option::<i64>::None))
+        .filter(apps::Column::OwnerId.eq(operator_id))
+        .filter(apps::Column::GroupId.eq(group.id))
+        .exec(txn)
+        .await?;
+
+      shares::Entity::delete_many()
+        .filter(shares::Column::GroupId.eq(group.id))
+        .exec(txn)
+        .await?;
+
+      group.delete(txn).await?;
+
+      Ok(())
+    })
+  })
+  .await
+  .map_err(AppError::from_err)?;
+
+  Ok(())
+}
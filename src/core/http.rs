@@ -0,0 +1,18 @@
+use lazy_static::lazy_static;
+use reqwest::Client;
+use std::time::Duration;
+
+lazy_static! {
+  // 全局共享的 HTTP 客户端，复用连接池
+  pub static ref CLIENT: Client = Client::builder()
+    .connect_timeout(Duration::from_secs(120))
+    .build()
+    .expect("HTTP client build failed");
+
+  // 不校验 TLS 证书的 HTTP 客户端，用于检查使用自签名证书的应用
+  pub static ref INSECURE_CLIENT: Client = Client::builder()
+    .connect_timeout(Duration::from_secs(120))
+    .danger_accept_invalid_certs(true)
+    .build()
+    .expect("HTTP client build failed");
+}
@@ -0,0 +1,514 @@
+use super::{
+  file,
+  http::{CLIENT, INSECURE_CLIENT},
+  monitor::{check_manage_app, CONCURRENCY},
+  user::check_admin,
+};
+use crate::{errors::AppError, settings::SETTINGS};
+
+use actix_web::http::StatusCode;
+use anyhow::{anyhow, bail};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use entity::apps;
+use futures_util::{stream, StreamExt};
+use reqwest::{Client, Url};
+use scraper::{Html, Selector};
+use sea_orm::{
+  entity::Set, sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition, DbConn, EntityTrait,
+  IntoActiveModel, QueryFilter,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use utils::serialize::i64_to_str;
+
+// 图标文件大小上限
+const MAX_ICON_SIZE: usize = 1024 * 1024;
+// 只读取页面开头的内容，图标声明都在 head 中
+pub(super) const MAX_PAGE_SIZE: usize = 512 * 1024;
+
+struct IconCandidate {
+  url: Url,
+  // 图标边长，未知时为 0
+  size: u32,
+}
+
+fn client() -> &'static Client {
+  if SETTINGS.monitor.verify_tls {
+    &CLIENT
+  } else {
+    &INSECURE_CLIENT
+  }
+}
+
+pub(super) struct Fetched {
+  // 跟随重定向后的最终地址
+  pub url: Url,
+  pub content_type: Option<String>,
+  pub body: Vec<u8>,
+}
+
+// 读取响应内容，超过上限时截断或报错
+pub(super) async fn fetch(url: &Url, limit: usize, truncate: bool) -> anyhow::Result<Fetched> {
+  let resp = client()
+    .get(url.clone())
+    .timeout(Duration::from_secs(SETTINGS.monitor.timeout))
+    .send()
+    .await?
+    .error_for_status()?;
+  let final_url = resp.url().clone();
+  let content_type = resp
+    .headers()
+    .get(reqwest::header::CONTENT_TYPE)
+    .and_then(|value| value.to_str().ok())
+    .map(String::from);
+
+  let mut body = Vec::new();
+  let mut chunks = resp.bytes_stream();
+  while let Some(chunk) = chunks.next().await {
+    let chunk = chunk?;
+    if body.len() + chunk.len() > limit {
+      if truncate {
+        body.extend_from_slice(&chunk[..limit - body.len()]);
+        break;
+      }
+      bail!("文件大小超过 {} KB", limit / 1024);
+    }
+    body.extend_from_slice(&chunk);
+  }
+
+  Ok(Fetched {
+    url: final_url,
+    content_type,
+    body,
+  })
+}
+
+// 解析 sizes 属性，如 "32x32 192x192"，取最大的尺寸
+fn parse_sizes(sizes: &str) -> Option<u32> {
+  sizes
+    .split_whitespace()
+    .filter_map(|size| {
+      let (width, height) = size
+        .to_ascii_lowercase()
+        .split_once('x')
+        .map(|(width, height)| (width.parse::<u32>(), height.parse::<u32>()))?;
+      Some(width.ok()?.min(height.ok()?))
+    })
+    .max()
+}
+
+// 不使用 SVG 图标，SVG 中可能包含脚本，且与面板同源访问
+fn is_svg(url: &Url, mime_type: Option<&str>) -> bool {
+  mime_type.is_some_and(|mime_type| mime_type.contains("svg"))
+    || url.path().to_ascii_lowercase().ends_with(".svg")
+}
+
+// 从页面的 link 标签中查找图标与 manifest 地址
+fn parse_html(html: &str, base: &Url) -> (Vec<IconCandidate>, Option<Url>) {
+  let document = Html::parse_document(html);
+  let selector = Selector::parse("link[rel][href]").unwrap();
+
+  let mut candidates = Vec::new();
+  let mut manifest = None;
+  for element in document.select(&selector) {
+    let element = element.value();
+    let rel = element.attr("rel").unwrap_or_default().to_ascii_lowercase();
+    let rels = rel.split_whitespace().collect::<Vec<&str>>();
+    let Some(url) = element
+      .attr("href")
+      .and_then(|href| base.join(href.trim()).ok())
+    else {
+      continue;
+    };
+
+    if rels.contains(&"manifest") {
+      manifest = manifest.or(Some(url));
+      continue;
+    }
+
+    let apple_touch = rels.iter().any(|rel| rel.starts_with("apple-touch-icon"));
+    if (!apple_touch && !rels.contains(&"icon")) || is_svg(&url, element.attr("type")) {
+      continue;
+    }
+
+    // apple-touch-icon 未声明尺寸时通常为 180x180
+    let size = element
+      .attr("sizes")
+      .and_then(parse_sizes)
+      .unwrap_or(if apple_touch { 180 } else { 0 });
+    candidates.push(IconCandidate { url, size });
+  }
+
+  (candidates, manifest)
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+  #[serde(default)]
+  icons: Vec<ManifestIcon>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestIcon {
+  src: String,
+  sizes: Option<String>,
+  #[serde(rename = "type")]
+  mime_type: Option<String>,
+  purpose: Option<String>,
+}
+
+async fn manifest_candidates(manifest_url: &Url) -> anyhow::Result<Vec<IconCandidate>> {
+  let Fetched {
+    url: manifest_url,
+    body,
+    ..
+  } = fetch(manifest_url, MAX_ICON_SIZE, false).await?;
+
+  parse_manifest(&body, &manifest_url)
+}
+
+// 图标地址相对于 manifest 的地址
+fn parse_manifest(body: &[u8], manifest_url: &Url) -> anyhow::Result<Vec<IconCandidate>> {
+  let manifest = serde_json::from_slice::<Manifest>(body)?;
+
+  Ok(
+    manifest
+      .icons
+      .into_iter()
+      // 单色图标只有轮廓，不适合作为应用图标
+      .filter(|icon| {
+        icon.purpose.as_deref().is_none_or(|purpose| {
+          purpose
+            .split_whitespace()
+            .any(|purpose| purpose != "monochrome")
+        })
+      })
+      .filter_map(|icon| {
+        let url = manifest_url.join(&icon.src).ok()?;
+        (!is_svg(&url, icon.mime_type.as_deref())).then(|| IconCandidate {
+          url,
+          size: icon.sizes.as_deref().and_then(parse_sizes).unwrap_or(0),
+        })
+      })
+      .collect(),
+  )
+}
+
+// 根据文件头判断图片格式，避免把错误页面当作图标保存
+pub(super) fn sniff_extension(bytes: &[u8]) -> Option<&'static str> {
+  match bytes {
+    [0x89, b'P', b'N', b'G', ..] => Some("png"),
+    [0xff, 0xd8, 0xff, ..] => Some("jpg"),
+    [b'G', b'I', b'F', b'8', ..] => Some("gif"),
+    [0x00, 0x00, 0x01, 0x00, ..] => Some("ico"),
+    [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("webp"),
+    _ => None,
+  }
+}
+
+// 下载指定地址的图标并保存，返回图标文件地址
+pub(super) async fn download_icon(owner_id: i64, url: &Url) -> anyhow::Result<String> {
+  let Fetched { body, .. } = fetch(url, MAX_ICON_SIZE, false).await?;
+  let Some(extension) = sniff_extension(&body) else {
+    bail!("不支持的图标格式");
+  };
+
+  file::save(owner_id, &body, "icon", extension).map_err(|err| anyhow!(err.message))
+}
+
+// 保存 data URI 中的图标，如浏览器书签中内嵌的图标
+pub(super) fn save_data_icon(owner_id: i64, uri: &str) -> anyhow::Result<String> {
+  let Some((_, data)) = uri
+    .strip_prefix("data:")
+    .and_then(|uri| uri.split_once(";base64,"))
+  else {
+    bail!("不支持的图标格式");
+  };
+  let body = STANDARD.decode(data.trim())?;
+  if body.len() > MAX_ICON_SIZE {
+    bail!("文件大小超过 {} KB", MAX_ICON_SIZE / 1024);
+  }
+  let Some(extension) = sniff_extension(&body) else {
+    bail!("不支持的图标格式");
+  };
+
+  file::save(owner_id, &body, "icon", extension).map_err(|err| anyhow!(err.message))
+}
+
+// 创建应用时图标为远程地址则下载保存，下载失败时不设置图标，由后台重新查找
+pub(super) async fn save_remote_icon(owner_id: i64, icon: Option<&str>) -> Option<String> {
+  let icon = icon?;
+  let url = match Url::parse(icon) {
+    Ok(url) if matches!(url.scheme(), "http" | "https") => url,
+    _ => return Some(icon.to_string()),
+  };
+
+  download_icon(owner_id, &url)
+    .await
+    .map_err(|err| log::debug!("Save icon {} failed: {}", url, err))
+    .ok()
+}
+
+pub async fn discover_icon(url: &str) -> anyhow::Result<(Vec<u8>, &'static str)> {
+  let url = Url::parse(url)?;
+
+  let (_, body, extension) = match fetch(&url, MAX_PAGE_SIZE, true).await {
+    Ok(page) => find_icon(&page.url, Some(&String::from_utf8_lossy(&page.body))).await?,
+    Err(err) => {
+      log::debug!("Fetch page {} failed: {}", url, err);
+      find_icon(&url, None).await?
+    }
+  };
+
+  Ok((body, extension))
+}
+
+// 依次尝试页面声明的图标、manifest 中的图标与 /favicon.ico，优先使用尺寸最大的图标，返回图标地址与内容
+pub(super) async fn find_icon(
+  base: &Url,
+  html: Option<&str>,
+) -> anyhow::Result<(Url, Vec<u8>, &'static str)> {
+  let mut candidates = Vec::new();
+
+  if let Some(html) = html {
+    let (icons, manifest) = parse_html(html, base);
+    candidates.extend(icons);
+
+    if let Some(manifest) = manifest {
+      match manifest_candidates(&manifest).await {
+        Ok(icons) => candidates.extend(icons),
+        Err(err) => log::debug!("Fetch manifest {} failed: {}", manifest, err),
+      }
+    }
+  }
+
+  candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.size));
+  candidates.push(IconCandidate {
+    url: base.join("/favicon.ico")?,
+    size: 0,
+  });
+
+  let mut tried = Vec::new();
+  for candidate in candidates {
+    if tried.contains(&candidate.url) {
+      continue;
+    }
+
+    match fetch(&candidate.url, MAX_ICON_SIZE, false).await {
+      Ok(Fetched { body, .. }) => {
+        if let Some(extension) = sniff_extension(&body) {
+          return Ok((candidate.url, body, extension));
+        }
+      }
+      Err(err) => log::debug!("Fetch icon {} failed: {}", candidate.url, err),
+    }
+
+    tried.push(candidate.url);
+  }
+
+  Err(anyhow!("未找到应用图标"))
+}
+
+// 发现并保存应用图标，返回更新后的应用
+pub async fn refresh_app_icon(db: &DbConn, app: apps::Model) -> Result<apps::Model, AppError> {
+  let (bytes, extension) = discover_icon(app.server_url()).await.map_err(|err| {
+    AppError::new(
+      StatusCode::BAD_GATEWAY,
+      502,
+      format!("获取应用图标失败：{}", err),
+    )
+  })?;
+  let icon = file::save(app.owner_id, &bytes, "icon", extension)?;
+
+  let mut app = app.into_active_model();
+  app.icon = Set(Some(icon));
+
+  app.update(db).await.map_err(Into::into)
+}
+
+// 新建的应用没有图标时，在后台查找图标，不影响应用的创建
+pub fn spawn_discover_icon(db: &DbConn, app: &apps::Model) {
+  if app.icon.is_some() {
+    return;
+  }
+
+  let db = db.clone();
+  let (app_id, owner_id, url) = (app.id, app.owner_id, app.server_url().to_string());
+  actix_web::rt::spawn(async move {
+    let result = async {
+      let (bytes, extension) = discover_icon(&url).await?;
+      let icon =
+        file::save(owner_id, &bytes, "icon", extension).map_err(|err| anyhow!(err.message))?;
+
+      // 查找期间用户已经设置了图标时，不再覆盖
+      apps::Entity::update_many()
+        .col_expr(apps::Column::Icon, Expr::value(icon))
+        .filter(apps::Column::Id.eq(app_id))
+        .filter(apps::Column::Icon.is_null())
+        .exec(&db)
+        .await?;
+
+      anyhow::Ok(())
+    };
+
+    if let Err(err) = result.await {
+      log::info!("Discover icon for app {} failed: {}", app_id, err);
+    }
+  });
+}
+
+pub async fn refresh_icon(
+  db: &DbConn,
+  operator_id: i64,
+  app_id: i64,
+) -> Result<apps::Model, AppError> {
+  let app = check_manage_app(db, operator_id, app_id).await?;
+
+  refresh_app_icon(db, app).await
+}
+
+#[derive(Debug, Serialize)]
+pub struct RefreshIconResult {
+  #[serde(serialize_with = "i64_to_str")]
+  pub app_id: i64,
+  pub icon: Option<String>,
+  pub error: Option<String>,
+}
+
+// 重新查找当前用户全部应用的图标，管理员同时刷新全站应用
+pub async fn refresh_all_icon(
+  db: &DbConn,
+  operator_id: i64,
+) -> Result<Vec<RefreshIconResult>, AppError> {
+  let mut condition = Condition::any().add(
+    Condition::all()
+      .add(apps::Column::OwnerId.eq(operator_id))
+      .add(apps::Column::IsGlobal.eq(false)),
+  );
+  if check_admin(db, operator_id).await.is_ok() {
+    condition = condition.add(apps::Column::IsGlobal.eq(true));
+  }
+
+  let all_apps = apps::Entity::find().filter(condition).all(db).await?;
+
+  Ok(
+    stream::iter(all_apps)
+      .map(|app| async move {
+        let app_id = app.id;
+        match refresh_app_icon(db, app).await {
+          Ok(app) => RefreshIconResult {
+            app_id,
+            icon: app.icon,
+            error: None,
+          },
+          Err(err) => RefreshIconResult {
+            app_id,
+            icon: None,
+            error: Some(err.message),
+          },
+        }
+      })
+      .buffer_unordered(CONCURRENCY)
+      .collect()
+      .await,
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn candidates(candidates: &[IconCandidate]) -> Vec<(&str, u32)> {
+    candidates
+      .iter()
+      .map(|candidate| (candidate.url.as_str(), candidate.size))
+      .collect()
+  }
+
+  #[test]
+  fn sniff_extension_checks_magic_bytes() {
+    assert_eq!(sniff_extension(b"\x89PNG\r\n\x1a\n"), Some("png"));
+    assert_eq!(sniff_extension(&[0xff, 0xd8, 0xff, 0xe0]), Some("jpg"));
+    assert_eq!(sniff_extension(b"GIF89a"), Some("gif"));
+    assert_eq!(
+      sniff_extension(&[0x00, 0x00, 0x01, 0x00, 0x01]),
+      Some("ico")
+    );
+    assert_eq!(
+      sniff_extension(b"RIFF\x24\x00\x00\x00WEBPVP8 "),
+      Some("webp")
+    );
+    assert_eq!(sniff_extension(b"RIFF\x24\x00\x00\x00WAVEfmt "), None);
+    assert_eq!(sniff_extension(b"<!DOCTYPE html>"), None);
+    assert_eq!(sniff_extension(b"<svg xmlns"), None);
+    assert_eq!(sniff_extension(&[]), None);
+  }
+
+  #[test]
+  fn parse_sizes_takes_the_largest_square_side() {
+    assert_eq!(parse_sizes("32x32"), Some(32));
+    assert_eq!(parse_sizes("16x16 192X192 48x48"), Some(192));
+    assert_eq!(parse_sizes("180x120"), Some(120));
+    assert_eq!(parse_sizes("any"), None);
+    assert_eq!(parse_sizes("any 64x64"), Some(64));
+    assert_eq!(parse_sizes(""), None);
+  }
+
+  #[test]
+  fn parse_html_collects_icons_and_manifest() {
+    let base = Url::parse("https://app.example.com/web/").unwrap();
+    let (icons, manifest) = parse_html(
+      r#"<html><head>
+        <link rel="stylesheet" href="/style.css">
+        <link rel="icon" href="favicon.ico">
+        <link rel="shortcut icon" href="/icon-64.png" sizes="64x64">
+        <link rel="icon" href="/icon.svg" type="image/svg+xml">
+        <link rel="mask-icon" href="/mask.png">
+        <link rel="apple-touch-icon-precomposed" href="https://cdn.example.com/touch.png">
+        <link rel="Manifest" href=" manifest.json ">
+        <link rel="manifest" href="/other.json">
+      </head></html>"#,
+      &base,
+    );
+
+    assert_eq!(
+      candidates(&icons),
+      vec![
+        ("https://app.example.com/web/favicon.ico", 0),
+        ("https://app.example.com/icon-64.png", 64),
+        ("https://cdn.example.com/touch.png", 180),
+      ]
+    );
+    assert_eq!(
+      manifest.as_ref().map(Url::as_str),
+      Some("https://app.example.com/web/manifest.json")
+    );
+  }
+
+  #[test]
+  fn parse_manifest_reads_sizes_and_skips_unusable_icons() {
+    let manifest_url = Url::parse("https://app.example.com/static/manifest.json").unwrap();
+    let icons = parse_manifest(
+      br#"{"name": "App", "icons": [
+        {"src": "icon-192.png", "sizes": "192x192", "type": "image/png"},
+        {"src": "/icon-512.png", "sizes": "256x256 512x512", "purpose": "any maskable"},
+        {"src": "mono.png", "sizes": "512x512", "purpose": "monochrome"},
+        {"src": "icon.svg", "sizes": "any", "type": "image/svg+xml"},
+        {"src": "icon-any.png"}
+      ]}"#,
+      &manifest_url,
+    )
+    .unwrap();
+
+    assert_eq!(
+      candidates(&icons),
+      vec![
+        ("https://app.example.com/static/icon-192.png", 192),
+        ("https://app.example.com/icon-512.png", 512),
+        ("https://app.example.com/static/icon-any.png", 0),
+      ]
+    );
+    assert!(candidates(&parse_manifest(br#"{"name": "App"}"#, &manifest_url).unwrap()).is_empty());
+    assert!(parse_manifest(b"<html>", &manifest_url).is_err());
+  }
+}
@@ -0,0 +1,907 @@
+use super::{
+  board::import_board,
+  bookmark::parse_bookmarks,
+  dashboard::resolve_dashboard,
+  file,
+  icon::{download_icon, save_data_icon, spawn_discover_icon},
+  metadata::truncate,
+  monitor::CONCURRENCY,
+  tag::{get_apps_tags, set_app_tags},
+};
+use crate::errors::AppError;
+
+use actix_web::http::StatusCode;
+use chrono::NaiveDateTime;
+use entity::{apps, groups, tags};
+use futures_util::{stream, StreamExt};
+use reqwest::Url;
+use sea_orm::{
+  entity::Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseTransaction, DbConn,
+  EntityTrait, IntoActiveModel, QueryFilter, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use utils::{deserialize::option_str_to_i64, yaml};
+use validator::Validate;
+
+// 与创建应用、分组时的长度限制保持一致
+const MAX_NAME_LENGTH: usize = 30;
+const MAX_TEXT_LENGTH: usize = 255;
+const MAX_TAG_LENGTH: usize = 20;
+
+// Homepage 与 Dashy 中只写名称的图标来自 dashboard-icons 图标库
+const DASHBOARD_ICONS_URL: &str = "https://cdn.jsdelivr.net/gh/walkxcode/dashboard-icons/png/";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportSource {
+  // Heimdall 导出的应用 JSON
+  Heimdall,
+  // Homer 的 config.yml
+  Homer,
+  // Dashy 的 conf.yml
+  Dashy,
+  // Homepage 的 services.yaml
+  Homepage,
+  // 本站导出的面板文件
+  Dash,
+  // 浏览器导出的 Netscape 书签 HTML
+  Bookmarks,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportMode {
+  // 保留已有数据，按 URL 更新同一应用
+  #[default]
+  Merge,
+  // 导入后删除文件中不存在的面板、分组、应用与标签，只适用于本站导出的面板文件
+  Replace,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FolderMode {
+  // 书签文件夹导入为分组
+  #[default]
+  Group,
+  // 书签文件夹导入为标签，应用不分组
+  Tag,
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct ImportData {
+  source: ImportSource,
+  #[validate(length(min = 1, max = 1048576, message = "导入内容不得超过 1 MB"))]
+  content: String,
+  // 为空时导入到默认面板
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  dashboard_id: Option<i64>,
+  // 配置中使用相对路径的图标基于该地址下载，一般为原面板的访问地址
+  #[validate(url(message = "图标地址格式不合法"))]
+  icon_base_url: Option<String>,
+  #[serde(default)]
+  mode: ImportMode,
+  // 书签文件夹的导入方式
+  #[serde(default)]
+  folders: FolderMode,
+  // 只解析并返回导入预览，不创建应用
+  #[serde(default)]
+  dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportApp {
+  pub name: String,
+  pub url: String,
+  pub description: Option<String>,
+  // 图标的下载地址
+  pub icon_url: Option<String>,
+  // 内嵌在书签中的 data URI 图标，内容较长，不在预览中返回
+  #[serde(skip)]
+  pub icon_data: Option<String>,
+  // 标签名称
+  pub tags: Vec<String>,
+  // 保留书签的添加时间
+  pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportGroup {
+  // 所属面板，只有本站导出的面板文件包含面板
+  pub dashboard: Option<String>,
+  // 为空时应用不属于任何分组
+  pub name: Option<String>,
+  pub apps: Vec<ImportApp>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportSkipped {
+  pub name: String,
+  pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportResult {
+  pub dry_run: bool,
+  pub groups: Vec<ImportGroup>,
+  // 无法导入的条目
+  pub skipped: Vec<ImportSkipped>,
+  pub group_count: usize,
+  pub app_count: usize,
+  // 下载失败的图标数量，这些应用会在创建后自动查找图标
+  pub icon_failed_count: usize,
+}
+
+// 各种配置解析后的条目，尚未校验
+#[derive(Default)]
+pub(super) struct RawApp {
+  pub(super) name: Option<String>,
+  pub(super) url: Option<String>,
+  pub(super) description: Option<String>,
+  pub(super) icon: Option<String>,
+  pub(super) tags: Vec<String>,
+  pub(super) created_at: Option<NaiveDateTime>,
+}
+
+pub(super) struct RawGroup {
+  pub(super) name: Option<String>,
+  pub(super) apps: Vec<RawApp>,
+}
+
+pub(super) fn invalid_content<T: std::fmt::Display>(err: T) -> AppError {
+  AppError::new(
+    StatusCode::UNPROCESSABLE_ENTITY,
+    422,
+    format!("导入内容格式不正确：{}", err),
+  )
+}
+
+fn parse_yaml(content: &str) -> Result<Value, AppError> {
+  let value = yaml::from_str(content).map_err(invalid_content)?;
+  if value.is_null() {
+    return Err(invalid_content("内容为空"));
+  }
+
+  Ok(value)
+}
+
+fn parse_heimdall(content: &str) -> Result<Vec<RawGroup>, AppError> {
+  #[derive(Deserialize)]
+  struct HeimdallItem {
+    title: Option<String>,
+    url: Option<String>,
+    description: Option<String>,
+    appdescription: Option<String>,
+    icon: Option<String>,
+  }
+
+  let items = serde_json::from_str::<Vec<HeimdallItem>>(content).map_err(invalid_content)?;
+
+  Ok(vec![RawGroup {
+    name: None,
+    apps: items
+      .into_iter()
+      .map(|item| RawApp {
+        name: item.title,
+        url: item.url,
+        description: item.description.or(item.appdescription),
+        icon: item.icon,
+        ..Default::default()
+      })
+      .collect(),
+  }])
+}
+
+fn parse_homer(content: &str) -> Result<Vec<RawGroup>, AppError> {
+  #[derive(Deserialize)]
+  struct HomerConfig {
+    #[serde(default)]
+    services: Vec<HomerService>,
+  }
+
+  #[derive(Deserialize)]
+  struct HomerService {
+    name: Option<String>,
+    #[serde(default)]
+    items: Vec<HomerItem>,
+  }
+
+  #[derive(Deserialize)]
+  struct HomerItem {
+    name: Option<String>,
+    subtitle: Option<String>,
+    url: Option<String>,
+    // 图片地址，icon 字段为 Font Awesome 图标，无法导入
+    logo: Option<String>,
+  }
+
+  let config =
+    serde_json::from_value::<HomerConfig>(parse_yaml(content)?).map_err(invalid_content)?;
+
+  Ok(
+    config
+      .services
+      .into_iter()
+      .map(|service| RawGroup {
+        name: service.name,
+        apps: service
+          .items
+          .into_iter()
+          .map(|item| RawApp {
+            name: item.name,
+            url: item.url,
+            description: item.subtitle,
+            icon: item.logo,
+            ..Default::default()
+          })
+          .collect(),
+      })
+      .collect(),
+  )
+}
+
+fn parse_dashy(content: &str) -> Result<Vec<RawGroup>, AppError> {
+  #[derive(Deserialize)]
+  struct DashyConfig {
+    #[serde(default)]
+    sections: Vec<DashySection>,
+  }
+
+  #[derive(Deserialize)]
+  struct DashySection {
+    name: Option<String>,
+    #[serde(default)]
+    items: Vec<DashyItem>,
+  }
+
+  #[derive(Deserialize)]
+  struct DashyItem {
+    title: Option<String>,
+    description: Option<String>,
+    url: Option<String>,
+    icon: Option<String>,
+  }
+
+  let config =
+    serde_json::from_value::<DashyConfig>(parse_yaml(content)?).map_err(invalid_content)?;
+
+  Ok(
+    config
+      .sections
+      .into_iter()
+      .map(|section| RawGroup {
+        name: section.name,
+        apps: section
+          .items
+          .into_iter()
+          .map(|item| RawApp {
+            name: item.title,
+            url: item.url,
+            description: item.description,
+            // hl- 开头的图标来自 dashboard-icons 图标库
+            icon: item.icon.map(|icon| match icon.strip_prefix("hl-") {
+              Some(name) => format!("{}.png", name),
+              None => icon,
+            }),
+            ..Default::default()
+          })
+          .collect(),
+      })
+      .collect(),
+  )
+}
+
+// Homepage 的分组与服务都是以名称为键的单键对象，如 `- 媒体: [- Jellyfin: {href: ...}]`
+fn parse_homepage(content: &str) -> Result<Vec<RawGroup>, AppError> {
+  fn entries(value: Value) -> Vec<(String, Value)> {
+    match value {
+      Value::Array(array) => array
+        .into_iter()
+        .filter_map(|entry| match entry {
+          Value::Object(object) => Some(object),
+          _ => None,
+        })
+        .flatten()
+        .collect(),
+      Value::Object(object) => object.into_iter().collect(),
+      _ => Vec::new(),
+    }
+  }
+
+  fn text(service: &Value, key: &str) -> Option<String> {
+    service.get(key).and_then(Value::as_str).map(String::from)
+  }
+
+  // 嵌套的分组展开到上一级分组中
+  fn collect_apps(services: Value, apps: &mut Vec<RawApp>) {
+    for (name, service) in entries(services) {
+      if service.is_array() {
+        collect_apps(service, apps);
+        continue;
+      }
+
+      apps.push(RawApp {
+        url: text(&service, "href"),
+        description: text(&service, "description"),
+        icon: text(&service, "icon"),
+        name: Some(name),
+        ..Default::default()
+      });
+    }
+  }
+
+  Ok(
+    entries(parse_yaml(content)?)
+      .into_iter()
+      .map(|(name, services)| {
+        let mut apps = Vec::new();
+        collect_apps(services, &mut apps);
+        RawGroup {
+          name: Some(name),
+          apps,
+        }
+      })
+      .collect(),
+  )
+}
+
+// 解析图标的下载地址，Font Awesome、Material Design 等字体图标无法导入
+fn resolve_icon(icon: &str, icon_base_url: Option<&Url>) -> Option<String> {
+  let icon = icon.trim();
+  if icon.starts_with("http://") || icon.starts_with("https://") {
+    return Some(icon.to_string());
+  }
+
+  let lower = icon.to_ascii_lowercase();
+  let is_image = [".png", ".jpg", ".jpeg", ".gif", ".ico", ".webp", ".svg"]
+    .iter()
+    .any(|extension| lower.ends_with(extension));
+  if !is_image {
+    return None;
+  }
+
+  // 只有文件名的图标来自 dashboard-icons，SVG 图标使用对应的 PNG 版本
+  if !icon.contains('/') {
+    let name = icon.rsplit_once('.').map_or(icon, |(name, _)| name);
+    return Some(format!("{}{}.png", DASHBOARD_ICONS_URL, name));
+  }
+
+  icon_base_url
+    .and_then(|base| base.join(icon).ok())
+    .map(String::from)
+}
+
+fn is_valid_url(url: &str) -> bool {
+  Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host())
+}
+
+fn normalize(text: Option<String>) -> Option<String> {
+  text
+    .map(|text| text.split_whitespace().collect::<Vec<&str>>().join(" "))
+    .filter(|text| !text.is_empty())
+}
+
+// 校验并转换解析出的条目，无法导入的条目记录原因
+fn convert(
+  raw_groups: Vec<RawGroup>,
+  icon_base_url: Option<&Url>,
+) -> (Vec<ImportGroup>, Vec<ImportSkipped>) {
+  let mut groups = Vec::new();
+  let mut skipped = Vec::new();
+
+  for raw_group in raw_groups {
+    let mut apps = Vec::new();
+    for raw_app in raw_group.apps {
+      let name = normalize(raw_app.name);
+      let Some(url) = normalize(raw_app.url) else {
+        skipped.push(ImportSkipped {
+          name: name.unwrap_or_default(),
+          reason: "缺少应用 URL".to_string(),
+        });
+        continue;
+      };
+      if url.chars().count() > MAX_TEXT_LENGTH || !is_valid_url(&url) {
+        skipped.push(ImportSkipped {
+          name: name.unwrap_or(url),
+          reason: "应用 URL 格式不合法或长度超过 255 个字符".to_string(),
+        });
+        continue;
+      }
+
+      let name = name.unwrap_or_else(|| {
+        Url::parse(&url)
+          .ok()
+          .and_then(|url| url.host_str().map(String::from))
+          .unwrap_or_else(|| url.clone())
+      });
+      let (icon_url, icon_data) = match raw_app.icon {
+        Some(icon) if icon.starts_with("data:") => (None, Some(icon)),
+        Some(icon) => (resolve_icon(&icon, icon_base_url), None),
+        None => (None, None),
+      };
+      let mut tags = Vec::new();
+      for tag in raw_app
+        .tags
+        .into_iter()
+        .filter_map(|tag| normalize(Some(tag)))
+      {
+        let tag = truncate(&tag, MAX_TAG_LENGTH);
+        if !tags.contains(&tag) {
+          tags.push(tag);
+        }
+      }
+      apps.push(ImportApp {
+        name: truncate(&name, MAX_NAME_LENGTH),
+        description: normalize(raw_app.description)
+          .map(|description| truncate(&description, MAX_TEXT_LENGTH)),
+        icon_url,
+        icon_data,
+        tags,
+        created_at: raw_app.created_at,
+        url,
+      });
+    }
+
+    if !apps.is_empty() {
+      groups.push(ImportGroup {
+        dashboard: None,
+        name: normalize(raw_group.name).map(|name| truncate(&name, MAX_NAME_LENGTH)),
+        apps,
+      });
+    }
+  }
+
+  (groups, skipped)
+}
+
+// 查询同名的标签，不存在时创建
+async fn ensure_tags<'a, C: ConnectionTrait>(
+  db: &C,
+  operator_id: i64,
+  names: impl Iterator<Item = &'a String>,
+) -> Result<HashMap<String, i64>, AppError> {
+  let names = names.cloned().collect::<HashSet<String>>();
+  if names.is_empty() {
+    return Ok(HashMap::new());
+  }
+
+  let mut tag_ids = tags::Entity::find()
+    .filter(tags::Column::OwnerId.eq(operator_id))
+    .filter(tags::Column::Name.is_in(names.clone()))
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|tag| (tag.name, tag.id))
+    .collect::<HashMap<String, i64>>();
+
+  for name in names {
+    if tag_ids.contains_key(&name) {
+      continue;
+    }
+
+    let tag = tags::ActiveModel {
+      name: Set(name.clone()),
+      owner_id: Set(operator_id),
+      ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    tag_ids.insert(name, tag.id);
+  }
+
+  Ok(tag_ids)
+}
+
+// 按名称匹配面板中已有的分组，按 URL 匹配面板中已有的应用，重复导入同一配置时不会重复创建，返回新创建的应用
+async fn save_groups(
+  txn: &DatabaseTransaction,
+  operator_id: i64,
+  dashboard_id: i64,
+  groups: &[ImportGroup],
+  mut icons: impl Iterator<Item = Option<String>>,
+) -> Result<Vec<apps::Model>, AppError> {
+  let tag_ids = ensure_tags(
+    txn,
+    operator_id,
+    groups
+      .iter()
+      .flat_map(|group| group.apps.iter().flat_map(|app| app.tags.iter())),
+  )
+  .await?;
+
+  let user_groups = groups::Entity::find()
+    .filter(groups::Column::OwnerId.eq(operator_id))
+    .all(txn)
+    .await?;
+  let user_apps = apps::Entity::find()
+    .filter(apps::Column::OwnerId.eq(operator_id))
+    .filter(apps::Column::IsGlobal.eq(false))
+    .all(txn)
+    .await?;
+  let mut apps_tags = get_apps_tags(txn, user_apps.iter().map(|app| app.id).collect()).await?;
+
+  // 新的分组与应用排在已有的之后
+  let mut group_index = user_groups
+    .iter()
+    .map(|group| group.index + 1)
+    .max()
+    .unwrap_or(0);
+  let mut app_index = user_apps.iter().map(|app| app.index + 1).max().unwrap_or(0);
+  let mut kept_group_ids = HashSet::new();
+  let mut kept_app_ids = HashSet::new();
+  let mut created_apps = Vec::new();
+
+  for group in groups {
+    let group_id = match &group.name {
+      Some(name) => {
+        let existing = user_groups.iter().find(|model| {
+          model.name == *name
+            && model.dashboard_id == Some(dashboard_id)
+            && !kept_group_ids.contains(&model.id)
+        });
+        let group_id = match existing {
+          Some(model) => model.id,
+          None => {
+            let model = groups::ActiveModel {
+              name: Set(name.clone()),
+              collapsed: Set(false),
+              index: Set(group_index),
+              owner_id: Set(operator_id),
+              dashboard_id: Set(Some(dashboard_id)),
+              ..Default::default()
+            }
+            .insert(txn)
+            .await?;
+            group_index += 1;
+            model.id
+          }
+        };
+        kept_group_ids.insert(group_id);
+        Some(group_id)
+      }
+      None => None,
+    };
+
+    for app in &group.apps {
+      let icon = icons.next().flatten();
+      let existing = user_apps
+        .iter()
+        .find(|model| {
+          model.url == app.url
+            && model.dashboard_id == Some(dashboard_id)
+            && !kept_app_ids.contains(&model.id)
+        })
+        .cloned();
+
+      // 已有的应用保留原有的图标、排序与标签，只更新导入的信息
+      let (model, mut app_tag_ids) = match existing {
+        Some(model) => {
+          let app_tag_ids = apps_tags
+            .remove(&model.id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|tag| tag.id)
+            .collect::<Vec<i64>>();
+          let has_icon = model.icon.is_some();
+          let mut model = model.into_active_model();
+          model.name = Set(app.name.clone());
+          if app.description.is_some() {
+            model.description = Set(app.description.clone());
+          }
+          if !has_icon {
+            model.icon = Set(icon);
+          }
+          model.group_id = Set(group_id);
+          (model.update(txn).await?, app_tag_ids)
+        }
+        None => {
+          let mut model = apps::ActiveModel {
+            name: Set(app.name.clone()),
+            url: Set(app.url.clone()),
+            description: Set(app.description.clone()),
+            icon: Set(icon),
+            index: Set(app_index),
+            owner_id: Set(operator_id),
+            group_id: Set(group_id),
+            dashboard_id: Set(Some(dashboard_id)),
+            ..Default::default()
+          };
+          if let Some(created_at) = app.created_at {
+            model.created_at = Set(created_at);
+          }
+          let model = model.insert(txn).await?;
+          app_index += 1;
+          created_apps.push(model.clone());
+          (model, Vec::new())
+        }
+      };
+
+      for &tag_id in app.tags.iter().filter_map(|tag| tag_ids.get(tag)) {
+        if !app_tag_ids.contains(&tag_id) {
+          app_tag_ids.push(tag_id);
+        }
+      }
+      set_app_tags(txn, operator_id, model.id, &app_tag_ids).await?;
+      kept_app_ids.insert(model.id);
+    }
+  }
+
+  Ok(created_apps)
+}
+
+// 导入其他面板的配置，分类导入为分组，预览模式下只返回解析结果
+pub async fn import_apps(
+  db: &DbConn,
+  operator_id: i64,
+  data: &ImportData,
+) -> Result<ImportResult, AppError> {
+  let raw_groups = match data.source {
+    ImportSource::Heimdall => parse_heimdall(&data.content)?,
+    ImportSource::Homer => parse_homer(&data.content)?,
+    ImportSource::Dashy => parse_dashy(&data.content)?,
+    ImportSource::Homepage => parse_homepage(&data.content)?,
+    ImportSource::Bookmarks => parse_bookmarks(&data.content, data.folders),
+    ImportSource::Dash => {
+      return import_board(db, operator_id, &data.content, data.mode, data.dry_run).await;
+    }
+  };
+  // 其他面板的配置不包含完整的面板数据，只能合并导入
+  if let ImportMode::Replace = data.mode {
+    return Err(AppError::new(
+      StatusCode::UNPROCESSABLE_ENTITY,
+      422,
+      "替换模式只适用于本站导出的面板文件",
+    ));
+  }
+
+  let icon_base_url = data
+    .icon_base_url
+    .as_deref()
+    .and_then(|url| Url::parse(url).ok());
+  let (groups, skipped) = convert(raw_groups, icon_base_url.as_ref());
+
+  let mut result = ImportResult {
+    dry_run: data.dry_run,
+    group_count: groups.iter().filter(|group| group.name.is_some()).count(),
+    app_count: groups.iter().map(|group| group.apps.len()).sum(),
+    icon_failed_count: 0,
+    groups,
+    skipped,
+  };
+  // 预览时也校验面板是否存在
+  let dashboard = resolve_dashboard(db, operator_id, data.dashboard_id).await?;
+  if data.dry_run {
+    return Ok(result);
+  }
+
+  // 先并发下载全部图标，再在事务中按原顺序保存
+  let icons = stream::iter(result.groups.iter().flat_map(|group| group.apps.iter()))
+    .map(|app| async move {
+      let icon = match (&app.icon_data, &app.icon_url) {
+        (Some(icon_data), _) => save_data_icon(operator_id, icon_data),
+        (None, Some(icon_url)) => match Url::parse(icon_url) {
+          Ok(url) => download_icon(operator_id, &url).await,
+          Err(err) => Err(err.into()),
+        },
+        (None, None) => return None,
+      };
+      Some(icon.map_err(|err| log::debug!("Save icon for {} failed: {}", app.url, err)))
+    })
+    .buffered(CONCURRENCY)
+    .collect::<Vec<Option<Result<String, ()>>>>()
+    .await;
+  result.icon_failed_count = icons
+    .iter()
+    .filter(|icon| matches!(icon, Some(Err(_))))
+    .count();
+  let icons = icons
+    .into_iter()
+    .map(|icon| icon.and_then(Result::ok))
+    .collect::<Vec<Option<String>>>();
+  let saved_icons = icons.iter().flatten().cloned().collect::<Vec<String>>();
+
+  // 导入失败时不保留任何数据，已下载的图标也一并删除
+  let created_apps = async {
+    let txn = db.begin().await?;
+    let created_apps = save_groups(
+      &txn,
+      operator_id,
+      dashboard.id,
+      &result.groups,
+      icons.into_iter(),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok::<_, AppError>(created_apps)
+  }
+  .await
+  .inspect_err(|_| file::remove(&saved_icons))?;
+
+  for app in &created_apps {
+    spawn_discover_icon(db, app);
+  }
+
+  Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn app_names(group: &RawGroup) -> Vec<&str> {
+    group
+      .apps
+      .iter()
+      .map(|app| app.name.as_deref().unwrap_or_default())
+      .collect()
+  }
+
+  #[test]
+  fn parse_heimdall_reads_items_into_one_group() {
+    let groups = parse_heimdall(
+      r#"[
+        {"title": "Grafana", "url": "https://grafana.example.com", "appdescription": "监控", "icon": "grafana.png"},
+        {"title": "Jellyfin", "url": "https://jf.example.com", "description": "媒体"}
+      ]"#,
+    )
+    .unwrap();
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].name, None);
+    assert_eq!(app_names(&groups[0]), vec!["Grafana", "Jellyfin"]);
+    assert_eq!(groups[0].apps[0].description.as_deref(), Some("监控"));
+    assert_eq!(groups[0].apps[0].icon.as_deref(), Some("grafana.png"));
+    assert_eq!(groups[0].apps[1].description.as_deref(), Some("媒体"));
+    assert!(parse_heimdall("{}").is_err());
+  }
+
+  #[test]
+  fn parse_homer_reads_services_as_groups() {
+    let groups = parse_homer(
+      r#"
+title: Home
+services:
+  - name: 监控
+    items:
+      - name: Grafana
+        subtitle: 仪表盘
+        url: https://grafana.example.com
+        logo: assets/tools/grafana.png
+  - name: 空分组
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].name.as_deref(), Some("监控"));
+    assert_eq!(app_names(&groups[0]), vec!["Grafana"]);
+    let app = &groups[0].apps[0];
+    assert_eq!(app.url.as_deref(), Some("https://grafana.example.com"));
+    assert_eq!(app.description.as_deref(), Some("仪表盘"));
+    assert_eq!(app.icon.as_deref(), Some("assets/tools/grafana.png"));
+    assert!(groups[1].apps.is_empty());
+  }
+
+  #[test]
+  fn parse_dashy_maps_dashboard_icons() {
+    let groups = parse_dashy(
+      r#"
+sections:
+  - name: 媒体
+    items:
+      - title: Jellyfin
+        url: https://jf.example.com
+        icon: hl-jellyfin
+      - title: Plex
+        url: https://plex.example.com
+        icon: fas fa-film
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(app_names(&groups[0]), vec!["Jellyfin", "Plex"]);
+    assert_eq!(groups[0].apps[0].icon.as_deref(), Some("jellyfin.png"));
+    assert_eq!(groups[0].apps[1].icon.as_deref(), Some("fas fa-film"));
+  }
+
+  #[test]
+  fn parse_homepage_flattens_nested_groups() {
+    let groups = parse_homepage(
+      r#"
+- 媒体:
+    - Jellyfin:
+        href: https://jf.example.com
+        description: 影音
+        icon: jellyfin.png
+    - 下载:
+        - qBittorrent:
+            href: https://qb.example.com
+- 监控:
+    - Grafana:
+        href: https://grafana.example.com
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].name.as_deref(), Some("媒体"));
+    assert_eq!(app_names(&groups[0]), vec!["Jellyfin", "qBittorrent"]);
+    assert_eq!(groups[0].apps[0].description.as_deref(), Some("影音"));
+    assert_eq!(
+      groups[0].apps[1].url.as_deref(),
+      Some("https://qb.example.com")
+    );
+    assert_eq!(groups[1].name.as_deref(), Some("监控"));
+    assert_eq!(app_names(&groups[1]), vec!["Grafana"]);
+    assert!(parse_homepage("").is_err());
+  }
+
+  #[test]
+  fn convert_skips_invalid_entries_and_resolves_icons() {
+    let raw_groups = vec![
+      RawGroup {
+        name: Some("  监控  工具 ".into()),
+        apps: vec![
+          RawApp {
+            name: Some("Grafana".into()),
+            url: Some("https://grafana.example.com".into()),
+            icon: Some("assets/grafana.png".into()),
+            tags: vec!["运维".into(), " 运维 ".into(), "".into()],
+            ..Default::default()
+          },
+          RawApp {
+            url: Some("https://jf.example.com/web".into()),
+            icon: Some("jellyfin.svg".into()),
+            ..Default::default()
+          },
+          RawApp {
+            name: Some("缺少地址".into()),
+            ..Default::default()
+          },
+          RawApp {
+            name: Some("FTP".into()),
+            url: Some("ftp://files.example.com".into()),
+            ..Default::default()
+          },
+        ],
+      },
+      // 全部条目都无法导入的分组不保留
+      RawGroup {
+        name: Some("空分组".into()),
+        apps: vec![RawApp {
+          name: Some("相对地址".into()),
+          url: Some("/admin".into()),
+          ..Default::default()
+        }],
+      },
+    ];
+    let base = Url::parse("https://homer.example.com/").unwrap();
+
+    let (groups, skipped) = convert(raw_groups, Some(&base));
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].name.as_deref(), Some("监控 工具"));
+    let apps = &groups[0].apps;
+    assert_eq!(apps.len(), 2);
+    assert_eq!(
+      apps[0].icon_url.as_deref(),
+      Some("https://homer.example.com/assets/grafana.png")
+    );
+    assert_eq!(apps[0].tags, vec!["运维"]);
+    // 缺少名称时使用域名
+    assert_eq!(apps[1].name, "jf.example.com");
+    assert_eq!(
+      apps[1].icon_url,
+      Some(format!("{}jellyfin.png", DASHBOARD_ICONS_URL))
+    );
+
+    let skipped = skipped
+      .iter()
+      .map(|skipped| skipped.name.as_str())
+      .collect::<Vec<&str>>();
+    assert_eq!(skipped, vec!["缺少地址", "FTP", "相对地址"]);
+  }
+}
@@ -0,0 +1,51 @@
+use entity::apps::{AppLink, AppLinks};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+pub struct AppLinkData {
+  #[validate(length(min = 1, max = 30, message = "链接名称长度不得超过 30 个字符"))]
+  name: String,
+  #[validate(
+    length(min = 1, max = 255, message = "链接 URL 长度不得超过 255 个字符"),
+    url(message = "链接 URL 格式不合法")
+  )]
+  url: String,
+  #[validate(length(min = 1, max = 255, message = "链接图标长度不得超过 255 个字符"))]
+  icon: Option<String>,
+}
+
+impl From<&AppLinkData> for AppLink {
+  fn from(data: &AppLinkData) -> Self {
+    AppLink {
+      name: data.name.clone(),
+      url: data.url.clone(),
+      icon: data.icon.clone(),
+    }
+  }
+}
+
+impl From<&AppLink> for AppLinkData {
+  fn from(link: &AppLink) -> Self {
+    AppLinkData {
+      name: link.name.clone(),
+      url: link.url.clone(),
+      icon: link.icon.clone(),
+    }
+  }
+}
+
+// 没有附加链接时保存为空
+pub(super) fn to_app_links(links: &[AppLinkData]) -> Option<AppLinks> {
+  match links.is_empty() {
+    true => None,
+    false => Some(AppLinks(links.iter().map(Into::into).collect())),
+  }
+}
+
+// 导出时转换为可重新导入的数据
+pub(super) fn to_links_data(links: Option<&AppLinks>) -> Vec<AppLinkData> {
+  links
+    .map(|links| links.0.iter().map(Into::into).collect())
+    .unwrap_or_default()
+}
@@ -0,0 +1,479 @@
+use crate::errors::AppError;
+
+use actix_web::http::StatusCode;
+use chrono::{Duration as ChronoDuration, NaiveDateTime, Utc};
+use cron::Schedule;
+use entity::{
+  apps, groups,
+  maintenances::{self, TargetIds},
+  users,
+};
+use sea_orm::{
+  entity::Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, DbErr, DeleteResult,
+  EntityTrait, IntoActiveModel, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, str::FromStr};
+use utils::{
+  deserialize::{str_to_i64, vec_str_to_i64},
+  serialize::i64_to_str,
+};
+use validator::{Validate, ValidationError};
+
+// 支持标准的 5 位 cron 表达式，缺少秒时补全为 0 秒
+fn parse_cron(expr: &str) -> Result<Schedule, cron::error::Error> {
+  match expr.split_whitespace().count() {
+    5 => Schedule::from_str(&format!("0 {}", expr)),
+    _ => Schedule::from_str(expr),
+  }
+}
+
+// 当前或下一次维护窗口，维护已结束或未启用时为空，cron 表达式按 UTC 时间计算
+pub fn next_window(
+  maintenance: &maintenances::Model,
+  now: NaiveDateTime,
+) -> Option<(NaiveDateTime, NaiveDateTime)> {
+  if !maintenance.enabled {
+    return None;
+  }
+
+  let (starts_at, ends_at) = match (&maintenance.cron, maintenance.duration) {
+    (Some(cron), Some(duration)) => {
+      let duration = ChronoDuration::minutes(duration as i64);
+      // 从 now - duration 之后查找，正在进行的窗口也会被找到
+      let from = (now - duration).max(maintenance.starts_at - ChronoDuration::seconds(1));
+      let starts_at = parse_cron(cron)
+        .ok()?
+        .after(&from.and_utc())
+        .next()?
+        .naive_utc();
+
+      if maintenance
+        .ends_at
+        .is_some_and(|ends_at| starts_at >= ends_at)
+      {
+        return None;
+      }
+
+      (starts_at, starts_at + duration)
+    }
+    _ => (maintenance.starts_at, maintenance.ends_at?),
+  };
+
+  (ends_at > now).then_some((starts_at, ends_at))
+}
+
+pub fn is_target(maintenance: &maintenances::Model, app: &apps::Model) -> bool {
+  maintenance.app_ids.0.contains(&app.id)
+    || app
+      .group_id
+      .is_some_and(|group_id| maintenance.group_ids.0.contains(&group_id))
+}
+
+pub async fn get_enabled_maintenance<C: ConnectionTrait>(
+  db: &C,
+) -> Result<Vec<maintenances::Model>, DbErr> {
+  maintenances::Entity::find()
+    .filter(maintenances::Column::Enabled.eq(true))
+    .all(db)
+    .await
+}
+
+// 应用当前所处的维护窗口
+#[derive(Debug, Clone, Serialize)]
+pub struct ActiveMaintenance {
+  #[serde(serialize_with = "i64_to_str")]
+  pub id: i64,
+  pub title: String,
+  pub description: Option<String>,
+  pub starts_at: NaiveDateTime,
+  pub ends_at: NaiveDateTime,
+}
+
+// 查询处于维护中的应用，同一应用处于多个维护窗口时取最晚结束的窗口
+pub async fn get_apps_maintenance<C: ConnectionTrait>(
+  db: &C,
+  apps: &[apps::Model],
+) -> Result<HashMap<i64, ActiveMaintenance>, DbErr> {
+  let now = Utc::now().naive_utc();
+  let active_maintenances = get_enabled_maintenance(db)
+    .await?
+    .into_iter()
+    .filter_map(|maintenance| {
+      let (starts_at, ends_at) = next_window(&maintenance, now)?;
+      (starts_at <= now)
+        .then_some(ActiveMaintenance {
+          id: maintenance.id,
+          title: maintenance.title.clone(),
+          description: maintenance.description.clone(),
+          starts_at,
+          ends_at,
+        })
+        .map(|active| (maintenance, active))
+    })
+    .collect::<Vec<(maintenances::Model, ActiveMaintenance)>>();
+
+  let mut apps_maintenance = HashMap::new();
+  for app in apps {
+    let active = active_maintenances
+      .iter()
+      .filter(|(maintenance, _)| is_target(maintenance, app))
+      .map(|(_, active)| active)
+      .max_by_key(|active| active.ends_at);
+
+    if let Some(active) = active {
+      apps_maintenance.insert(app.id, active.clone());
+    }
+  }
+
+  Ok(apps_maintenance)
+}
+
+#[derive(Debug, Serialize)]
+pub struct MaintenanceInfo {
+  #[serde(flatten)]
+  pub maintenance: maintenances::Model,
+  // 当前或下一次维护窗口
+  pub next_starts_at: Option<NaiveDateTime>,
+  pub next_ends_at: Option<NaiveDateTime>,
+  pub active: bool,
+}
+
+impl From<maintenances::Model> for MaintenanceInfo {
+  fn from(maintenance: maintenances::Model) -> Self {
+    let now = Utc::now().naive_utc();
+    let window = next_window(&maintenance, now);
+
+    MaintenanceInfo {
+      next_starts_at: window.map(|(starts_at, _)| starts_at),
+      next_ends_at: window.map(|(_, ends_at)| ends_at),
+      active: window.is_some_and(|(starts_at, _)| starts_at <= now),
+      maintenance,
+    }
+  }
+}
+
+pub async fn get_user_all_maintenance(
+  db: &DbConn,
+  user_id: i64,
+) -> Result<Vec<MaintenanceInfo>, AppError> {
+  Ok(
+    maintenances::Entity::find()
+      .filter(maintenances::Column::OwnerId.eq(user_id))
+      .order_by_asc(maintenances::Column::CreatedAt)
+      .all(db)
+      .await?
+      .into_iter()
+      .map(Into::into)
+      .collect(),
+  )
+}
+
+async fn check_maintenance_owner(
+  db: &DbConn,
+  operator_id: i64,
+  maintenance_id: i64,
+) -> Result<maintenances::Model, AppError> {
+  maintenances::Entity::find_by_id(maintenance_id)
+    .filter(maintenances::Column::OwnerId.eq(operator_id))
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "维护计划不存在"))
+}
+
+fn unique_count(ids: &[i64]) -> u64 {
+  let mut ids = ids.to_vec();
+  ids.sort_unstable();
+  ids.dedup();
+  ids.len() as u64
+}
+
+// 只能维护自己的应用与分组，管理员还可以维护全站应用
+async fn check_targets(
+  db: &DbConn,
+  operator_id: i64,
+  data: &MaintenanceData,
+) -> Result<(), AppError> {
+  let user = users::Entity::find_by_id(operator_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户不存在"))?;
+
+  let target_apps = apps::Entity::find()
+    .filter(apps::Column::Id.is_in(data.app_ids.clone()))
+    .all(db)
+    .await?;
+  let allowed = target_apps
+    .iter()
+    .all(|app| app.owner_id == operator_id || (app.is_global && user.is_admin));
+  if !allowed || target_apps.len() as u64 != unique_count(&data.app_ids) {
+    return Err(AppError::new(StatusCode::NOT_FOUND, 404, "应用不存在"));
+  }
+
+  let group_count = groups::Entity::find()
+    .filter(groups::Column::Id.is_in(data.group_ids.clone()))
+    .filter(groups::Column::OwnerId.eq(operator_id))
+    .count(db)
+    .await?;
+  if group_count != unique_count(&data.group_ids) {
+    return Err(AppError::new(StatusCode::NOT_FOUND, 404, "分组不存在"));
+  }
+
+  Ok(())
+}
+
+fn validate_cron(expr: &str) -> Result<(), ValidationError> {
+  parse_cron(expr)
+    .map(|_| ())
+    .map_err(|_| ValidationError::new("cron"))
+}
+
+fn validate_maintenance(data: &MaintenanceData) -> Result<(), ValidationError> {
+  if data.app_ids.is_empty() && data.group_ids.is_empty() {
+    return Err(ValidationError::new("targets").with_message("请选择维护的应用或分组".into()));
+  }
+
+  if data.cron.is_some() && data.duration.is_none() {
+    return Err(ValidationError::new("duration").with_message("周期维护需要设置持续时间".into()));
+  }
+
+  match (data.cron.as_ref(), data.ends_at) {
+    (None, None) => {
+      Err(ValidationError::new("ends_at").with_message("一次性维护需要设置结束时间".into()))
+    }
+    (_, Some(ends_at)) if data.starts_at.is_some_and(|starts_at| ends_at <= starts_at) => {
+      Err(ValidationError::new("ends_at").with_message("结束时间必须晚于开始时间".into()))
+    }
+    _ => Ok(()),
+  }
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+#[validate(schema(function = "validate_maintenance"))]
+pub struct MaintenanceData {
+  #[validate(length(min = 1, max = 100, message = "维护标题长度不得超过 100 个字符"))]
+  title: String,
+  #[validate(length(min = 1, max = 255, message = "维护说明长度不得超过 255 个字符"))]
+  description: Option<String>,
+  #[serde(default, deserialize_with = "vec_str_to_i64")]
+  #[validate(length(max = 100, message = "维护的应用不得超过 100 个"))]
+  app_ids: Vec<i64>,
+  #[serde(default, deserialize_with = "vec_str_to_i64")]
+  #[validate(length(max = 100, message = "维护的分组不得超过 100 个"))]
+  group_ids: Vec<i64>,
+  // 开始时间，为空时使用当前时间
+  starts_at: Option<NaiveDateTime>,
+  ends_at: Option<NaiveDateTime>,
+  #[validate(
+    length(min = 1, max = 100, message = "cron 表达式长度不得超过 100 个字符"),
+    custom(function = "validate_cron", message = "cron 表达式不合法")
+  )]
+  cron: Option<String>,
+  #[validate(range(min = 1, max = 10080, message = "持续时间必须为 1 ~ 10080 分钟"))]
+  duration: Option<i32>,
+  #[serde(default = "default_enabled")]
+  enabled: bool,
+}
+
+fn default_enabled() -> bool {
+  true
+}
+
+pub async fn create_maintenance(
+  db: &DbConn,
+  operator_id: i64,
+  data: &MaintenanceData,
+) -> Result<MaintenanceInfo, AppError> {
+  check_targets(db, operator_id, data).await?;
+
+  let now = Utc::now().naive_utc();
+  let starts_at = data.starts_at.unwrap_or(now);
+  if data.ends_at.is_some_and(|ends_at| ends_at <= starts_at) {
+    return Err(AppError::new(
+      StatusCode::UNPROCESSABLE_ENTITY,
+      422,
+      "结束时间必须晚于开始时间",
+    ));
+  }
+
+  let maintenance = maintenances::ActiveModel {
+    title: Set(data.title.clone()),
+    description: Set(data.description.clone()),
+    app_ids: Set(TargetIds(data.app_ids.clone())),
+    group_ids: Set(TargetIds(data.group_ids.clone())),
+    starts_at: Set(starts_at),
+    ends_at: Set(data.ends_at),
+    cron: Set(data.cron.clone()),
+    duration: Set(data.cron.as_ref().and(data.duration)),
+    enabled: Set(data.enabled),
+    owner_id: Set(operator_id),
+    created_at: Set(now),
+    ..Default::default()
+  }
+  .insert(db)
+  .await?;
+
+  Ok(maintenance.into())
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct UpdateMaintenanceData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+  #[serde(flatten)]
+  #[validate(nested)]
+  data: MaintenanceData,
+}
+
+pub async fn update_maintenance(
+  db: &DbConn,
+  operator_id: i64,
+  data: &UpdateMaintenanceData,
+) -> Result<MaintenanceInfo, AppError> {
+  let maintenance = check_maintenance_owner(db, operator_id, data.id).await?;
+  let data = &data.data;
+  check_targets(db, operator_id, data).await?;
+
+  let starts_at = data.starts_at.unwrap_or(maintenance.starts_at);
+  if data.ends_at.is_some_and(|ends_at| ends_at <= starts_at) {
+    return Err(AppError::new(
+      StatusCode::UNPROCESSABLE_ENTITY,
+      422,
+      "结束时间必须晚于开始时间",
+    ));
+  }
+
+  let mut maintenance = maintenance.into_active_model();
+
+  maintenance.title = Set(data.title.clone());
+  maintenance.description = Set(data.description.clone());
+  maintenance.app_ids = Set(TargetIds(data.app_ids.clone()));
+  maintenance.group_ids = Set(TargetIds(data.group_ids.clone()));
+  maintenance.starts_at = Set(starts_at);
+  maintenance.ends_at = Set(data.ends_at);
+  maintenance.cron = Set(data.cron.clone());
+  maintenance.duration = Set(data.cron.as_ref().and(data.duration));
+  maintenance.enabled = Set(data.enabled);
+
+  Ok(maintenance.update(db).await?.into())
+}
+
+pub async fn delete_maintenance(
+  db: &DbConn,
+  operator_id: i64,
+  maintenance_id: i64,
+) -> Result<DeleteResult, AppError> {
+  check_maintenance_owner(db, operator_id, maintenance_id)
+    .await?
+    .delete(db)
+    .await
+    .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::NaiveDate;
+
+  fn time(day: u32, hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2026, 10, day)
+      .and_then(|date| date.and_hms_opt(hour, minute, second))
+      .unwrap()
+  }
+
+  fn maintenance(
+    starts_at: NaiveDateTime,
+    ends_at: Option<NaiveDateTime>,
+    cron: Option<&str>,
+    duration: Option<i32>,
+  ) -> maintenances::Model {
+    maintenances::Model {
+      id: 1,
+      title: "升级".into(),
+      description: None,
+      app_ids: TargetIds(vec![1]),
+      group_ids: TargetIds::default(),
+      starts_at,
+      ends_at,
+      cron: cron.map(String::from),
+      duration,
+      enabled: true,
+      owner_id: 1,
+      created_at: starts_at,
+    }
+  }
+
+  #[test]
+  fn parse_cron_prepends_seconds_to_five_fields() {
+    let five = parse_cron("30 2 * * *").unwrap();
+    let six = parse_cron("0 30 2 * * *").unwrap();
+    let from = time(19, 0, 0, 0).and_utc();
+    assert_eq!(five.after(&from).next(), six.after(&from).next());
+    assert_eq!(
+      five.after(&from).next().map(|time| time.naive_utc()),
+      Some(time(19, 2, 30, 0))
+    );
+    assert!(parse_cron("* * *").is_err());
+    assert!(parse_cron("61 * * * *").is_err());
+  }
+
+  #[test]
+  fn next_window_for_one_off_maintenance_ends_exclusively() {
+    let window = (time(19, 10, 0, 0), time(19, 12, 0, 0));
+    let one_off = maintenance(window.0, Some(window.1), None, None);
+
+    assert_eq!(next_window(&one_off, time(19, 9, 0, 0)), Some(window));
+    assert_eq!(next_window(&one_off, window.0), Some(window));
+    assert_eq!(next_window(&one_off, time(19, 11, 59, 59)), Some(window));
+    assert_eq!(next_window(&one_off, window.1), None);
+
+    let disabled = maintenances::Model {
+      enabled: false,
+      ..one_off
+    };
+    assert_eq!(next_window(&disabled, time(19, 11, 0, 0)), None);
+    let endless = maintenance(window.0, None, None, None);
+    assert_eq!(next_window(&endless, time(19, 11, 0, 0)), None);
+  }
+
+  #[test]
+  fn next_window_for_cron_maintenance_at_window_boundaries() {
+    // 每天 02:00 开始，持续 60 分钟
+    let daily = maintenance(time(1, 0, 0, 0), None, Some("0 2 * * *"), Some(60));
+    let today = (time(19, 2, 0, 0), time(19, 3, 0, 0));
+    let tomorrow = (time(20, 2, 0, 0), time(20, 3, 0, 0));
+
+    assert_eq!(next_window(&daily, time(19, 1, 59, 59)), Some(today));
+    assert_eq!(next_window(&daily, today.0), Some(today));
+    assert_eq!(next_window(&daily, time(19, 2, 59, 59)), Some(today));
+    assert_eq!(next_window(&daily, today.1), Some(tomorrow));
+  }
+
+  #[test]
+  fn next_window_for_cron_maintenance_respects_start_and_end() {
+    // 开始时间正好是一个窗口的开始时，该窗口有效
+    let starts_on_window = maintenance(time(19, 2, 0, 0), None, Some("0 2 * * *"), Some(60));
+    assert_eq!(
+      next_window(&starts_on_window, time(18, 12, 0, 0)),
+      Some((time(19, 2, 0, 0), time(19, 3, 0, 0)))
+    );
+    let starts_after_window = maintenance(time(19, 2, 0, 1), None, Some("0 2 * * *"), Some(60));
+    assert_eq!(
+      next_window(&starts_after_window, time(19, 2, 30, 0)),
+      Some((time(20, 2, 0, 0), time(20, 3, 0, 0)))
+    );
+
+    // 结束时间之后开始的窗口不再有效
+    let ends_on_window = maintenance(
+      time(1, 0, 0, 0),
+      Some(time(20, 2, 0, 0)),
+      Some("0 2 * * *"),
+      Some(60),
+    );
+    assert_eq!(
+      next_window(&ends_on_window, time(19, 2, 30, 0)),
+      Some((time(19, 2, 0, 0), time(19, 3, 0, 0)))
+    );
+    assert_eq!(next_window(&ends_on_window, time(19, 3, 0, 0)), None);
+  }
+}
@@ -0,0 +1,298 @@
+use super::icon::{fetch, find_icon, MAX_PAGE_SIZE};
+use crate::errors::AppError;
+
+use actix_web::http::StatusCode;
+use encoding_rs::{Encoding, UTF_8};
+use lazy_static::lazy_static;
+use regex::{bytes, Regex};
+use reqwest::Url;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+// 与 CreateAppData 中的长度限制保持一致
+const MAX_NAME_LENGTH: usize = 30;
+const MAX_DESCRIPTION_LENGTH: usize = 255;
+const MAX_ICON_LENGTH: usize = 255;
+
+lazy_static! {
+  // 在页面开头查找 <meta charset="..."> 或 <meta http-equiv="Content-Type" content="...; charset=...">
+  static ref META_CHARSET: bytes::Regex =
+    bytes::Regex::new(r#"(?i)<meta[^>]+charset\s*=\s*["']?\s*([a-z0-9_:.-]+)"#).unwrap();
+
+  // 标题中分隔页面名称与站点名称的符号
+  static ref TITLE_SEPARATOR: Regex = Regex::new(r"\s[|\-–—·:]\s").unwrap();
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct GetUrlMetadataQuery {
+  #[validate(
+    length(min = 1, max = 255, message = "应用 URL 长度不得超过 255 个字符"),
+    url(message = "应用 URL 格式不合法")
+  )]
+  url: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UrlMetadata {
+  // 跟随重定向后的地址
+  pub url: String,
+  pub name: String,
+  pub description: Option<String>,
+  // 图标地址，创建应用时才会下载保存
+  pub icon: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct PageMeta {
+  title: Option<String>,
+  site_name: Option<String>,
+  og_title: Option<String>,
+  description: Option<String>,
+  og_description: Option<String>,
+}
+
+// 确定页面编码，依次使用响应头、BOM 与页面中的 meta 声明，默认为 UTF-8
+fn decode(content_type: Option<&str>, body: &[u8]) -> String {
+  let header_charset = content_type.and_then(|content_type| {
+    content_type.split(';').skip(1).find_map(|param| {
+      let (key, value) = param.split_once('=')?;
+      key
+        .trim()
+        .eq_ignore_ascii_case("charset")
+        .then(|| value.trim().trim_matches(['"', '\'']))
+    })
+  });
+
+  let encoding = header_charset
+    .and_then(|charset| Encoding::for_label(charset.as_bytes()))
+    .or_else(|| Encoding::for_bom(body).map(|(encoding, _)| encoding))
+    .or_else(|| {
+      META_CHARSET
+        .captures(&body[..body.len().min(1024)])
+        .and_then(|captures| Encoding::for_label(&captures[1]))
+    })
+    .unwrap_or(UTF_8);
+
+  encoding.decode(body).0.into_owned()
+}
+
+// 合并连续的空白字符
+fn normalize(text: &str) -> Option<String> {
+  let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+  (!text.is_empty()).then_some(text)
+}
+
+pub(super) fn truncate(text: &str, max: usize) -> String {
+  if text.chars().count() <= max {
+    return text.to_string();
+  }
+
+  let mut text = text.chars().take(max - 1).collect::<String>();
+  text.truncate(text.trim_end().len());
+  text.push('…');
+  text
+}
+
+fn parse_html(html: &str) -> PageMeta {
+  let document = Html::parse_document(html);
+  let title_selector = Selector::parse("title").unwrap();
+  let meta_selector = Selector::parse("meta[content]").unwrap();
+
+  let mut meta = PageMeta {
+    title: document
+      .select(&title_selector)
+      .next()
+      .and_then(|title| normalize(&title.text().collect::<String>())),
+    ..Default::default()
+  };
+
+  for element in document.select(&meta_selector) {
+    let element = element.value();
+    let key = element
+      .attr("property")
+      .or(element.attr("name"))
+      .unwrap_or_default()
+      .to_ascii_lowercase();
+    let field = match key.as_str() {
+      "og:site_name" => &mut meta.site_name,
+      "og:title" => &mut meta.og_title,
+      "description" => &mut meta.description,
+      "og:description" => &mut meta.og_description,
+      _ => continue,
+    };
+    if field.is_none() {
+      *field = element.attr("content").and_then(normalize);
+    }
+  }
+
+  meta
+}
+
+// 应用名称优先使用站点名称，标题过长时取最后一段能放下的部分，如 "Home - Grafana" 取 "Grafana"
+fn suggest_name(meta: &PageMeta, url: &Url) -> String {
+  if let Some(site_name) = &meta.site_name {
+    return truncate(site_name, MAX_NAME_LENGTH);
+  }
+
+  let Some(title) = meta.title.as_ref().or(meta.og_title.as_ref()) else {
+    return truncate(url.host_str().unwrap_or(url.as_str()), MAX_NAME_LENGTH);
+  };
+  if title.chars().count() <= MAX_NAME_LENGTH {
+    return title.clone();
+  }
+
+  TITLE_SEPARATOR
+    .split(title)
+    .map(str::trim)
+    .collect::<Vec<&str>>()
+    .into_iter()
+    .rfind(|part| !part.is_empty() && part.chars().count() <= MAX_NAME_LENGTH)
+    .map(String::from)
+    .unwrap_or_else(|| truncate(title, MAX_NAME_LENGTH))
+}
+
+pub async fn get_url_metadata(query: &GetUrlMetadataQuery) -> Result<UrlMetadata, AppError> {
+  let url = Url::parse(&query.url)
+    .map_err(|_| AppError::new(StatusCode::UNPROCESSABLE_ENTITY, 422, "应用 URL 格式不合法"))?;
+
+  let page = fetch(&url, MAX_PAGE_SIZE, true).await.map_err(|err| {
+    AppError::new(
+      StatusCode::BAD_GATEWAY,
+      502,
+      format!("获取页面信息失败：{}", err),
+    )
+  })?;
+
+  // 非 HTML 页面只根据地址推荐名称
+  let is_html = page
+    .content_type
+    .as_deref()
+    .is_none_or(|content_type| content_type.contains("html"));
+  let html = is_html.then(|| decode(page.content_type.as_deref(), &page.body));
+  let meta = html.as_deref().map(parse_html).unwrap_or_default();
+
+  // 地址过长时无法保存到应用中，不返回图标，创建应用后由后台重新查找
+  let icon = match find_icon(&page.url, html.as_deref()).await {
+    Ok((url, ..)) => Some(url.to_string()).filter(|url| url.len() <= MAX_ICON_LENGTH),
+    Err(err) => {
+      log::debug!("Find icon for {} failed: {}", page.url, err);
+      None
+    }
+  };
+
+  Ok(UrlMetadata {
+    name: suggest_name(&meta, &page.url),
+    description: meta
+      .description
+      .or(meta.og_description)
+      .map(|description| truncate(&description, MAX_DESCRIPTION_LENGTH)),
+    icon,
+    url: page.url.to_string(),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use encoding_rs::{GBK, SHIFT_JIS};
+
+  #[test]
+  fn decode_uses_header_bom_then_meta_charset() {
+    let (gbk, ..) = GBK.encode("<title>监控面板</title>");
+    assert_eq!(
+      decode(Some("text/html; charset=\"GBK\""), &gbk),
+      "<title>监控面板</title>"
+    );
+    // 响应头优先于页面中的声明
+    let mut page = b"<meta charset=\"utf-8\">".to_vec();
+    page.extend_from_slice(&gbk);
+    assert!(decode(Some("text/html;charset=gb2312"), &page).ends_with("<title>监控面板</title>"));
+
+    let mut utf16 = vec![0xff, 0xfe];
+    utf16.extend(
+      "<title>Grafana</title>"
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes),
+    );
+    assert_eq!(decode(Some("text/html"), &utf16), "<title>Grafana</title>");
+
+    let (shift_jis, ..) = SHIFT_JIS.encode(
+      "<meta http-equiv=\"Content-Type\" content=\"text/html; charset=Shift_JIS\"><title>設定</title>",
+    );
+    assert!(decode(None, &shift_jis).ends_with("<title>設定</title>"));
+
+    assert_eq!(
+      decode(None, "<title>默认</title>".as_bytes()),
+      "<title>默认</title>"
+    );
+  }
+
+  #[test]
+  fn truncate_counts_characters() {
+    assert_eq!(truncate("监控面板", 4), "监控面板");
+    assert_eq!(truncate("监控面板测试", 4), "监控面…");
+    assert_eq!(truncate("ab cdef", 4), "ab…");
+    assert_eq!(truncate("🚀🚀🚀", 2), "🚀…");
+  }
+
+  #[test]
+  fn suggest_name_prefers_site_name_then_title_parts() {
+    let url = Url::parse("https://grafana.example.com/login").unwrap();
+    let meta = |site_name: Option<&str>, title: Option<&str>| PageMeta {
+      site_name: site_name.map(String::from),
+      title: title.map(String::from),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      suggest_name(&meta(Some("Grafana"), Some("Login")), &url),
+      "Grafana"
+    );
+    assert_eq!(suggest_name(&meta(None, Some("Login")), &url), "Login");
+    assert_eq!(
+      suggest_name(
+        &meta(
+          None,
+          Some("Welcome to the home dashboard of our team - Grafana")
+        ),
+        &url
+      ),
+      "Grafana"
+    );
+    assert_eq!(
+      suggest_name(
+        &meta(
+          None,
+          Some("这是一个非常非常非常非常非常非常非常非常长的没有分隔符的页面标题")
+        ),
+        &url
+      )
+      .chars()
+      .count(),
+      MAX_NAME_LENGTH
+    );
+    assert_eq!(suggest_name(&meta(None, None), &url), "grafana.example.com");
+  }
+
+  #[test]
+  fn parse_html_keeps_the_first_meta_values() {
+    let meta = parse_html(
+      r#"<html><head>
+        <title>
+          Home   Dashboard
+        </title>
+        <meta property="og:site_name" content="Grafana">
+        <meta name="Description" content="  开源的
+          监控平台 ">
+        <meta name="description" content="重复的描述">
+        <meta property="og:description" content="">
+      </head></html>"#,
+    );
+
+    assert_eq!(meta.title.as_deref(), Some("Home Dashboard"));
+    assert_eq!(meta.site_name.as_deref(), Some("Grafana"));
+    assert_eq!(meta.description.as_deref(), Some("开源的 监控平台"));
+    assert_eq!(meta.og_description, None);
+  }
+}
@@ -1,7 +1,33 @@
 pub mod app;
 pub mod auth;
+pub mod badge;
+pub mod board;
+pub mod bookmark;
+pub mod bulk;
+pub mod cert;
+pub mod click;
+pub mod dashboard;
 pub mod proxy;
 pub mod file;
+pub mod global_app;
+pub mod group;
+pub mod http;
+pub mod icon;
+pub mod import;
+pub mod link;
+pub mod maintenance;
+pub mod metadata;
+pub mod monitor;
+pub mod network;
+pub mod notification;
 pub mod password;
+pub mod search;
 pub mod setting;
+pub mod share;
+pub mod short_link;
+pub mod status_page;
+pub mod tag;
 pub mod user;
+
+#[cfg(test)]
+mod testing;
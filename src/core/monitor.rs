@@ -0,0 +1,754 @@
+use super::{
+  app::get_user_visible_app,
+  cert::check_all_cert,
+  http::{CLIENT, INSECURE_CLIENT},
+  maintenance::{get_apps_maintenance, ActiveMaintenance},
+  notification::handle_check,
+  share::check_app_permission,
+  user::check_admin,
+};
+use crate::{errors::AppError, settings::SETTINGS};
+
+use actix_web::{http::StatusCode, rt::time};
+use chrono::{Duration as ChronoDuration, NaiveDateTime, Utc};
+use entity::{
+  app_checks::{self, CheckStatus},
+  apps::{self, Monitor, MonitorHeader, MonitorKind},
+  shares::SharePermission,
+};
+use futures_util::{stream, StreamExt};
+use regex::Regex;
+use reqwest::{
+  header::{HeaderName, HeaderValue},
+  Method, Url,
+};
+use sea_orm::{
+  entity::Set, ActiveModelTrait, ColumnTrait, DbConn, DbErr, EntityTrait, IntoActiveModel,
+  QueryFilter, QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_json_path::JsonPath;
+use std::{
+  collections::HashMap,
+  time::{Duration, Instant},
+};
+use tokio::net::{lookup_host, TcpStream};
+use utils::{crypto::random_token, serialize::i64_to_str};
+use validator::{Validate, ValidationError};
+
+// 同时进行检查的应用数量
+pub const CONCURRENCY: usize = 16;
+
+fn validate_method(method: &str) -> Result<(), ValidationError> {
+  match method {
+    "GET" | "HEAD" | "POST" | "PUT" | "PATCH" | "DELETE" | "OPTIONS" => Ok(()),
+    _ => Err(ValidationError::new("method")),
+  }
+}
+
+fn validate_header_name(name: &str) -> Result<(), ValidationError> {
+  HeaderName::from_bytes(name.as_bytes())
+    .map(|_| ())
+    .map_err(|_| ValidationError::new("header_name"))
+}
+
+fn validate_header_value(value: &str) -> Result<(), ValidationError> {
+  HeaderValue::from_str(value)
+    .map(|_| ())
+    .map_err(|_| ValidationError::new("header_value"))
+}
+
+fn validate_json_path(json_path: &str) -> Result<(), ValidationError> {
+  JsonPath::parse(json_path)
+    .map(|_| ())
+    .map_err(|_| ValidationError::new("json_path"))
+}
+
+fn validate_monitor(data: &MonitorData) -> Result<(), ValidationError> {
+  match &data.keyword {
+    Some(keyword) if data.keyword_regex && Regex::new(keyword).is_err() => {
+      Err(ValidationError::new("keyword").with_message("关键字正则表达式不合法".into()))
+    }
+    _ => Ok(()),
+  }
+}
+
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+pub struct MonitorHeaderData {
+  #[validate(
+    length(min = 1, max = 255, message = "请求头名称长度不得超过 255 个字符"),
+    custom(function = "validate_header_name", message = "请求头名称不合法")
+  )]
+  name: String,
+  #[validate(
+    length(max = 255, message = "请求头内容长度不得超过 255 个字符"),
+    custom(function = "validate_header_value", message = "请求头内容不合法")
+  )]
+  value: String,
+}
+
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+#[validate(schema(function = "validate_monitor"))]
+pub struct MonitorData {
+  #[serde(default)]
+  kind: MonitorKind,
+  #[validate(custom(function = "validate_method", message = "请求方法不合法"))]
+  method: Option<String>,
+  #[serde(default)]
+  #[validate(length(max = 20, message = "请求头不得超过 20 个"), nested)]
+  headers: Vec<MonitorHeaderData>,
+  #[validate(length(min = 1, max = 255, message = "关键字长度不得超过 255 个字符"))]
+  keyword: Option<String>,
+  #[serde(default)]
+  keyword_regex: bool,
+  #[serde(default)]
+  keyword_absent: bool,
+  #[validate(
+    length(min = 1, max = 255, message = "JSONPath 长度不得超过 255 个字符"),
+    custom(function = "validate_json_path", message = "JSONPath 表达式不合法")
+  )]
+  json_path: Option<String>,
+  #[validate(length(min = 1, max = 255, message = "期望值长度不得超过 255 个字符"))]
+  expected: Option<String>,
+  #[validate(length(min = 1, max = 255, message = "主机长度不得超过 255 个字符"))]
+  host: Option<String>,
+  #[validate(range(min = 1, message = "端口必须为 1 ~ 65535"))]
+  port: Option<u16>,
+  #[validate(range(min = 1, message = "心跳间隔必须大于 0 秒"))]
+  push_interval: Option<u64>,
+  #[validate(range(min = 1, max = 100, message = "通知前的连续失败次数必须为 1 ~ 100 次"))]
+  notify_after: Option<u32>,
+}
+
+impl From<&MonitorData> for Monitor {
+  fn from(data: &MonitorData) -> Self {
+    Monitor {
+      kind: data.kind.clone(),
+      method: data.method.clone(),
+      headers: data
+        .headers
+        .iter()
+        .map(|header| MonitorHeader {
+          name: header.name.clone(),
+          value: header.value.clone(),
+        })
+        .collect(),
+      keyword: data.keyword.clone(),
+      keyword_regex: data.keyword_regex,
+      keyword_absent: data.keyword_absent,
+      json_path: data.json_path.clone(),
+      expected: data.expected.clone(),
+      host: data.host.clone(),
+      port: data.port,
+      push_interval: data.push_interval,
+      notify_after: data.notify_after,
+    }
+  }
+}
+
+impl From<&Monitor> for MonitorData {
+  fn from(monitor: &Monitor) -> Self {
+    MonitorData {
+      kind: monitor.kind.clone(),
+      method: monitor.method.clone(),
+      headers: monitor
+        .headers
+        .iter()
+        .map(|header| MonitorHeaderData {
+          name: header.name.clone(),
+          value: header.value.clone(),
+        })
+        .collect(),
+      keyword: monitor.keyword.clone(),
+      keyword_regex: monitor.keyword_regex,
+      keyword_absent: monitor.keyword_absent,
+      json_path: monitor.json_path.clone(),
+      expected: monitor.expected.clone(),
+      host: monitor.host.clone(),
+      port: monitor.port,
+      push_interval: monitor.push_interval,
+      notify_after: monitor.notify_after,
+    }
+  }
+}
+
+// 推送监控需要令牌，已有令牌时保持不变
+pub fn ensure_push_token(
+  monitor: Option<&MonitorData>,
+  push_token: Option<String>,
+) -> Result<Option<String>, AppError> {
+  match (monitor, push_token) {
+    (_, Some(push_token)) => Ok(Some(push_token)),
+    (Some(monitor), None) if monitor.kind == MonitorKind::Push => random_token(16)
+      .map(Some)
+      .map_err(AppError::from_err),
+    _ => Ok(None),
+  }
+}
+
+// 后台定时检查全部应用
+pub async fn run(db: DbConn) {
+  let mut interval = time::interval(Duration::from_secs(SETTINGS.monitor.interval));
+
+  loop {
+    interval.tick().await;
+
+    if let Err(err) = check_all(&db).await {
+      log::error!("App health check failed: {}", err);
+    }
+  }
+}
+
+async fn check_all(db: &DbConn) -> Result<(), DbErr> {
+  let all_apps = apps::Entity::find().all(db).await?;
+  let apps_maintenance = get_apps_maintenance(db, &all_apps).await?;
+
+  let mut checks = stream::iter(&all_apps)
+    .map(|app| async move { check_app(db, app).await })
+    .buffer_unordered(CONCURRENCY)
+    .collect::<Vec<Result<Option<app_checks::ActiveModel>, DbErr>>>()
+    .await
+    .into_iter()
+    .filter_map(Result::transpose)
+    .collect::<Result<Vec<app_checks::ActiveModel>, DbErr>>()?;
+
+  // 维护中的应用照常检查，但记录为维护状态且不发送通知
+  for check in checks.iter_mut() {
+    if apps_maintenance.contains_key(check.app_id.as_ref()) {
+      check.status = Set(CheckStatus::Maintenance);
+    }
+  }
+
+  let results = checks
+    .iter()
+    .filter(|check| !apps_maintenance.contains_key(check.app_id.as_ref()))
+    .map(|check| {
+      (
+        check.app_id.clone().unwrap(),
+        check.status.clone().unwrap(),
+        check.message.clone().unwrap(),
+      )
+    })
+    .collect::<Vec<(i64, CheckStatus, Option<String>)>>();
+
+  if !checks.is_empty() {
+    app_checks::Entity::insert_many(checks).exec(db).await?;
+  }
+
+  for (app_id, status, message) in results {
+    if let Some(app) = all_apps.iter().find(|app| app.id == app_id) {
+      handle_check(db, app, status, message).await?;
+    }
+  }
+
+  let expired_at = Utc::now().naive_utc() - ChronoDuration::days(SETTINGS.monitor.retention_days);
+  app_checks::Entity::delete_many()
+    .filter(app_checks::Column::CheckedAt.lt(expired_at))
+    .exec(db)
+    .await?;
+
+  check_all_cert(db, &all_apps).await
+}
+
+fn is_expected_status(status_code: u16) -> bool {
+  if SETTINGS.monitor.expected_status.is_empty() {
+    (200..400).contains(&status_code)
+  } else {
+    SETTINGS.monitor.expected_status.contains(&status_code)
+  }
+}
+
+fn truncate_message(message: String) -> String {
+  message.chars().take(255).collect()
+}
+
+struct CheckResult {
+  status: CheckStatus,
+  status_code: Option<i32>,
+  latency: Option<i32>,
+  message: Option<String>,
+}
+
+impl CheckResult {
+  fn up(latency: i32, message: String) -> Self {
+    CheckResult {
+      status: CheckStatus::Up,
+      status_code: None,
+      latency: Some(latency),
+      message: Some(message),
+    }
+  }
+
+  fn down(message: String) -> Self {
+    CheckResult {
+      status: CheckStatus::Down,
+      status_code: None,
+      latency: None,
+      message: Some(message),
+    }
+  }
+}
+
+async fn check_app(
+  db: &DbConn,
+  app: &apps::Model,
+) -> Result<Option<app_checks::ActiveModel>, DbErr> {
+  let monitor = app.monitor.clone().unwrap_or_default();
+
+  let result = match monitor.kind {
+    MonitorKind::Http => check_http(app.server_url(), &monitor).await,
+    MonitorKind::Tcp => check_tcp(app.server_url(), &monitor).await,
+    MonitorKind::Dns => check_dns(app.server_url(), &monitor).await,
+    MonitorKind::Push => match check_push(db, app, &monitor).await? {
+      Some(result) => result,
+      None => return Ok(None),
+    },
+  };
+
+  Ok(Some(app_checks::ActiveModel {
+    app_id: Set(app.id),
+    status: Set(result.status),
+    status_code: Set(result.status_code),
+    latency: Set(result.latency),
+    message: Set(result.message.map(truncate_message)),
+    checked_at: Set(Utc::now().naive_utc()),
+    ..Default::default()
+  }))
+}
+
+// 推送监控在超过心跳间隔未收到心跳时记录一次异常，之后每个间隔记录一次
+async fn check_push(
+  db: &DbConn,
+  app: &apps::Model,
+  monitor: &Monitor,
+) -> Result<Option<CheckResult>, DbErr> {
+  let push_interval = monitor.push_interval.unwrap_or(SETTINGS.monitor.interval) as i64;
+
+  let last_checked_at = app_checks::Entity::find()
+    .filter(app_checks::Column::AppId.eq(app.id))
+    .order_by_desc(app_checks::Column::CheckedAt)
+    .one(db)
+    .await?
+    .map_or(app.created_at, |check| check.checked_at);
+
+  if last_checked_at + ChronoDuration::seconds(push_interval) > Utc::now().naive_utc() {
+    return Ok(None);
+  }
+
+  Ok(Some(CheckResult::down(format!(
+    "超过 {} 秒未收到心跳",
+    push_interval
+  ))))
+}
+
+async fn check_http(url: &str, monitor: &Monitor) -> CheckResult {
+  let client = if SETTINGS.monitor.verify_tls {
+    &*CLIENT
+  } else {
+    &*INSECURE_CLIENT
+  };
+
+  let method = monitor
+    .method
+    .as_deref()
+    .and_then(|method| Method::from_bytes(method.as_bytes()).ok())
+    .unwrap_or(Method::GET);
+
+  let mut request = client
+    .request(method, url)
+    .timeout(Duration::from_secs(SETTINGS.monitor.timeout));
+  for header in &monitor.headers {
+    request = request.header(&header.name, &header.value);
+  }
+
+  let start = Instant::now();
+  let resp = match request.send().await {
+    Ok(resp) => resp,
+    Err(err) => return CheckResult::down(err.to_string()),
+  };
+  let status_code = resp.status();
+
+  let mut result = CheckResult {
+    status: CheckStatus::Down,
+    status_code: Some(status_code.as_u16() as i32),
+    latency: Some(start.elapsed().as_millis() as i32),
+    message: Some(status_code.to_string()),
+  };
+
+  if !is_expected_status(status_code.as_u16()) {
+    return result;
+  }
+
+  if monitor.keyword.is_some() || monitor.json_path.is_some() {
+    let body = match resp.text().await {
+      Ok(body) => body,
+      Err(err) => {
+        result.message = Some(err.to_string());
+        return result;
+      }
+    };
+
+    if let Err(message) = check_body(&body, monitor) {
+      result.message = Some(message);
+      return result;
+    }
+  }
+
+  result.status = CheckStatus::Up;
+  result
+}
+
+// 检查响应内容中的关键字与 JSONPath 结果
+fn check_body(body: &str, monitor: &Monitor) -> Result<(), String> {
+  if let Some(keyword) = &monitor.keyword {
+    let found = if monitor.keyword_regex {
+      Regex::new(keyword).is_ok_and(|regex| regex.is_match(body))
+    } else {
+      body.contains(keyword.as_str())
+    };
+
+    if found && monitor.keyword_absent {
+      return Err(format!("响应内容中包含关键字 {}", keyword));
+    }
+    if !found && !monitor.keyword_absent {
+      return Err(format!("响应内容中未找到关键字 {}", keyword));
+    }
+  }
+
+  if let Some(json_path) = &monitor.json_path {
+    let value =
+      serde_json::from_str::<Value>(body).map_err(|err| format!("响应内容不是 JSON：{}", err))?;
+    let path = JsonPath::parse(json_path).map_err(|err| err.to_string())?;
+    let nodes = path
+      .query(&value)
+      .all()
+      .into_iter()
+      .map(|node| match node {
+        Value::String(node) => node.clone(),
+        node => node.to_string(),
+      })
+      .collect::<Vec<String>>();
+
+    if nodes.is_empty() {
+      return Err(format!("JSONPath {} 没有匹配结果", json_path));
+    }
+
+    if let Some(expected) = monitor.expected.as_ref().filter(|expected| !nodes.contains(expected)) {
+      return Err(format!(
+        "JSONPath {} 的结果为 {}，期望为 {}",
+        json_path,
+        nodes.join(", "),
+        expected
+      ));
+    }
+  }
+
+  Ok(())
+}
+
+// 解析 TCP 与 DNS 检查的主机与端口，未配置时使用应用 URL 中的值
+fn resolve_target(url: &str, monitor: &Monitor) -> Result<(String, Option<u16>), String> {
+  let url = Url::parse(url).ok();
+  let host = monitor
+    .host
+    .clone()
+    .or_else(|| {
+      url
+        .as_ref()
+        .and_then(|url| url.host_str().map(ToString::to_string))
+    })
+    .ok_or("未配置检查的主机".to_string())?;
+  let port = monitor
+    .port
+    .or_else(|| url.as_ref().and_then(|url| url.port_or_known_default()));
+
+  Ok((host, port))
+}
+
+async fn check_tcp(url: &str, monitor: &Monitor) -> CheckResult {
+  let (host, port) = match resolve_target(url, monitor) {
+    Ok((host, Some(port))) => (host, port),
+    Ok(_) => return CheckResult::down("未配置检查的端口".to_string()),
+    Err(message) => return CheckResult::down(message),
+  };
+
+  let start = Instant::now();
+  let connect = TcpStream::connect((host.as_str(), port));
+  match time::timeout(Duration::from_secs(SETTINGS.monitor.timeout), connect).await {
+    Ok(Ok(_)) => CheckResult::up(
+      start.elapsed().as_millis() as i32,
+      format!("{}:{} 连接成功", host, port),
+    ),
+    Ok(Err(err)) => CheckResult::down(err.to_string()),
+    Err(_) => CheckResult::down(format!("{}:{} 连接超时", host, port)),
+  }
+}
+
+async fn check_dns(url: &str, monitor: &Monitor) -> CheckResult {
+  let host = match resolve_target(url, monitor) {
+    Ok((host, _)) => host,
+    Err(message) => return CheckResult::down(message),
+  };
+
+  let start = Instant::now();
+  let lookup = lookup_host((host.as_str(), 0));
+  let addrs = match time::timeout(Duration::from_secs(SETTINGS.monitor.timeout), lookup).await {
+    Ok(Ok(addrs)) => addrs
+      .map(|addr| addr.ip().to_string())
+      .collect::<Vec<String>>(),
+    Ok(Err(err)) => return CheckResult::down(err.to_string()),
+    Err(_) => return CheckResult::down(format!("{} 解析超时", host)),
+  };
+  let latency = start.elapsed().as_millis() as i32;
+
+  if addrs.is_empty() {
+    return CheckResult::down(format!("{} 没有解析结果", host));
+  }
+
+  match &monitor.expected {
+    Some(expected) if !addrs.contains(expected) => CheckResult {
+      latency: Some(latency),
+      ..CheckResult::down(format!(
+        "{} 的解析结果为 {}，期望为 {}",
+        host,
+        addrs.join(", "),
+        expected
+      ))
+    },
+    _ => CheckResult::up(latency, addrs.join(", ")),
+  }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PushStatus {
+  Up,
+  Down,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PushQuery {
+  status: Option<PushStatus>,
+  msg: Option<String>,
+  // 推送方统计的响应时间，单位毫秒
+  ping: Option<i32>,
+}
+
+// 接收推送监控的心跳
+pub async fn push_heartbeat(
+  db: &DbConn,
+  push_token: &str,
+  query: &PushQuery,
+) -> Result<(), AppError> {
+  let app = apps::Entity::find()
+    .filter(apps::Column::PushToken.eq(push_token))
+    .one(db)
+    .await?
+    .filter(|app| {
+      app
+        .monitor
+        .as_ref()
+        .is_some_and(|monitor| monitor.kind == MonitorKind::Push)
+    })
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "推送地址不存在"))?;
+
+  let in_maintenance = !get_apps_maintenance(db, std::slice::from_ref(&app))
+    .await?
+    .is_empty();
+  let status = match query.status {
+    _ if in_maintenance => CheckStatus::Maintenance,
+    Some(PushStatus::Down) => CheckStatus::Down,
+    _ => CheckStatus::Up,
+  };
+
+  app_checks::ActiveModel {
+    app_id: Set(app.id),
+    status: Set(status),
+    status_code: Set(None),
+    latency: Set(query.ping),
+    message: Set(query.msg.clone().map(truncate_message)),
+    checked_at: Set(Utc::now().naive_utc()),
+    ..Default::default()
+  }
+  .insert(db)
+  .await?;
+
+  if !in_maintenance {
+    handle_check(db, &app, status, query.msg.clone()).await?;
+  }
+
+  Ok(())
+}
+
+#[derive(Debug, Serialize)]
+pub struct PushInfo {
+  pub push_token: String,
+  pub push_url: String,
+}
+
+impl From<String> for PushInfo {
+  fn from(push_token: String) -> Self {
+    PushInfo {
+      push_url: format!("/api/push/{}", push_token),
+      push_token,
+    }
+  }
+}
+
+// 全站应用只有管理员可以管理，其他应用需要有编辑权限
+pub async fn check_manage_app(
+  db: &DbConn,
+  operator_id: i64,
+  app_id: i64,
+) -> Result<apps::Model, AppError> {
+  let app = apps::Entity::find_by_id(app_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "应用不存在"))?;
+
+  if app.is_global {
+    check_admin(db, operator_id).await?;
+    return Ok(app);
+  }
+
+  check_app_permission(db, operator_id, app_id, SharePermission::Edit).await
+}
+
+pub async fn get_push_info(
+  db: &DbConn,
+  operator_id: i64,
+  app_id: i64,
+) -> Result<PushInfo, AppError> {
+  check_manage_app(db, operator_id, app_id)
+    .await?
+    .push_token
+    .map(Into::into)
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "应用未开启推送监控"))
+}
+
+// 重新生成推送令牌，旧的推送地址随即失效
+pub async fn reset_push_token(
+  db: &DbConn,
+  operator_id: i64,
+  app_id: i64,
+) -> Result<PushInfo, AppError> {
+  let app = check_manage_app(db, operator_id, app_id).await?;
+
+  if app.push_token.is_none() {
+    return Err(AppError::new(StatusCode::NOT_FOUND, 404, "应用未开启推送监控"));
+  }
+
+  let push_token = random_token(16).map_err(AppError::from_err)?;
+
+  let mut app = app.into_active_model();
+  app.push_token = Set(Some(push_token.clone()));
+  app.update(db).await?;
+
+  Ok(push_token.into())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetAppStatusQuery {
+  // 查询最近多少小时的检查记录，默认 24 小时
+  hours: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckPoint {
+  pub status: CheckStatus,
+  pub latency: Option<i32>,
+  pub checked_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AppStatus {
+  #[serde(serialize_with = "i64_to_str")]
+  pub app_id: i64,
+  pub status: Option<CheckStatus>,
+  pub status_code: Option<i32>,
+  pub latency: Option<i32>,
+  pub message: Option<String>,
+  pub checked_at: Option<NaiveDateTime>,
+  // 查询时间范围内的可用率，维护期间的检查不计入，没有检查记录时为空
+  pub uptime: Option<f64>,
+  pub history: Vec<CheckPoint>,
+  // 应用当前所处的维护窗口
+  pub maintenance: Option<ActiveMaintenance>,
+}
+
+pub async fn get_user_app_status(
+  db: &DbConn,
+  user_id: i64,
+  query: &GetAppStatusQuery,
+) -> Result<Vec<AppStatus>, AppError> {
+  let visible_apps = get_user_visible_app(db, user_id).await?;
+  let mut apps_maintenance = get_apps_maintenance(db, &visible_apps).await?;
+
+  let hours = query
+    .hours
+    .unwrap_or(24)
+    .clamp(1, SETTINGS.monitor.retention_days * 24);
+  let since = Utc::now().naive_utc() - ChronoDuration::hours(hours);
+
+  let mut apps_checks = app_checks::Entity::find()
+    .filter(app_checks::Column::AppId.is_in(visible_apps.iter().map(|app| app.id)))
+    .filter(app_checks::Column::CheckedAt.gte(since))
+    .order_by_asc(app_checks::Column::CheckedAt)
+    .all(db)
+    .await?
+    .into_iter()
+    .fold(HashMap::new(), |mut apps_checks, check| {
+      apps_checks
+        .entry(check.app_id)
+        .or_insert_with(Vec::new)
+        .push(check);
+      apps_checks
+    });
+
+  Ok(
+    visible_apps
+      .iter()
+      .map(|app| {
+        let checks = apps_checks.remove(&app.id).unwrap_or_default();
+        let last_check = checks.last();
+
+        let counted = checks
+          .iter()
+          .filter(|check| check.status != CheckStatus::Maintenance)
+          .collect::<Vec<&app_checks::Model>>();
+        let uptime = (!counted.is_empty()).then(|| {
+          let up_count = counted
+            .iter()
+            .filter(|check| check.status == CheckStatus::Up)
+            .count();
+          up_count as f64 / counted.len() as f64
+        });
+        let maintenance = apps_maintenance.remove(&app.id);
+
+        AppStatus {
+          app_id: app.id,
+          // 维护窗口开始后立即显示为维护中，不必等待下一次检查
+          status: match maintenance {
+            Some(_) => Some(CheckStatus::Maintenance),
+            None => last_check.map(|check| check.status),
+          },
+          status_code: last_check.and_then(|check| check.status_code),
+          latency: last_check.and_then(|check| check.latency),
+          message: last_check.and_then(|check| check.message.clone()),
+          checked_at: last_check.map(|check| check.checked_at),
+          uptime,
+          history: checks
+            .iter()
+            .map(|check| CheckPoint {
+              status: check.status,
+              latency: check.latency,
+              checked_at: check.checked_at,
+            })
+            .collect(),
+          maintenance,
+        }
+      })
+      .collect(),
+  )
+}
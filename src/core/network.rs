@@ -0,0 +1,175 @@
+use crate::settings::SETTINGS;
+
+use actix_web::HttpRequest;
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkMode {
+  // 按客户端地址判断是否在内网中
+  #[default]
+  Auto,
+  Internal,
+  External,
+}
+
+fn contains(networks: &[IpNet], ip: IpAddr) -> bool {
+  networks.iter().any(|network| network.contains(&ip))
+}
+
+// 转发地址可能带有端口
+fn parse_forwarded_ip(value: &str) -> Option<IpAddr> {
+  let value = value.trim();
+  value
+    .parse::<IpAddr>()
+    .or_else(|_| value.parse::<SocketAddr>().map(|addr| addr.ip()))
+    .ok()
+}
+
+// 请求来自可信的反向代理时，从右向左读取 X-Forwarded-For，直到遇到第一个不可信的地址
+pub fn client_ip(req: &HttpRequest) -> Option<IpAddr> {
+  let peer_ip = req.peer_addr()?.ip();
+  let forwarded_ips = req
+    .headers()
+    .get_all("x-forwarded-for")
+    .filter_map(|value| value.to_str().ok())
+    .flat_map(|value| value.split(','))
+    .collect::<Vec<&str>>();
+
+  Some(resolve_client_ip(
+    peer_ip,
+    &forwarded_ips,
+    &SETTINGS.network.proxy_networks,
+  ))
+}
+
+fn resolve_client_ip(peer_ip: IpAddr, forwarded_ips: &[&str], proxy_networks: &[IpNet]) -> IpAddr {
+  let mut client_ip = peer_ip.to_canonical();
+  for forwarded_ip in forwarded_ips.iter().rev() {
+    if !contains(proxy_networks, client_ip) {
+      break;
+    }
+    match parse_forwarded_ip(forwarded_ip) {
+      Some(ip) => client_ip = ip.to_canonical(),
+      None => break,
+    }
+  }
+
+  client_ip
+}
+
+// 无法获取客户端地址时视为外网
+pub fn is_internal(mode: Option<NetworkMode>, client_ip: Option<IpAddr>) -> bool {
+  is_internal_ip(mode, client_ip, &SETTINGS.network.internal_networks)
+}
+
+fn is_internal_ip(
+  mode: Option<NetworkMode>,
+  client_ip: Option<IpAddr>,
+  internal_networks: &[IpNet],
+) -> bool {
+  match mode.unwrap_or_default() {
+    NetworkMode::Auto => client_ip.is_some_and(|ip| contains(internal_networks, ip)),
+    NetworkMode::Internal => true,
+    NetworkMode::External => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use actix_web::test::TestRequest;
+
+  fn networks(cidrs: &[&str]) -> Vec<IpNet> {
+    cidrs.iter().map(|cidr| cidr.parse().unwrap()).collect()
+  }
+
+  fn ip(ip: &str) -> IpAddr {
+    ip.parse().unwrap()
+  }
+
+  #[test]
+  fn ignores_forwarded_ips_from_untrusted_peer() {
+    let proxies = networks(&["10.0.0.0/8"]);
+
+    assert_eq!(
+      resolve_client_ip(ip("203.0.113.9"), &["192.168.1.2"], &proxies),
+      ip("203.0.113.9")
+    );
+  }
+
+  #[test]
+  fn reads_forwarded_ips_through_trusted_proxies() {
+    let proxies = networks(&["10.0.0.0/8", "172.16.0.1/32"]);
+
+    // 客户端伪造的地址在最左侧，遇到第一个不可信的地址时停止
+    assert_eq!(
+      resolve_client_ip(
+        ip("10.0.0.2"),
+        &["192.168.1.2", " 198.51.100.7", " 172.16.0.1"],
+        &proxies
+      ),
+      ip("198.51.100.7")
+    );
+    // 全部经过可信代理时使用最左侧的地址
+    assert_eq!(
+      resolve_client_ip(ip("10.0.0.2"), &["198.51.100.7:4321", "10.1.1.1"], &proxies),
+      ip("198.51.100.7")
+    );
+    // 无法解析的地址不再继续读取
+    assert_eq!(
+      resolve_client_ip(ip("10.0.0.2"), &["198.51.100.7", "unknown"], &proxies),
+      ip("10.0.0.2")
+    );
+  }
+
+  #[test]
+  fn canonicalizes_mapped_ipv4() {
+    assert_eq!(
+      resolve_client_ip(ip("::ffff:192.168.1.2"), &[], &[]),
+      ip("192.168.1.2")
+    );
+    assert_eq!(
+      resolve_client_ip(
+        ip("::ffff:10.0.0.2"),
+        &["[::ffff:198.51.100.7]:80"],
+        &networks(&["10.0.0.0/8"])
+      ),
+      ip("198.51.100.7")
+    );
+  }
+
+  #[test]
+  fn checks_internal_network_by_mode() {
+    let internal = networks(&["192.168.0.0/16", "fc00::/7"]);
+
+    assert!(is_internal_ip(None, Some(ip("192.168.1.2")), &internal));
+    assert!(is_internal_ip(None, Some(ip("fd00::1")), &internal));
+    assert!(!is_internal_ip(None, Some(ip("203.0.113.9")), &internal));
+    assert!(!is_internal_ip(None, None, &internal));
+    assert!(is_internal_ip(Some(NetworkMode::Internal), None, &internal));
+    assert!(!is_internal_ip(
+      Some(NetworkMode::External),
+      Some(ip("192.168.1.2")),
+      &internal
+    ));
+  }
+
+  // 默认配置不信任任何代理，默认内网地址段包含私有地址
+  #[test]
+  fn uses_default_settings() {
+    let req = TestRequest::default()
+      .peer_addr("192.168.1.2:4321".parse().unwrap())
+      .insert_header(("x-forwarded-for", "203.0.113.9"))
+      .to_http_request();
+
+    let resolved = client_ip(&req);
+
+    assert_eq!(resolved, Some(ip("192.168.1.2")));
+    assert!(is_internal(None, resolved));
+    assert!(!is_internal(None, Some(ip("203.0.113.9"))));
+    assert_eq!(client_ip(&TestRequest::default().to_http_request()), None);
+  }
+}
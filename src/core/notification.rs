@@ -0,0 +1,897 @@
+use super::{http::CLIENT, user::check_admin};
+use crate::{errors::AppError, settings::SETTINGS};
+
+use actix_web::http::StatusCode;
+use anyhow::{anyhow, bail};
+use chrono::{Duration as ChronoDuration, NaiveDateTime, Utc};
+use entity::{
+  app_alerts,
+  app_checks::CheckStatus,
+  apps,
+  notifications::{self, NotificationConfig, NotificationKind, SmtpSecurity},
+  users,
+};
+use futures_util::future::join_all;
+use lettre::{
+  message::{header::ContentType, Mailbox},
+  transport::smtp::authentication::Credentials,
+  AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use sea_orm::{
+  entity::Set, sea_query::OnConflict, ActiveModelTrait, ColumnTrait, Condition, DbConn, DbErr,
+  DeleteResult, EntityTrait, IntoActiveModel, ModelTrait, QueryFilter, QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+use utils::deserialize::{double_option, str_to_i64};
+use validator::{Validate, ValidationError};
+
+const DEFAULT_TITLE_TEMPLATE: &str = "[{{status}}] {{name}}";
+const DEFAULT_BODY_TEMPLATE: &str = "{{name}}（{{url}}）{{status}}：{{message}}\n时间：{{time}}";
+
+// 通知中使用的应用状态变化
+pub struct NotifyEvent {
+  pub app_id: Option<i64>,
+  pub name: String,
+  pub url: String,
+  pub status: CheckStatus,
+  pub message: String,
+  pub time: NaiveDateTime,
+}
+
+impl NotifyEvent {
+  fn status_text(&self) -> &'static str {
+    match self.status {
+      CheckStatus::Up => "正常",
+      CheckStatus::Down => "异常",
+      CheckStatus::Maintenance => "维护中",
+    }
+  }
+
+  // 模板中可以使用 {{name}}、{{url}}、{{status}}、{{message}} 与 {{time}}
+  fn render(&self, template: &str) -> String {
+    template
+      .replace("{{name}}", &self.name)
+      .replace("{{url}}", &self.url)
+      .replace("{{status}}", self.status_text())
+      .replace("{{message}}", &self.message)
+      .replace("{{time}}", &self.time.format("%Y-%m-%d %H:%M:%S").to_string())
+  }
+}
+
+async fn send(notification: &notifications::Model, event: &NotifyEvent) -> anyhow::Result<()> {
+  let title = event.render(
+    notification
+      .title_template
+      .as_deref()
+      .unwrap_or(DEFAULT_TITLE_TEMPLATE),
+  );
+  let body = event.render(
+    notification
+      .body_template
+      .as_deref()
+      .unwrap_or(DEFAULT_BODY_TEMPLATE),
+  );
+  let config = &notification.config;
+  let timeout = Duration::from_secs(SETTINGS.monitor.timeout);
+
+  let request = match notification.kind {
+    NotificationKind::Webhook => CLIENT.post(required(&config.url, "url")?).json(&json!({
+      "title": title,
+      "body": body,
+      "app": {
+        "id": event.app_id.map(|id| id.to_string()),
+        "name": event.name,
+        "url": event.url,
+      },
+      "status": event.status,
+      "message": event.message,
+      "time": event.time,
+    })),
+    // 使用 JSON 格式发布消息，避免请求头中无法使用中文标题
+    NotificationKind::Ntfy => {
+      let server = config.url.as_deref().unwrap_or("https://ntfy.sh");
+      let tag = match event.status {
+        CheckStatus::Up => "white_check_mark",
+        CheckStatus::Down => "rotating_light",
+        CheckStatus::Maintenance => "construction",
+      };
+      let mut request = CLIENT.post(server).json(&json!({
+        "topic": required(&config.topic, "topic")?,
+        "title": title,
+        "message": body,
+        "priority": config.priority.unwrap_or(3),
+        "tags": [tag],
+      }));
+      if let Some(token) = &config.token {
+        request = request.bearer_auth(token);
+      }
+      request
+    }
+    NotificationKind::Gotify => {
+      let server = required(&config.url, "url")?;
+      CLIENT
+        .post(format!("{}/message", server.trim_end_matches('/')))
+        .header("X-Gotify-Key", required(&config.token, "token")?)
+        .json(&json!({
+          "title": title,
+          "message": body,
+          "priority": config.priority.unwrap_or(5),
+        }))
+    }
+    NotificationKind::Discord => CLIENT
+      .post(required(&config.url, "url")?)
+      .json(&json!({ "content": format!("**{}**\n{}", title, body) })),
+    NotificationKind::Slack => CLIENT
+      .post(required(&config.url, "url")?)
+      .json(&json!({ "text": format!("*{}*\n{}", title, body) })),
+    NotificationKind::Smtp => return send_email(config, title, body, timeout).await,
+  };
+
+  request.timeout(timeout).send().await?.error_for_status()?;
+
+  Ok(())
+}
+
+fn required<'a>(value: &'a Option<String>, name: &str) -> anyhow::Result<&'a str> {
+  value
+    .as_deref()
+    .ok_or_else(|| anyhow!("通知渠道缺少 {} 配置", name))
+}
+
+async fn send_email(
+  config: &NotificationConfig,
+  title: String,
+  body: String,
+  timeout: Duration,
+) -> anyhow::Result<()> {
+  let host = required(&config.smtp_host, "smtp_host")?;
+  let mut transport = match config.smtp_security {
+    SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host)?,
+    SmtpSecurity::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?,
+    SmtpSecurity::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+  }
+  .timeout(Some(timeout));
+  if let Some(port) = config.smtp_port {
+    transport = transport.port(port);
+  }
+  if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+    transport = transport.credentials(Credentials::new(username.clone(), password.clone()));
+  }
+
+  if config.smtp_to.is_empty() {
+    bail!("通知渠道缺少 smtp_to 配置");
+  }
+
+  let mut message = Message::builder()
+    .from(required(&config.smtp_from, "smtp_from")?.parse::<Mailbox>()?)
+    .subject(title)
+    .header(ContentType::TEXT_PLAIN);
+  for to in &config.smtp_to {
+    message = message.to(to.parse::<Mailbox>()?);
+  }
+
+  transport.build().send(message.body(body)?).await?;
+
+  Ok(())
+}
+
+// 应用所有者的通知渠道与全站通知渠道都会收到应用的状态变化
+async fn notify(db: &DbConn, app: &apps::Model, event: &NotifyEvent) -> Result<(), DbErr> {
+  let channels = notifications::Entity::find()
+    .filter(notifications::Column::Enabled.eq(true))
+    .filter(
+      Condition::any()
+        .add(notifications::Column::IsGlobal.eq(true))
+        .add(notifications::Column::OwnerId.eq(app.owner_id)),
+    )
+    .all(db)
+    .await?;
+
+  let results = join_all(channels.iter().map(|channel| send(channel, event))).await;
+  for (channel, result) in channels.iter().zip(results) {
+    if let Err(err) = result {
+      log::error!("Send notification {} failed: {}", channel.name, err);
+    }
+  }
+
+  Ok(())
+}
+
+// 根据检查结果更新应用的告警状态，连续失败达到次数或恢复正常时发送通知
+pub async fn handle_check(
+  db: &DbConn,
+  app: &apps::Model,
+  status: CheckStatus,
+  message: Option<String>,
+) -> Result<(), DbErr> {
+  let alert = app_alerts::Entity::find_by_id(app.id).one(db).await?;
+  let (notified_status, failures, notified_at) = match alert {
+    Some(alert) => (alert.status, alert.failures, alert.notified_at),
+    None => (CheckStatus::Up, 0, None),
+  };
+
+  let failures = match status {
+    CheckStatus::Up => 0,
+    CheckStatus::Down => failures.saturating_add(1),
+    CheckStatus::Maintenance => failures,
+  };
+  let notify_after = app
+    .monitor
+    .as_ref()
+    .and_then(|monitor| monitor.notify_after)
+    .unwrap_or(SETTINGS.monitor.notify_after)
+    .max(1) as i32;
+
+  let current_status = match status {
+    CheckStatus::Down if failures < notify_after => notified_status,
+    status => status,
+  };
+
+  // 距离上次通知的时间过短时暂不通知，状态稳定后再通知，避免状态抖动时频繁通知
+  let now = Utc::now().naive_utc();
+  let suppressed = notified_at.is_some_and(|notified_at| {
+    notified_at + ChronoDuration::seconds(SETTINGS.monitor.notify_interval) > now
+  });
+  let should_notify = current_status != notified_status && !suppressed;
+
+  app_alerts::Entity::insert(app_alerts::ActiveModel {
+    app_id: Set(app.id),
+    status: Set(if should_notify {
+      current_status
+    } else {
+      notified_status
+    }),
+    failures: Set(failures),
+    notified_at: Set(if should_notify { Some(now) } else { notified_at }),
+  })
+  .on_conflict(
+    OnConflict::column(app_alerts::Column::AppId)
+      .update_columns([
+        app_alerts::Column::Status,
+        app_alerts::Column::Failures,
+        app_alerts::Column::NotifiedAt,
+      ])
+      .to_owned(),
+  )
+  .exec(db)
+  .await?;
+
+  if should_notify {
+    let event = NotifyEvent {
+      app_id: Some(app.id),
+      name: app.name.clone(),
+      url: app.url.clone(),
+      status: current_status,
+      message: message.unwrap_or_default(),
+      time: now,
+    };
+    notify(db, app, &event).await?;
+  }
+
+  Ok(())
+}
+
+// 令牌与 SMTP 密码不返回给前端，只返回是否已设置
+#[derive(Debug, Serialize)]
+pub struct NotificationInfo {
+  #[serde(flatten)]
+  pub notification: notifications::Model,
+  pub has_token: bool,
+  pub has_smtp_password: bool,
+}
+
+impl From<notifications::Model> for NotificationInfo {
+  fn from(mut notification: notifications::Model) -> Self {
+    let has_token = notification.config.token.take().is_some();
+    let has_smtp_password = notification.config.smtp_password.take().is_some();
+
+    Self {
+      notification,
+      has_token,
+      has_smtp_password,
+    }
+  }
+}
+
+pub async fn get_user_all_notification(
+  db: &DbConn,
+  user_id: i64,
+) -> Result<Vec<NotificationInfo>, AppError> {
+  let user = users::Entity::find_by_id(user_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户不存在"))?;
+
+  // 管理员可以管理全站通知渠道
+  let mut condition = Condition::any().add(
+    Condition::all()
+      .add(notifications::Column::OwnerId.eq(user_id))
+      .add(notifications::Column::IsGlobal.eq(false)),
+  );
+  if user.is_admin {
+    condition = condition.add(notifications::Column::IsGlobal.eq(true));
+  }
+
+  let notifications = notifications::Entity::find()
+    .filter(condition)
+    .order_by_asc(notifications::Column::CreatedAt)
+    .all(db)
+    .await?;
+
+  Ok(notifications.into_iter().map(Into::into).collect())
+}
+
+// 校验当前用户可以管理的通知渠道，全站通知渠道只有管理员可以管理
+async fn check_notification_owner(
+  db: &DbConn,
+  operator_id: i64,
+  notification_id: i64,
+) -> Result<notifications::Model, AppError> {
+  let notification = notifications::Entity::find_by_id(notification_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "通知渠道不存在"))?;
+
+  if notification.is_global {
+    check_admin(db, operator_id).await?;
+  } else if notification.owner_id != operator_id {
+    return Err(AppError::new(StatusCode::NOT_FOUND, 404, "通知渠道不存在"));
+  }
+
+  Ok(notification)
+}
+
+fn validate_mailbox(mailbox: &str) -> Result<(), ValidationError> {
+  mailbox
+    .parse::<Mailbox>()
+    .map(|_| ())
+    .map_err(|_| ValidationError::new("mailbox"))
+}
+
+fn validate_mailboxes(mailboxes: &[String]) -> Result<(), ValidationError> {
+  mailboxes
+    .iter()
+    .try_for_each(|mailbox| validate_mailbox(mailbox))
+}
+
+// 编辑时未传入的密钥使用已保存的值，因此在合并配置后再检查是否完整
+fn check_config(kind: NotificationKind, config: &NotificationConfig) -> Result<(), AppError> {
+  let missing = match kind {
+    NotificationKind::Webhook | NotificationKind::Discord | NotificationKind::Slack => {
+      config.url.is_none()
+    }
+    NotificationKind::Ntfy => config.topic.is_none(),
+    NotificationKind::Gotify => config.url.is_none() || config.token.is_none(),
+    NotificationKind::Smtp => {
+      config.smtp_host.is_none() || config.smtp_from.is_none() || config.smtp_to.is_empty()
+    }
+  };
+
+  if missing {
+    return Err(AppError::new(
+      StatusCode::UNPROCESSABLE_ENTITY,
+      422,
+      "通知渠道配置不完整",
+    ));
+  }
+
+  Ok(())
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct NotificationConfigData {
+  #[validate(
+    length(min = 1, max = 255, message = "通知地址长度不得超过 255 个字符"),
+    url(message = "通知地址格式不合法")
+  )]
+  url: Option<String>,
+  // 编辑时缺失则保留已保存的令牌，为 null 时删除令牌
+  #[validate(length(min = 1, max = 255, message = "令牌长度不得超过 255 个字符"))]
+  #[serde(
+    default,
+    deserialize_with = "double_option",
+    skip_serializing_if = "Option::is_none"
+  )]
+  token: Option<Option<String>>,
+  #[validate(length(min = 1, max = 64, message = "主题长度不得超过 64 个字符"))]
+  topic: Option<String>,
+  #[validate(range(min = 0, max = 10, message = "优先级必须为 0 ~ 10"))]
+  priority: Option<i8>,
+  #[validate(length(min = 1, max = 255, message = "SMTP 服务器长度不得超过 255 个字符"))]
+  smtp_host: Option<String>,
+  #[validate(range(min = 1, message = "SMTP 端口必须为 1 ~ 65535"))]
+  smtp_port: Option<u16>,
+  #[serde(default)]
+  smtp_security: SmtpSecurity,
+  #[validate(length(min = 1, max = 255, message = "SMTP 用户名长度不得超过 255 个字符"))]
+  smtp_username: Option<String>,
+  // 编辑时缺失则保留已保存的密码，为 null 时删除密码
+  #[validate(length(min = 1, max = 255, message = "SMTP 密码长度不得超过 255 个字符"))]
+  #[serde(
+    default,
+    deserialize_with = "double_option",
+    skip_serializing_if = "Option::is_none"
+  )]
+  smtp_password: Option<Option<String>>,
+  #[validate(custom(function = "validate_mailbox", message = "发件人地址不合法"))]
+  smtp_from: Option<String>,
+  #[serde(default)]
+  #[validate(
+    length(max = 20, message = "收件人不得超过 20 个"),
+    custom(function = "validate_mailboxes", message = "收件人地址不合法")
+  )]
+  smtp_to: Vec<String>,
+}
+
+impl NotificationConfigData {
+  // 使用已保存的配置补全编辑时未传入的令牌与密码
+  fn to_config(&self, saved: Option<&NotificationConfig>) -> NotificationConfig {
+    NotificationConfig {
+      url: self.url.clone(),
+      token: match &self.token {
+        Some(token) => token.clone(),
+        None => saved.and_then(|saved| saved.token.clone()),
+      },
+      topic: self.topic.clone(),
+      priority: self.priority,
+      smtp_host: self.smtp_host.clone(),
+      smtp_port: self.smtp_port,
+      smtp_security: self.smtp_security,
+      smtp_username: self.smtp_username.clone(),
+      smtp_password: match &self.smtp_password {
+        Some(smtp_password) => smtp_password.clone(),
+        None => saved.and_then(|saved| saved.smtp_password.clone()),
+      },
+      smtp_from: self.smtp_from.clone(),
+      smtp_to: self.smtp_to.clone(),
+    }
+  }
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct NotificationData {
+  #[validate(length(min = 1, max = 30, message = "通知渠道名称长度不得超过 30 个字符"))]
+  name: String,
+  kind: NotificationKind,
+  #[validate(nested)]
+  config: NotificationConfigData,
+  #[validate(length(min = 1, max = 255, message = "标题模板长度不得超过 255 个字符"))]
+  title_template: Option<String>,
+  #[validate(length(min = 1, max = 2000, message = "内容模板长度不得超过 2000 个字符"))]
+  body_template: Option<String>,
+  #[serde(default = "default_enabled")]
+  enabled: bool,
+  // 全站通知渠道，只有管理员可以创建
+  #[serde(default)]
+  is_global: bool,
+}
+
+fn default_enabled() -> bool {
+  true
+}
+
+pub async fn create_notification(
+  db: &DbConn,
+  operator_id: i64,
+  data: &NotificationData,
+) -> Result<NotificationInfo, AppError> {
+  if data.is_global {
+    check_admin(db, operator_id).await?;
+  }
+
+  let config = data.config.to_config(None);
+  check_config(data.kind, &config)?;
+
+  let notification = notifications::ActiveModel {
+    name: Set(data.name.clone()),
+    kind: Set(data.kind),
+    config: Set(config),
+    title_template: Set(data.title_template.clone()),
+    body_template: Set(data.body_template.clone()),
+    enabled: Set(data.enabled),
+    is_global: Set(data.is_global),
+    owner_id: Set(operator_id),
+    ..Default::default()
+  }
+  .insert(db)
+  .await?;
+
+  Ok(notification.into())
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct UpdateNotificationData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+  #[serde(flatten)]
+  #[validate(nested)]
+  data: NotificationData,
+}
+
+pub async fn update_notification(
+  db: &DbConn,
+  operator_id: i64,
+  data: &UpdateNotificationData,
+) -> Result<NotificationInfo, AppError> {
+  let notification = check_notification_owner(db, operator_id, data.id).await?;
+
+  if data.data.is_global != notification.is_global {
+    check_admin(db, operator_id).await?;
+  }
+
+  let data = &data.data;
+  let config = data.config.to_config(Some(&notification.config));
+  check_config(data.kind, &config)?;
+  let mut notification = notification.into_active_model();
+
+  notification.name = Set(data.name.clone());
+  notification.kind = Set(data.kind);
+  notification.config = Set(config);
+  notification.title_template = Set(data.title_template.clone());
+  notification.body_template = Set(data.body_template.clone());
+  notification.enabled = Set(data.enabled);
+  notification.is_global = Set(data.is_global);
+
+  Ok(notification.update(db).await?.into())
+}
+
+pub async fn delete_notification(
+  db: &DbConn,
+  operator_id: i64,
+  notification_id: i64,
+) -> Result<DeleteResult, AppError> {
+  check_notification_owner(db, operator_id, notification_id)
+    .await?
+    .delete(db)
+    .await
+    .map_err(Into::into)
+}
+
+// 发送一条测试通知，用于检查通知渠道的配置
+pub async fn test_notification(
+  db: &DbConn,
+  operator_id: i64,
+  notification_id: i64,
+) -> Result<(), AppError> {
+  let notification = check_notification_owner(db, operator_id, notification_id).await?;
+
+  let event = NotifyEvent {
+    app_id: None,
+    name: "Dash".to_string(),
+    url: String::new(),
+    status: CheckStatus::Up,
+    message: "这是一条测试通知".to_string(),
+    time: Utc::now().naive_utc(),
+  };
+
+  send(&notification, &event).await.map_err(|err| {
+    AppError::new(
+      StatusCode::BAD_GATEWAY,
+      502,
+      format!("通知发送失败：{}", err),
+    )
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use actix_web::rt::{spawn, task::JoinHandle};
+  use chrono::NaiveDate;
+  use serde_json::Value;
+  use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+  };
+
+  struct HttpRequest {
+    head: String,
+    body: Value,
+  }
+
+  // 接收一个 HTTP 请求并返回指定的状态码
+  async fn serve_http(status: u16) -> (String, JoinHandle<HttpRequest>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = spawn(async move {
+      let (stream, _) = listener.accept().await.unwrap();
+      let mut reader = BufReader::new(stream);
+      let mut head = String::new();
+      loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).await.unwrap();
+        if line.trim().is_empty() {
+          break;
+        }
+        head.push_str(&line.to_ascii_lowercase());
+      }
+
+      let length = head
+        .lines()
+        .find_map(|line| line.strip_prefix("content-length:"))
+        .and_then(|length| length.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+      let mut body = vec![0; length];
+      reader.read_exact(&mut body).await.unwrap();
+      reader
+        .get_mut()
+        .write_all(
+          format!(
+            "HTTP/1.1 {} Test\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            status
+          )
+          .as_bytes(),
+        )
+        .await
+        .unwrap();
+
+      HttpRequest {
+        head,
+        body: serde_json::from_slice(&body).unwrap(),
+      }
+    });
+
+    (url, handle)
+  }
+
+  struct SmtpSession {
+    commands: Vec<String>,
+    data: String,
+  }
+
+  // 只实现发送一封邮件需要的命令，不支持扩展
+  async fn serve_smtp() -> (u16, JoinHandle<SmtpSession>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let handle = spawn(async move {
+      let (stream, _) = listener.accept().await.unwrap();
+      let (reader, mut writer) = stream.into_split();
+      let mut lines = BufReader::new(reader).lines();
+      let mut session = SmtpSession {
+        commands: Vec::new(),
+        data: String::new(),
+      };
+      let mut in_data = false;
+
+      writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+      while let Some(line) = lines.next_line().await.unwrap() {
+        if in_data {
+          match line.as_str() {
+            "." => {
+              in_data = false;
+              writer.write_all(b"250 OK\r\n").await.unwrap();
+            }
+            _ => session.data.push_str(&format!("{}\n", line)),
+          }
+          continue;
+        }
+
+        let command = line
+          .split_whitespace()
+          .next()
+          .unwrap_or_default()
+          .to_ascii_uppercase();
+        session.commands.push(line);
+        match command.as_str() {
+          "DATA" => {
+            in_data = true;
+            writer.write_all(b"354 Start mail input\r\n").await.unwrap();
+          }
+          "QUIT" => {
+            writer.write_all(b"221 Bye\r\n").await.unwrap();
+            break;
+          }
+          _ => writer.write_all(b"250 OK\r\n").await.unwrap(),
+        }
+      }
+
+      session
+    });
+
+    (port, handle)
+  }
+
+  fn event() -> NotifyEvent {
+    NotifyEvent {
+      app_id: Some(1),
+      name: "Grafana".to_string(),
+      url: "https://grafana.example.com".to_string(),
+      status: CheckStatus::Down,
+      message: "连接超时".to_string(),
+      time: NaiveDate::from_ymd_opt(2026, 10, 19)
+        .unwrap()
+        .and_hms_opt(8, 0, 0)
+        .unwrap(),
+    }
+  }
+
+  fn notification(kind: NotificationKind, config: NotificationConfig) -> notifications::Model {
+    notifications::Model {
+      id: 1,
+      name: "测试".to_string(),
+      kind,
+      config,
+      title_template: None,
+      body_template: None,
+      enabled: true,
+      is_global: false,
+      owner_id: 1,
+      created_at: event().time,
+      deleted_at: None,
+    }
+  }
+
+  #[actix_web::test]
+  async fn sends_webhook() {
+    let (url, server) = serve_http(200).await;
+    let config = NotificationConfig {
+      url: Some(format!("{}/hook", url)),
+      ..Default::default()
+    };
+
+    send(&notification(NotificationKind::Webhook, config), &event())
+      .await
+      .unwrap();
+
+    let request = server.await.unwrap();
+    assert!(request.head.starts_with("post /hook http/1.1"));
+    assert_eq!(request.body["title"], "[异常] Grafana");
+    assert_eq!(
+      request.body["body"],
+      "Grafana（https://grafana.example.com）异常：连接超时\n时间：2026-10-19 08:00:00"
+    );
+    assert_eq!(request.body["app"]["id"], "1");
+    assert_eq!(request.body["message"], "连接超时");
+  }
+
+  #[actix_web::test]
+  async fn sends_ntfy_with_token() {
+    let (url, server) = serve_http(200).await;
+    let config = NotificationConfig {
+      url: Some(url),
+      token: Some("tk_test".to_string()),
+      topic: Some("dash".to_string()),
+      ..Default::default()
+    };
+
+    send(&notification(NotificationKind::Ntfy, config), &event())
+      .await
+      .unwrap();
+
+    let request = server.await.unwrap();
+    assert!(request.head.contains("authorization: bearer tk_test"));
+    assert_eq!(request.body["topic"], "dash");
+    assert_eq!(request.body["priority"], 3);
+    assert_eq!(request.body["tags"][0], "rotating_light");
+  }
+
+  #[actix_web::test]
+  async fn sends_gotify_with_custom_template() {
+    let (url, server) = serve_http(200).await;
+    let config = NotificationConfig {
+      url: Some(format!("{}/", url)),
+      token: Some("app_token".to_string()),
+      ..Default::default()
+    };
+    let mut notification = notification(NotificationKind::Gotify, config);
+    notification.title_template = Some("{{name}} {{status}}".to_string());
+    notification.body_template = Some("{{url}}".to_string());
+
+    send(&notification, &event()).await.unwrap();
+
+    let request = server.await.unwrap();
+    assert!(request.head.starts_with("post /message http/1.1"));
+    assert!(request.head.contains("x-gotify-key: app_token"));
+    assert_eq!(request.body["title"], "Grafana 异常");
+    assert_eq!(request.body["message"], "https://grafana.example.com");
+    assert_eq!(request.body["priority"], 5);
+  }
+
+  #[actix_web::test]
+  async fn fails_on_error_status() {
+    let (url, server) = serve_http(500).await;
+    let config = NotificationConfig {
+      url: Some(url),
+      ..Default::default()
+    };
+
+    let result = send(&notification(NotificationKind::Slack, config), &event()).await;
+
+    assert!(result.is_err());
+    let request = server.await.unwrap();
+    assert!(request.body["text"]
+      .as_str()
+      .is_some_and(|text| text.starts_with("*[异常] Grafana*\n")));
+  }
+
+  #[actix_web::test]
+  async fn sends_email() {
+    let (port, server) = serve_smtp().await;
+    let config = NotificationConfig {
+      smtp_host: Some("127.0.0.1".to_string()),
+      smtp_port: Some(port),
+      smtp_security: SmtpSecurity::None,
+      smtp_from: Some("Dash <dash@example.com>".to_string()),
+      smtp_to: vec!["ops@example.com".to_string(), "dev@example.com".to_string()],
+      ..Default::default()
+    };
+
+    send(&notification(NotificationKind::Smtp, config), &event())
+      .await
+      .unwrap();
+
+    let session = server.await.unwrap();
+    let commands = session.commands.join("\n");
+    assert!(commands.contains("MAIL FROM:<dash@example.com>"));
+    assert!(commands.contains("RCPT TO:<ops@example.com>"));
+    assert!(commands.contains("RCPT TO:<dev@example.com>"));
+    assert!(session.data.contains("From: Dash <dash@example.com>"));
+    assert!(session.data.contains("ops@example.com"));
+  }
+
+  #[actix_web::test]
+  async fn requires_channel_config() {
+    let webhook = notification(NotificationKind::Webhook, NotificationConfig::default());
+    let smtp = notification(
+      NotificationKind::Smtp,
+      NotificationConfig {
+        smtp_host: Some("127.0.0.1".to_string()),
+        smtp_security: SmtpSecurity::None,
+        smtp_from: Some("dash@example.com".to_string()),
+        ..Default::default()
+      },
+    );
+
+    let err = send(&webhook, &event()).await.unwrap_err();
+    assert_eq!(err.to_string(), "通知渠道缺少 url 配置");
+    let err = send(&smtp, &event()).await.unwrap_err();
+    assert_eq!(err.to_string(), "通知渠道缺少 smtp_to 配置");
+  }
+
+  #[test]
+  fn keeps_saved_secrets_when_omitted() {
+    let saved = NotificationConfig {
+      token: Some("token".to_string()),
+      smtp_password: Some("password".to_string()),
+      ..Default::default()
+    };
+
+    let data =
+      serde_json::from_value::<NotificationConfigData>(json!({ "topic": "dash" })).unwrap();
+    let config = data.to_config(Some(&saved));
+    assert_eq!(config.token.as_deref(), Some("token"));
+    assert_eq!(config.smtp_password.as_deref(), Some("password"));
+    assert_eq!(config.topic.as_deref(), Some("dash"));
+
+    let data = serde_json::from_value::<NotificationConfigData>(json!({
+      "token": "new",
+      "smtp_password": null,
+    }))
+    .unwrap();
+    let config = data.to_config(Some(&saved));
+    assert_eq!(config.token.as_deref(), Some("new"));
+    assert_eq!(config.smtp_password, None);
+  }
+
+  #[test]
+  fn hides_secrets_from_response() {
+    let info = NotificationInfo::from(notification(
+      NotificationKind::Gotify,
+      NotificationConfig {
+        url: Some("https://gotify.example.com".to_string()),
+        token: Some("token".to_string()),
+        ..Default::default()
+      },
+    ));
+
+    let value = serde_json::to_value(&info).unwrap();
+    assert_eq!(value["config"]["token"], Value::Null);
+    assert_eq!(value["config"]["smtp_password"], Value::Null);
+    assert_eq!(value["has_token"], true);
+    assert_eq!(value["has_smtp_password"], false);
+  }
+}
@@ -39,7 +39,7 @@ pub async fn update_password(
     .ok_or(AppError::new(StatusCode::FORBIDDEN, 404, "未找到对应账号"))?
     .into_active_model();
 
-  crypto::verify(&user.password.as_ref(), &data.old_password)
+  crypto::verify(user.password.as_ref(), &data.old_password)
     .map_err(AppError::from_err)?
     .then_some(true)
     .ok_or(AppError::new(StatusCode::FORBIDDEN, 403, "原密码不正确"))?;
@@ -1,10 +1,8 @@
-use std::time::Duration;
-
+use super::http::CLIENT;
 use crate::errors::AppError;
 
 use actix_web::web;
 use futures_core::Stream;
-use reqwest::Client;
 use serde::Deserialize;
 use validator::Validate;
 
@@ -20,9 +18,6 @@ pub struct ProxyData {
 pub async fn get(
   data: &ProxyData,
 ) -> Result<impl Stream<Item = reqwest::Result<web::Bytes>> + 'static, AppError> {
-  let client = Client::builder()
-    .connect_timeout(Duration::from_secs(120))
-    .build()?;
-  let resp = client.get(&data.url).send().await?;
+  let resp = CLIENT.get(&data.url).send().await?;
   Ok(resp.bytes_stream())
 }
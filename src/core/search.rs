@@ -0,0 +1,622 @@
+use super::{app::get_user_visible_app, tag::get_apps_tags};
+use crate::errors::AppError;
+
+use entity::{apps, tags};
+use reqwest::Url;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DbConn, Statement};
+use serde::{Deserialize, Serialize};
+use std::{
+  cmp::Ordering,
+  collections::{BTreeMap, HashMap, HashSet},
+};
+use validator::Validate;
+
+// 应用数量超过该值时先使用数据库的全文索引筛选候选应用
+const FULL_TEXT_THRESHOLD: usize = 200;
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_SEARCH_LIMIT: usize = 100;
+
+// 匹配的字符范围，按字符计算，左闭右开
+type Ranges = Vec<(usize, usize)>;
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct SearchAppQuery {
+  #[validate(length(min = 1, max = 100, message = "搜索内容长度不得超过 100 个字符"))]
+  q: String,
+  // 默认返回 20 个结果
+  limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchField {
+  Name,
+  Host,
+  Description,
+  Tag,
+}
+
+impl SearchField {
+  fn weight(self) -> f64 {
+    match self {
+      SearchField::Name => 1.0,
+      SearchField::Tag => 0.8,
+      SearchField::Host => 0.6,
+      SearchField::Description => 0.4,
+    }
+  }
+
+  // 描述较长，只做子串匹配，避免零散字符的误匹配
+  fn is_fuzzy(self) -> bool {
+    self != SearchField::Description
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchHighlight {
+  pub field: SearchField,
+  pub value: String,
+  pub ranges: Ranges,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResult {
+  #[serde(flatten)]
+  pub app: apps::Model,
+  pub tags: Vec<tags::Model>,
+  pub score: f64,
+  pub highlights: Vec<SearchHighlight>,
+}
+
+// 逐个字符转为小写，保证字符位置与原文一致
+fn lowercase_chars(text: &str) -> Vec<char> {
+  text
+    .chars()
+    .map(|char| char.to_lowercase().next().unwrap_or(char))
+    .collect()
+}
+
+fn find_chars(text: &[char], pattern: &[char]) -> Option<usize> {
+  text
+    .windows(pattern.len())
+    .position(|window| window == pattern)
+}
+
+// 返回匹配得分与字符范围，子串匹配优先，否则按顺序匹配全部字符
+fn match_field(text: &[char], token: &[char], fuzzy: bool) -> Option<(f64, Ranges)> {
+  if token.is_empty() || token.len() > text.len() {
+    return None;
+  }
+
+  if let Some(start) = find_chars(text, token) {
+    let score = match start {
+      0 => 1.5,
+      start if !text[start - 1].is_alphanumeric() => 1.25,
+      _ => 1.0,
+    };
+    return Some((score, vec![(start, start + token.len())]));
+  }
+
+  if !fuzzy {
+    return None;
+  }
+
+  let mut ranges = Vec::<(usize, usize)>::new();
+  let mut chars = token.iter().peekable();
+  for (index, char) in text.iter().enumerate() {
+    if chars.peek() != Some(&char) {
+      continue;
+    }
+    chars.next();
+    match ranges.last_mut() {
+      Some(range) if range.1 == index => range.1 += 1,
+      _ => ranges.push((index, index + 1)),
+    }
+    if chars.peek().is_none() {
+      break;
+    }
+  }
+  if chars.peek().is_some() {
+    return None;
+  }
+
+  // 匹配的字符越集中得分越高，过于分散时不算匹配
+  let span = ranges.last()?.1 - ranges.first()?.0;
+  if span > token.len() * 3 {
+    return None;
+  }
+  Some((
+    0.5 * token.len() as f64 / span as f64 / ranges.len() as f64,
+    ranges,
+  ))
+}
+
+fn merge_ranges(mut ranges: Ranges) -> Ranges {
+  ranges.sort();
+  let mut merged = Vec::<(usize, usize)>::new();
+  for range in ranges {
+    match merged.last_mut() {
+      Some(last) if range.0 <= last.1 => last.1 = last.1.max(range.1),
+      _ => merged.push(range),
+    }
+  }
+  merged
+}
+
+// 每个关键词都需要匹配至少一个字段，得分为各关键词最佳匹配的加权得分之和
+fn rank_app(
+  app: apps::Model,
+  tags: Vec<tags::Model>,
+  tokens: &[Vec<char>],
+) -> Option<SearchResult> {
+  let host = Url::parse(&app.url)
+    .ok()
+    .and_then(|url| url.host_str().map(String::from));
+
+  let mut fields = vec![(SearchField::Name, app.name.clone())];
+  fields.extend(host.map(|host| (SearchField::Host, host)));
+  fields.extend(
+    app
+      .description
+      .clone()
+      .map(|description| (SearchField::Description, description)),
+  );
+  fields.extend(tags.iter().map(|tag| (SearchField::Tag, tag.name.clone())));
+  let field_chars = fields
+    .iter()
+    .map(|(_, value)| lowercase_chars(value))
+    .collect::<Vec<Vec<char>>>();
+
+  let mut score = 0.0;
+  let mut field_ranges = BTreeMap::<usize, Ranges>::new();
+  for token in tokens {
+    let mut best: Option<(f64, usize, Ranges)> = None;
+    for (index, (field, _)) in fields.iter().enumerate() {
+      let Some((field_score, ranges)) = match_field(&field_chars[index], token, field.is_fuzzy())
+      else {
+        continue;
+      };
+      let field_score = field_score * field.weight();
+      if best
+        .as_ref()
+        .is_none_or(|(best_score, ..)| field_score > *best_score)
+      {
+        best = Some((field_score, index, ranges));
+      }
+    }
+
+    let (token_score, index, ranges) = best?;
+    score += token_score;
+    field_ranges.entry(index).or_default().extend(ranges);
+  }
+
+  // 按字段的顺序返回匹配范围
+  let highlights = field_ranges
+    .into_iter()
+    .map(|(index, ranges)| {
+      let (field, value) = fields[index].clone();
+      SearchHighlight {
+        field,
+        value,
+        ranges: merge_ranges(ranges),
+      }
+    })
+    .collect::<Vec<SearchHighlight>>();
+
+  Some(SearchResult {
+    app,
+    tags,
+    score,
+    highlights,
+  })
+}
+
+// SQLite 未启用 FTS5 时迁移不会创建全文索引表
+async fn has_sqlite_table(db: &DbConn, name: &str) -> bool {
+  let statement = Statement::from_sql_and_values(
+    DatabaseBackend::Sqlite,
+    "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?",
+    [name.into()],
+  );
+  matches!(db.query_one(statement).await, Ok(Some(_)))
+}
+
+// 使用数据库的全文索引在可见应用中查询候选应用，未建立索引或查询失败时返回空，由调用方在内存中匹配全部应用
+async fn full_text_search(db: &DbConn, app_ids: &[i64], tokens: &[String]) -> Option<HashSet<i64>> {
+  // 去掉全文检索语法中的特殊字符
+  let words = tokens
+    .iter()
+    .map(|token| {
+      token
+        .chars()
+        .filter(|char| char.is_alphanumeric())
+        .collect::<String>()
+    })
+    .filter(|word| !word.is_empty())
+    .collect::<Vec<String>>();
+  if words.is_empty() {
+    return None;
+  }
+
+  // 应用 ID 均为整数，直接拼接到查询中
+  let app_ids = app_ids
+    .iter()
+    .map(i64::to_string)
+    .collect::<Vec<String>>()
+    .join(", ");
+
+  let backend = db.get_database_backend();
+  let statement = match backend {
+    // trigram 分词至少需要 3 个字符，关键词含有特殊字符时去掉后的查询不再等价于子串匹配
+    DatabaseBackend::Sqlite => {
+      if words != tokens
+        || words.iter().any(|word| word.chars().count() < 3)
+        || !has_sqlite_table(db, "apps_fts").await
+      {
+        return None;
+      }
+      let query = words
+        .iter()
+        .map(|word| format!("\"{}\"", word))
+        .collect::<Vec<String>>()
+        .join(" OR ");
+      Statement::from_sql_and_values(
+        backend,
+        format!(
+          "SELECT rowid FROM apps_fts WHERE apps_fts MATCH ? AND rowid IN ({})",
+          app_ids
+        ),
+        [query.into()],
+      )
+    }
+    DatabaseBackend::Postgres => {
+      let query = words
+        .iter()
+        .map(|word| format!("{}:*", word))
+        .collect::<Vec<String>>()
+        .join(" | ");
+      Statement::from_sql_and_values(
+        backend,
+        format!(
+          "SELECT id FROM apps WHERE search_vector @@ to_tsquery('simple', $1) AND id IN ({})",
+          app_ids
+        ),
+        [query.into()],
+      )
+    }
+    DatabaseBackend::MySql => {
+      let query = words
+        .iter()
+        .map(|word| format!("{}*", word))
+        .collect::<Vec<String>>()
+        .join(" ");
+      Statement::from_sql_and_values(
+        backend,
+        format!(
+          "SELECT id FROM apps WHERE MATCH (name, url, description) AGAINST (? IN BOOLEAN MODE) AND id IN ({})",
+          app_ids
+        ),
+        [query.into()],
+      )
+    }
+  };
+
+  match db.query_all(statement).await {
+    Ok(rows) => Some(
+      rows
+        .iter()
+        .filter_map(|row| row.try_get_by_index::<i64>(0).ok())
+        .collect(),
+    ),
+    Err(err) => {
+      log::warn!("Full text search failed: {}", err);
+      None
+    }
+  }
+}
+
+// 全文索引未命中的应用中，每个关键词可能得到的最高分
+fn missed_token_score(backend: DatabaseBackend) -> f64 {
+  match backend {
+    // trigram 索引能找到全部子串匹配，未命中的应用只可能模糊匹配名称、域名或标签，
+    // 模糊匹配至少分成两段，得分低于 0.25
+    DatabaseBackend::Sqlite => 0.25,
+    // 按词建立的索引受分词规则与停用词影响，可能漏掉任意位置的匹配
+    DatabaseBackend::Postgres | DatabaseBackend::MySql => 1.5,
+  }
+}
+
+// 得分相同时保持应用原有的顺序
+fn rank_apps(
+  apps: Vec<apps::Model>,
+  apps_tags: &HashMap<i64, Vec<tags::Model>>,
+  tokens: &[Vec<char>],
+) -> Vec<SearchResult> {
+  let mut results = apps
+    .into_iter()
+    .filter_map(|app| {
+      let tags = apps_tags.get(&app.id).cloned().unwrap_or_default();
+      rank_app(app, tags, tokens)
+    })
+    .collect::<Vec<SearchResult>>();
+  results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+  results
+}
+
+// 搜索用户可以访问的全部应用，按名称、域名、描述与标签模糊匹配
+pub async fn search_app(
+  db: &DbConn,
+  user_id: i64,
+  query: &SearchAppQuery,
+) -> Result<Vec<SearchResult>, AppError> {
+  let raw_tokens = query
+    .q
+    .split_whitespace()
+    .map(|token| token.to_lowercase())
+    .collect::<Vec<String>>();
+  let tokens = raw_tokens
+    .iter()
+    .map(|token| lowercase_chars(token))
+    .collect::<Vec<Vec<char>>>();
+  if tokens.is_empty() {
+    return Ok(Vec::new());
+  }
+
+  let visible_apps = get_user_visible_app(db, user_id).await?;
+  let apps_tags = get_apps_tags(db, visible_apps.iter().map(|app| app.id).collect()).await?;
+  let limit = query
+    .limit
+    .unwrap_or(DEFAULT_SEARCH_LIMIT)
+    .clamp(1, MAX_SEARCH_LIMIT);
+
+  // 全文索引不支持模糊匹配，也不包含标签。只有候选应用之外的应用不可能排进前 limit 个时
+  // 才直接返回候选应用的结果，否则在全部应用中匹配，保证结果与应用数量较少时一致
+  if visible_apps.len() > FULL_TEXT_THRESHOLD {
+    let app_ids = visible_apps.iter().map(|app| app.id).collect::<Vec<i64>>();
+    if let Some(full_text_app_ids) = full_text_search(db, &app_ids, &raw_tokens).await {
+      let candidates = visible_apps
+        .iter()
+        .filter(|app| {
+          full_text_app_ids.contains(&app.id)
+            || apps_tags.get(&app.id).is_some_and(|tags| {
+              tags.iter().any(|tag| {
+                let name = lowercase_chars(&tag.name);
+                tokens
+                  .iter()
+                  .any(|token| find_chars(&name, token).is_some())
+              })
+            })
+        })
+        .cloned()
+        .collect::<Vec<apps::Model>>();
+      let mut results = rank_apps(candidates, &apps_tags, &tokens);
+      // 得分相同时按应用原有的顺序排列，候选之外的应用可能排在前面，因此要求严格大于上限
+      let missed_score = missed_token_score(db.get_database_backend()) * tokens.len() as f64;
+      if results.len() >= limit && results[limit - 1].score > missed_score {
+        results.truncate(limit);
+        return Ok(results);
+      }
+    }
+  }
+
+  let mut results = rank_apps(visible_apps, &apps_tags, &tokens);
+  results.truncate(limit);
+
+  Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::testing;
+  use sea_orm::{ActiveModelTrait, IntoActiveModel, Set};
+
+  fn app(id: i64, name: &str, url: &str, description: Option<&str>) -> apps::Model {
+    apps::Model {
+      id,
+      name: name.into(),
+      url: url.into(),
+      internal_url: None,
+      description: description.map(String::from),
+      icon: None,
+      slug: None,
+      index: 0,
+      owner_id: 1,
+      group_id: None,
+      dashboard_id: None,
+      is_global: false,
+      audience: None,
+      links: None,
+      monitor: None,
+      push_token: None,
+      badge_token: None,
+      created_at: Default::default(),
+      deleted_at: None,
+    }
+  }
+
+  fn tag(id: i64, name: &str) -> tags::Model {
+    tags::Model {
+      id,
+      name: name.into(),
+      color: None,
+      owner_id: 1,
+      created_at: Default::default(),
+      deleted_at: None,
+    }
+  }
+
+  fn tokens(query: &str) -> Vec<Vec<char>> {
+    query.split_whitespace().map(lowercase_chars).collect()
+  }
+
+  #[test]
+  fn match_field_prefers_substring_at_word_start() {
+    let token = lowercase_chars("gra");
+    assert_eq!(
+      match_field(&lowercase_chars("Grafana"), &token, true),
+      Some((1.5, vec![(0, 3)]))
+    );
+    assert_eq!(
+      match_field(&lowercase_chars("my-grafana"), &token, true),
+      Some((1.25, vec![(3, 6)]))
+    );
+    assert_eq!(
+      match_field(&lowercase_chars("infograph"), &token, true),
+      Some((1.0, vec![(4, 7)]))
+    );
+  }
+
+  #[test]
+  fn match_field_fuzzy_ranges() {
+    let text = lowercase_chars("grafana");
+    let token = lowercase_chars("gfa");
+    let (score, ranges) = match_field(&text, &token, true).unwrap();
+    assert_eq!(ranges, vec![(0, 1), (3, 5)]);
+    assert!(score > 0.0 && score < 1.0);
+    assert_eq!(match_field(&text, &token, false), None);
+  }
+
+  #[test]
+  fn match_field_rejects_spread_or_missing_chars() {
+    let token = lowercase_chars("ab");
+    assert_eq!(
+      match_field(&lowercase_chars("axxxxxxxxxxb"), &token, true),
+      None
+    );
+    assert_eq!(match_field(&lowercase_chars("ba"), &token, true), None);
+    assert_eq!(match_field(&lowercase_chars("a"), &token, true), None);
+    assert_eq!(match_field(&lowercase_chars("ab"), &[], true), None);
+  }
+
+  #[test]
+  fn merge_ranges_joins_overlapping_and_adjacent() {
+    assert_eq!(
+      merge_ranges(vec![(5, 7), (0, 2), (1, 3), (7, 8), (10, 11)]),
+      vec![(0, 3), (5, 8), (10, 11)]
+    );
+  }
+
+  #[test]
+  fn rank_app_weights_fields_and_highlights_each() {
+    let result = rank_app(
+      app(
+        1,
+        "Grafana",
+        "https://metrics.example.com",
+        Some("监控面板"),
+      ),
+      vec![tag(1, "ops")],
+      &tokens("gra METRICS 监控 ops"),
+    )
+    .unwrap();
+
+    // 名称 1.5 + 域名 1.5 * 0.6 + 描述 1.5 * 0.4 + 标签 1.5 * 0.8
+    assert!((result.score - 4.2).abs() < 1e-9);
+    let highlights = result
+      .highlights
+      .iter()
+      .map(|highlight| {
+        (
+          highlight.field,
+          highlight.value.as_str(),
+          highlight.ranges.clone(),
+        )
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      highlights,
+      vec![
+        (SearchField::Name, "Grafana", vec![(0, 3)]),
+        (SearchField::Host, "metrics.example.com", vec![(0, 7)]),
+        (SearchField::Description, "监控面板", vec![(0, 2)]),
+        (SearchField::Tag, "ops", vec![(0, 3)]),
+      ]
+    );
+  }
+
+  #[test]
+  fn rank_app_merges_ranges_of_tokens_in_same_field() {
+    let result = rank_app(
+      app(1, "Home Assistant", "http://10.0.0.2:8123", None),
+      Vec::new(),
+      &tokens("home ass"),
+    )
+    .unwrap();
+    assert_eq!(result.highlights.len(), 1);
+    assert_eq!(result.highlights[0].ranges, vec![(0, 4), (5, 8)]);
+  }
+
+  #[test]
+  fn rank_app_requires_every_token() {
+    let app = app(1, "Grafana", "https://metrics.example.com", None);
+    assert!(rank_app(app, Vec::new(), &tokens("grafana jellyfin")).is_none());
+  }
+
+  #[test]
+  fn rank_apps_sorts_by_score_and_keeps_order_on_ties() {
+    let apps = vec![
+      app(
+        1,
+        "Docs",
+        "https://docs.example.com",
+        Some("Grafana 使用说明"),
+      ),
+      app(2, "Grafana", "https://grafana.example.com", None),
+      app(3, "Jellyfin", "https://media.example.com", None),
+      app(4, "Grafana", "https://grafana2.example.com", None),
+    ];
+    let ids = rank_apps(apps, &HashMap::new(), &tokens("grafana"))
+      .into_iter()
+      .map(|result| result.app.id)
+      .collect::<Vec<i64>>();
+    assert_eq!(ids, vec![2, 4, 1]);
+  }
+
+  #[actix_web::test]
+  async fn search_app_keeps_stronger_fuzzy_matches_outside_full_text_candidates() {
+    let db = testing::setup_db().await;
+    let user = testing::create_user(&db, "searcher").await;
+    for index in 0..FULL_TEXT_THRESHOLD {
+      testing::create_app(
+        &db,
+        user.id,
+        &format!("Filler {}", index),
+        &format!("https://filler{}.example.com", index),
+      )
+      .await;
+    }
+    // 描述中包含 dashboard，会被全文索引命中，但得分较低
+    let mut candidate = testing::create_app(&db, user.id, "m.o.n.i.t.o.r", "https://a.example.com")
+      .await
+      .into_active_model();
+    candidate.description = Set(Some("xdashboardx".into()));
+    candidate.update(&db).await.unwrap();
+    // 名称与两个关键词都模糊匹配，不会被全文索引命中
+    let fuzzy =
+      testing::create_app(&db, user.id, "dashboar-d monito-r", "https://b.example.com").await;
+
+    let query = SearchAppQuery {
+      q: "dashboard monitor".into(),
+      limit: Some(1),
+    };
+    let results = search_app(&db, user.id, &query).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].app.id, fuzzy.id);
+  }
+
+  #[actix_web::test]
+  async fn full_text_search_returns_none_without_index_table() {
+    let db = testing::setup_db().await;
+    let user = testing::create_user(&db, "searcher").await;
+    let app = testing::create_app(&db, user.id, "Grafana", "https://grafana.example.com").await;
+    let tokens = vec!["grafana".to_string()];
+    assert_eq!(
+      full_text_search(&db, &[app.id], &tokens).await,
+      Some(HashSet::from([app.id]))
+    );
+
+    db.execute_unprepared("DROP TABLE apps_fts").await.unwrap();
+    assert_eq!(full_text_search(&db, &[app.id], &tokens).await, None);
+  }
+}
@@ -15,6 +15,30 @@ pub struct UpdateSettingData {
   bg_image: Option<String>,
   #[validate(range(min = 0, max = 20, message = "背景模糊必须为 0 ~ 20 px"))]
   bg_blur: Option<i8>,
+  #[serde(default)]
+  disable_tracking: bool,
+}
+
+impl From<&UpdateSettingData> for users::Setting {
+  fn from(data: &UpdateSettingData) -> Self {
+    users::Setting {
+      theme: data.theme.clone(),
+      bg_image: data.bg_image.clone(),
+      bg_blur: data.bg_blur,
+      disable_tracking: data.disable_tracking,
+    }
+  }
+}
+
+impl From<&users::Setting> for UpdateSettingData {
+  fn from(setting: &users::Setting) -> Self {
+    UpdateSettingData {
+      theme: setting.theme.clone(),
+      bg_image: setting.bg_image.clone(),
+      bg_blur: setting.bg_blur,
+      disable_tracking: setting.disable_tracking,
+    }
+  }
 }
 
 pub async fn update_setting(
@@ -29,13 +53,7 @@ pub async fn update_setting(
     .ok_or(AppError::new(StatusCode::FORBIDDEN, 404, "未找到对应账号"))?
     .into_active_model();
 
-  let setting = users::Setting {
-    theme: data.theme.clone(),
-    bg_image: data.bg_image.clone(),
-    bg_blur: data.bg_blur,
-  };
-
-  user.setting = Set(Some(setting));
+  user.setting = Set(Some(data.into()));
 
   user.update(db).await.map_err(Into::into)
 }
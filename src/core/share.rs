@@ -0,0 +1,203 @@
+use super::{
+  dashboard::{get_default_dashboard, resolve_dashboard},
+  group::check_group_owner,
+};
+use crate::errors::AppError;
+
+use actix_web::http::StatusCode;
+use entity::{
+  apps,
+  shares::{self, SharePermission},
+  users,
+};
+use sea_orm::{
+  entity::Set, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbConn, DeleteResult,
+  EntityTrait, IntoActiveModel, ModelTrait, QueryFilter, QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use utils::deserialize::{option_str_to_i64, str_to_i64};
+
+// 校验当前用户对应用的权限，应用所有者拥有全部权限，其他用户需要有对应的分享授权
+pub async fn check_app_permission<C: ConnectionTrait>(
+  db: &C,
+  operator_id: i64,
+  app_id: i64,
+  required: SharePermission,
+) -> Result<apps::Model, AppError> {
+  let app = apps::Entity::find_by_id(app_id)
+    .filter(apps::Column::IsGlobal.eq(false))
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "应用不存在"))?;
+
+  if app.owner_id == operator_id {
+    return Ok(app);
+  }
+
+  let dashboard_id = match app.dashboard_id {
+    Some(dashboard_id) => dashboard_id,
+    None => get_default_dashboard(db, app.owner_id).await?.id,
+  };
+
+  // 分享整个面板时，面板中的应用都可以访问
+  let board_condition = Condition::all()
+    .add(shares::Column::GroupId.is_null())
+    .add(shares::Column::DashboardId.eq(dashboard_id));
+  let group_condition = match app.group_id {
+    Some(group_id) => Condition::any()
+      .add(board_condition)
+      .add(shares::Column::GroupId.eq(group_id)),
+    None => board_condition,
+  };
+
+  let user_shares = shares::Entity::find()
+    .filter(shares::Column::OwnerId.eq(app.owner_id))
+    .filter(shares::Column::GranteeId.eq(operator_id))
+    .filter(group_condition)
+    .all(db)
+    .await?;
+
+  if user_shares.is_empty() {
+    return Err(AppError::new(StatusCode::NOT_FOUND, 404, "应用不存在"));
+  }
+
+  user_shares
+    .iter()
+    .any(|share| share.permission.allows(required))
+    .then_some(app)
+    .ok_or(AppError::new(StatusCode::FORBIDDEN, 403, "没有权限"))
+}
+
+pub async fn get_user_all_share(
+  db: &DbConn,
+  user_id: i64,
+) -> Result<Vec<shares::Model>, AppError> {
+  shares::Entity::find()
+    .filter(shares::Column::OwnerId.eq(user_id))
+    .order_by_asc(shares::Column::CreatedAt)
+    .all(db)
+    .await
+    .map_err(Into::into)
+}
+
+// 查询分享给当前用户的面板与分组
+pub async fn get_shared_with_user<C: ConnectionTrait>(
+  db: &C,
+  user_id: i64,
+) -> Result<Vec<shares::Model>, AppError> {
+  shares::Entity::find()
+    .filter(shares::Column::GranteeId.eq(user_id))
+    .order_by_asc(shares::Column::CreatedAt)
+    .all(db)
+    .await
+    .map_err(Into::into)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateShareData {
+  #[serde(deserialize_with = "str_to_i64")]
+  grantee_id: i64,
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  group_id: Option<i64>,
+  // 分享整个面板时指定的面板，为空时分享默认面板
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  dashboard_id: Option<i64>,
+  permission: SharePermission,
+}
+
+pub async fn create_share(
+  db: &DbConn,
+  operator_id: i64,
+  data: &CreateShareData,
+) -> Result<shares::Model, AppError> {
+  if data.grantee_id == operator_id {
+    return Err(AppError::new(StatusCode::FORBIDDEN, 403, "不能分享给自己"));
+  }
+
+  users::Entity::find_by_id(data.grantee_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户不存在"))?;
+
+  let dashboard_id = match data.group_id {
+    Some(group_id) => {
+      check_group_owner(db, operator_id, group_id).await?;
+      None
+    }
+    None => Some(resolve_dashboard(db, operator_id, data.dashboard_id).await?.id),
+  };
+
+  let group_condition = match data.group_id {
+    Some(group_id) => Condition::all().add(shares::Column::GroupId.eq(group_id)),
+    None => Condition::all()
+      .add(shares::Column::GroupId.is_null())
+      .add(shares::Column::DashboardId.eq(dashboard_id)),
+  };
+
+  let share = shares::Entity::find()
+    .filter(shares::Column::OwnerId.eq(operator_id))
+    .filter(shares::Column::GranteeId.eq(data.grantee_id))
+    .filter(group_condition)
+    .one(db)
+    .await?;
+
+  if share.is_some() {
+    return Err(AppError::new(StatusCode::CONFLICT, 409, "已经分享给该用户"));
+  }
+
+  shares::ActiveModel {
+    owner_id: Set(operator_id),
+    grantee_id: Set(data.grantee_id),
+    group_id: Set(data.group_id),
+    dashboard_id: Set(dashboard_id),
+    permission: Set(data.permission),
+    ..Default::default()
+  }
+  .insert(db)
+  .await
+  .map_err(Into::into)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateShareData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+  permission: SharePermission,
+}
+
+pub async fn update_share(
+  db: &DbConn,
+  operator_id: i64,
+  data: &UpdateShareData,
+) -> Result<shares::Model, AppError> {
+  let mut share = shares::Entity::find_by_id(data.id)
+    .filter(shares::Column::OwnerId.eq(operator_id))
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "分享不存在"))?
+    .into_active_model();
+
+  share.permission = Set(data.permission);
+
+  share.update(db).await.map_err(Into::into)
+}
+
+// 分享的所有者可以取消分享，被分享的用户也可以退出分享
+pub async fn delete_share(
+  db: &DbConn,
+  operator_id: i64,
+  share_id: i64,
+) -> Result<DeleteResult, AppError> {
+  shares::Entity::find_by_id(share_id)
+    .filter(
+      Condition::any()
+        .add(shares::Column::OwnerId.eq(operator_id))
+        .add(shares::Column::GranteeId.eq(operator_id)),
+    )
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "分享不存在"))?
+    .delete(db)
+    .await
+    .map_err(Into::into)
+}
@@ -0,0 +1,208 @@
+use super::{app::get_user_visible_app, click::record_click};
+use crate::errors::AppError;
+
+use actix_web::http::StatusCode;
+use entity::{apps, users};
+use lazy_static::lazy_static;
+use regex::Regex;
+use sea_orm::{ColumnTrait, Condition, ConnectionTrait, DbConn, EntityTrait, QueryFilter};
+use serde::Serialize;
+use std::{cmp::Ordering, collections::HashSet};
+
+// 最多推荐的相近短链接数量
+const MAX_SUGGESTIONS: usize = 5;
+// 相似度不低于该值时作为推荐
+const SUGGESTION_THRESHOLD: f64 = 0.75;
+
+lazy_static! {
+  // 短链接只能包含小写字母、数字、连字符与下划线，且以字母或数字开头
+  pub static ref SLUG_REGEX: Regex = Regex::new(r"^[a-z0-9][a-z0-9_-]{0,49}$").unwrap();
+}
+
+// 个人应用的短链接在用户自己的应用中唯一，且不能与全站应用重复，全站应用的短链接在全站应用中唯一
+pub(super) async fn check_slug<C: ConnectionTrait>(
+  db: &C,
+  owner_id: i64,
+  slug: Option<&str>,
+  is_global: bool,
+  app_id: Option<i64>,
+) -> Result<(), AppError> {
+  let Some(slug) = slug else {
+    return Ok(());
+  };
+
+  let mut namespace = Condition::any().add(apps::Column::IsGlobal.eq(true));
+  if !is_global {
+    namespace = namespace.add(
+      Condition::all()
+        .add(apps::Column::OwnerId.eq(owner_id))
+        .add(apps::Column::IsGlobal.eq(false)),
+    );
+  }
+
+  let mut select = apps::Entity::find()
+    .filter(apps::Column::Slug.eq(slug))
+    .filter(namespace);
+  if let Some(app_id) = app_id {
+    select = select.filter(apps::Column::Id.ne(app_id));
+  }
+
+  match select.one(db).await? {
+    Some(_) => Err(AppError::new(StatusCode::CONFLICT, 409, "短链接已被使用")),
+    None => Ok(()),
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SlugSuggestion {
+  pub slug: String,
+  pub name: String,
+}
+
+#[derive(Debug)]
+pub enum ShortLink {
+  // 短链接对应的应用地址
+  Found(String),
+  // 未找到时推荐的相近短链接
+  NotFound(Vec<SlugSuggestion>),
+}
+
+fn similarity(slug: &str, other: &str) -> f64 {
+  let score = strsim::jaro_winkler(slug, other);
+  // 输入的是完整短链接的一部分时也作为推荐，如 graf 推荐 grafana
+  match other.contains(slug) || slug.contains(other) {
+    true => score.max(SUGGESTION_THRESHOLD),
+    false => score,
+  }
+}
+
+// 用户自己的应用优先，其次是全站应用，最后是分享给用户的应用
+fn namespace_priority(app: &apps::Model, user_id: i64) -> u8 {
+  match (app.is_global, app.owner_id == user_id) {
+    (false, true) => 0,
+    (true, _) => 1,
+    (false, false) => 2,
+  }
+}
+
+// 依次在用户自己的应用、全站应用与分享给用户的应用中查找短链接
+pub async fn resolve_short_link(
+  db: &DbConn,
+  user_id: i64,
+  slug: &str,
+  internal: bool,
+) -> Result<ShortLink, AppError> {
+  let user = users::Entity::find_by_id(user_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户不存在"))?;
+
+  let slug = slug.trim().to_lowercase();
+  let visible_apps = get_user_visible_app(db, user_id).await?;
+
+  // 不同命名空间中可能存在相同的短链接，按命名空间的优先级选择
+  if let Some(app) = visible_apps
+    .iter()
+    .filter(|app| app.slug.as_deref() == Some(slug.as_str()))
+    .min_by_key(|app| namespace_priority(app, user_id))
+  {
+    record_click(db, &user, app.id, None).await?;
+    return Ok(ShortLink::Found(app.client_url(internal).to_string()));
+  }
+
+  let mut suggestions = visible_apps
+    .into_iter()
+    .filter_map(|app| {
+      let app_slug = app.slug?;
+      let score = similarity(&slug, &app_slug);
+      (score >= SUGGESTION_THRESHOLD).then_some((
+        score,
+        SlugSuggestion {
+          slug: app_slug,
+          name: app.name,
+        },
+      ))
+    })
+    .collect::<Vec<(f64, SlugSuggestion)>>();
+  suggestions.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+  // 不同命名空间中相同的短链接只推荐一次
+  let mut slugs = HashSet::new();
+  Ok(ShortLink::NotFound(
+    suggestions
+      .into_iter()
+      .map(|(_, suggestion)| suggestion)
+      .filter(|suggestion| slugs.insert(suggestion.slug.clone()))
+      .take(MAX_SUGGESTIONS)
+      .collect(),
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::{dashboard::get_default_dashboard, testing};
+  use entity::shares::{self, SharePermission};
+  use sea_orm::{entity::Set, ActiveModelTrait, IntoActiveModel, ModelTrait};
+
+  async fn create_slug_app(db: &DbConn, owner_id: i64, url: &str, is_global: bool) -> apps::Model {
+    let mut app = testing::create_app(db, owner_id, "Wiki", url)
+      .await
+      .into_active_model();
+    app.slug = Set(Some("wiki".into()));
+    app.is_global = Set(is_global);
+    if is_global {
+      app.dashboard_id = Set(None);
+    }
+    app.update(db).await.unwrap()
+  }
+
+  async fn resolve(db: &DbConn, user_id: i64) -> Option<String> {
+    match resolve_short_link(db, user_id, "Wiki", false)
+      .await
+      .unwrap()
+    {
+      ShortLink::Found(url) => Some(url),
+      ShortLink::NotFound(_) => None,
+    }
+  }
+
+  #[actix_web::test]
+  async fn resolve_short_link_prefers_own_then_global_then_shared_apps() {
+    let db = testing::setup_db().await;
+    let user = testing::create_user(&db, "reader").await;
+    let owner = testing::create_user(&db, "sharer").await;
+
+    let dashboard = get_default_dashboard(&db, owner.id).await.unwrap();
+    shares::ActiveModel {
+      owner_id: Set(owner.id),
+      grantee_id: Set(user.id),
+      dashboard_id: Set(Some(dashboard.id)),
+      permission: Set(SharePermission::Read),
+      ..Default::default()
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+
+    // 分享的应用先于其他应用创建，不依赖查询顺序
+    create_slug_app(&db, owner.id, "https://shared.example.com", false).await;
+    let global_app = create_slug_app(&db, 1, "https://global.example.com", true).await;
+    let own_app = create_slug_app(&db, user.id, "https://own.example.com", false).await;
+
+    assert_eq!(
+      resolve(&db, user.id).await.as_deref(),
+      Some("https://own.example.com")
+    );
+    own_app.delete(&db).await.unwrap();
+    assert_eq!(
+      resolve(&db, user.id).await.as_deref(),
+      Some("https://global.example.com")
+    );
+    global_app.delete(&db).await.unwrap();
+    assert_eq!(
+      resolve(&db, user.id).await.as_deref(),
+      Some("https://shared.example.com")
+    );
+  }
+}
@@ -0,0 +1,665 @@
+use super::{
+  maintenance::{get_apps_maintenance, get_enabled_maintenance, is_target, next_window},
+  user::check_admin,
+};
+use crate::{errors::AppError, settings::SETTINGS};
+
+use actix_web::http::StatusCode;
+use chrono::{Duration as ChronoDuration, NaiveDate, NaiveDateTime, Utc};
+use entity::{
+  app_checks::{self, CheckStatus},
+  apps,
+  status_components::{self, ComponentApps},
+  status_incidents::{self, IncidentStatus},
+};
+use lazy_static::lazy_static;
+use sea_orm::{
+  entity::Set, sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition, DbConn, DbErr,
+  EntityTrait, IntoActiveModel, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+  TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+  collections::HashMap,
+  sync::{Arc, RwLock},
+  time::{Duration, Instant},
+};
+use utils::{
+  deserialize::{option_str_to_i64, str_to_i64, vec_str_to_i64},
+  serialize::{i64_to_str, option_i64_to_str},
+};
+use validator::Validate;
+
+// 状态页展示最近多少天的可用率
+pub const STATUS_PAGE_DAYS: i64 = 90;
+// 状态页提前展示多少天内的维护计划
+const UPCOMING_MAINTENANCE_DAYS: i64 = 7;
+
+lazy_static! {
+  static ref STATUS_PAGE_CACHE: RwLock<Option<(Instant, Arc<StatusPage>)>> = RwLock::new(None);
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayUptime {
+  pub date: NaiveDate,
+  // 当天没有检查记录时为空，维护期间的检查不计入
+  pub uptime: Option<f64>,
+  // 当天是否有维护
+  pub maintenance: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusPageApp {
+  pub name: String,
+  pub status: Option<CheckStatus>,
+  pub uptime: Option<f64>,
+  pub days: Vec<DayUptime>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusPageComponent {
+  #[serde(serialize_with = "i64_to_str")]
+  pub id: i64,
+  pub name: String,
+  pub description: Option<String>,
+  pub status: Option<CheckStatus>,
+  pub uptime: Option<f64>,
+  pub apps: Vec<StatusPageApp>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusPageIncident {
+  #[serde(serialize_with = "i64_to_str")]
+  pub id: i64,
+  pub title: String,
+  pub content: String,
+  pub status: IncidentStatus,
+  #[serde(serialize_with = "option_i64_to_str")]
+  pub component_id: Option<i64>,
+  pub started_at: NaiveDateTime,
+  pub resolved_at: Option<NaiveDateTime>,
+  pub updated_at: NaiveDateTime,
+}
+
+impl From<status_incidents::Model> for StatusPageIncident {
+  fn from(incident: status_incidents::Model) -> Self {
+    StatusPageIncident {
+      id: incident.id,
+      title: incident.title,
+      content: incident.content,
+      status: incident.status,
+      component_id: incident.component_id,
+      started_at: incident.started_at,
+      resolved_at: incident.resolved_at,
+      updated_at: incident.updated_at,
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusPageMaintenance {
+  pub title: String,
+  pub description: Option<String>,
+  pub starts_at: NaiveDateTime,
+  pub ends_at: NaiveDateTime,
+  pub active: bool,
+}
+
+// 公开状态页的数据，不包含应用地址等内部信息
+#[derive(Debug, Serialize)]
+pub struct StatusPage {
+  pub title: String,
+  pub status: Option<CheckStatus>,
+  pub components: Vec<StatusPageComponent>,
+  pub incidents: Vec<StatusPageIncident>,
+  // 进行中以及即将开始的维护
+  pub maintenances: Vec<StatusPageMaintenance>,
+  pub generated_at: NaiveDateTime,
+}
+
+// 按异常、维护中、正常的顺序取最严重的状态，全部没有检查记录时为空
+fn worst_status(statuses: impl Iterator<Item = Option<CheckStatus>>) -> Option<CheckStatus> {
+  statuses.flatten().max_by_key(|status| match status {
+    CheckStatus::Up => 0,
+    CheckStatus::Maintenance => 1,
+    CheckStatus::Down => 2,
+  })
+}
+
+fn ratio(up: i64, total: i64) -> Option<f64> {
+  (total > 0).then(|| up as f64 / total as f64)
+}
+
+// 查询状态页数据，数据按配置的时间缓存，避免每次访问都统计检查记录
+pub async fn get_status_page(db: &DbConn) -> Result<Arc<StatusPage>, AppError> {
+  let ttl = Duration::from_secs(SETTINGS.status_page.cache_seconds);
+  let cached = STATUS_PAGE_CACHE.read().ok().and_then(|cache| {
+    cache
+      .as_ref()
+      .filter(|(cached_at, _)| cached_at.elapsed() < ttl)
+      .map(|(_, page)| page.clone())
+  });
+
+  if let Some(page) = cached {
+    return Ok(page);
+  }
+
+  let page = Arc::new(build_status_page(db).await?);
+  if let Ok(mut cache) = STATUS_PAGE_CACHE.write() {
+    *cache = Some((Instant::now(), page.clone()));
+  }
+
+  Ok(page)
+}
+
+// 组件或事件变化后清除缓存，使状态页立即生效
+fn clear_status_page_cache() {
+  if let Ok(mut cache) = STATUS_PAGE_CACHE.write() {
+    *cache = None;
+  }
+}
+
+async fn build_status_page(db: &DbConn) -> Result<StatusPage, DbErr> {
+  let components = status_components::Entity::find()
+    .order_by_asc(status_components::Column::Index)
+    .all(db)
+    .await?;
+
+  let app_ids = components
+    .iter()
+    .flat_map(|component| component.app_ids.0.iter().copied())
+    .collect::<Vec<i64>>();
+
+  let page_apps = apps::Entity::find()
+    .filter(apps::Column::Id.is_in(app_ids.clone()))
+    .all(db)
+    .await?;
+  let apps_maintenance = get_apps_maintenance(db, &page_apps).await?;
+  let apps_name = page_apps
+    .iter()
+    .map(|app| (app.id, app.name.clone()))
+    .collect::<HashMap<i64, String>>();
+
+  // 每个应用最新一条检查记录的状态
+  let latest_ids = app_checks::Entity::find()
+    .select_only()
+    .column_as(app_checks::Column::Id.max(), "id")
+    .filter(app_checks::Column::AppId.is_in(app_ids.clone()))
+    .group_by(app_checks::Column::AppId)
+    .into_tuple::<i64>()
+    .all(db)
+    .await?;
+  let apps_status = app_checks::Entity::find()
+    .filter(app_checks::Column::Id.is_in(latest_ids))
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|check| (check.app_id, check.status))
+    .collect::<HashMap<i64, CheckStatus>>();
+
+  // 按应用、日期与状态统计检查次数，日期按 UTC 计算
+  let today = Utc::now().date_naive();
+  let first_day = today - ChronoDuration::days(STATUS_PAGE_DAYS - 1);
+  let day = Expr::cust("DATE(checked_at)");
+  let mut apps_days = HashMap::<(i64, NaiveDate), (i64, i64, bool)>::new();
+  for (app_id, date, status, count) in app_checks::Entity::find()
+    .select_only()
+    .column(app_checks::Column::AppId)
+    .column_as(day.clone(), "day")
+    .column(app_checks::Column::Status)
+    .column_as(app_checks::Column::Id.count(), "count")
+    .filter(app_checks::Column::AppId.is_in(app_ids))
+    .filter(app_checks::Column::CheckedAt.gte(first_day.and_hms_opt(0, 0, 0).unwrap()))
+    .group_by(app_checks::Column::AppId)
+    .group_by(day)
+    .group_by(app_checks::Column::Status)
+    .into_tuple::<(i64, NaiveDate, CheckStatus, i64)>()
+    .all(db)
+    .await?
+  {
+    let (up, total, maintenance) = apps_days.entry((app_id, date)).or_default();
+    match status {
+      CheckStatus::Up => {
+        *up += count;
+        *total += count;
+      }
+      CheckStatus::Down => *total += count,
+      CheckStatus::Maintenance => *maintenance = true,
+    }
+  }
+
+  let components = components
+    .into_iter()
+    .map(|component| {
+      let (mut component_up, mut component_total) = (0, 0);
+      let apps = component
+        .app_ids
+        .0
+        .iter()
+        .filter_map(|app_id| {
+          let name = apps_name.get(app_id)?.clone();
+          let (mut app_up, mut app_total) = (0, 0);
+          let days = (0..STATUS_PAGE_DAYS)
+            .map(|offset| {
+              let date = first_day + ChronoDuration::days(offset);
+              let (up, total, maintenance) = apps_days
+                .get(&(*app_id, date))
+                .copied()
+                .unwrap_or_default();
+              app_up += up;
+              app_total += total;
+              DayUptime {
+                date,
+                uptime: ratio(up, total),
+                maintenance,
+              }
+            })
+            .collect();
+
+          component_up += app_up;
+          component_total += app_total;
+
+          let status = match apps_maintenance.contains_key(app_id) {
+            true => Some(CheckStatus::Maintenance),
+            false => apps_status.get(app_id).copied(),
+          };
+
+          Some(StatusPageApp {
+            name,
+            status,
+            uptime: ratio(app_up, app_total),
+            days,
+          })
+        })
+        .collect::<Vec<StatusPageApp>>();
+
+      StatusPageComponent {
+        id: component.id,
+        name: component.name,
+        description: component.description,
+        status: worst_status(apps.iter().map(|app| app.status)),
+        uptime: ratio(component_up, component_total),
+        apps,
+      }
+    })
+    .collect::<Vec<StatusPageComponent>>();
+
+  // 展示未解决的事件以及统计时间范围内的事件
+  let incidents = status_incidents::Entity::find()
+    .filter(
+      Condition::any()
+        .add(status_incidents::Column::ResolvedAt.is_null())
+        .add(status_incidents::Column::StartedAt.gte(first_day.and_hms_opt(0, 0, 0).unwrap())),
+    )
+    .order_by_desc(status_incidents::Column::StartedAt)
+    .all(db)
+    .await?
+    .into_iter()
+    .map(Into::into)
+    .collect();
+
+  // 只展示与状态页中的应用相关的维护
+  let now = Utc::now().naive_utc();
+  let upcoming_before = now + ChronoDuration::days(UPCOMING_MAINTENANCE_DAYS);
+  let mut maintenances = get_enabled_maintenance(db)
+    .await?
+    .into_iter()
+    .filter(|maintenance| page_apps.iter().any(|app| is_target(maintenance, app)))
+    .filter_map(|maintenance| {
+      let (starts_at, ends_at) = next_window(&maintenance, now)?;
+      (starts_at <= upcoming_before).then_some(StatusPageMaintenance {
+        title: maintenance.title,
+        description: maintenance.description,
+        starts_at,
+        ends_at,
+        active: starts_at <= now,
+      })
+    })
+    .collect::<Vec<StatusPageMaintenance>>();
+  maintenances.sort_by_key(|maintenance| maintenance.starts_at);
+
+  Ok(StatusPage {
+    title: SETTINGS.status_page.title.clone(),
+    status: worst_status(components.iter().map(|component| component.status)),
+    components,
+    incidents,
+    maintenances,
+    generated_at: now,
+  })
+}
+
+pub async fn get_all_status_component(
+  db: &DbConn,
+  operator_id: i64,
+) -> Result<Vec<status_components::Model>, AppError> {
+  check_admin(db, operator_id).await?;
+
+  status_components::Entity::find()
+    .order_by_asc(status_components::Column::Index)
+    .all(db)
+    .await
+    .map_err(Into::into)
+}
+
+// 状态页中只能展示全站应用与管理员自己的应用
+async fn check_component_apps(
+  db: &DbConn,
+  operator_id: i64,
+  app_ids: &[i64],
+) -> Result<(), AppError> {
+  let count = apps::Entity::find()
+    .filter(apps::Column::Id.is_in(app_ids.to_vec()))
+    .filter(
+      Condition::any()
+        .add(apps::Column::IsGlobal.eq(true))
+        .add(apps::Column::OwnerId.eq(operator_id)),
+    )
+    .count(db)
+    .await?;
+
+  let mut unique_ids = app_ids.to_vec();
+  unique_ids.sort_unstable();
+  unique_ids.dedup();
+
+  if count as usize != unique_ids.len() {
+    return Err(AppError::new(StatusCode::NOT_FOUND, 404, "应用不存在"));
+  }
+
+  Ok(())
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct StatusComponentData {
+  #[validate(length(min = 1, max = 30, message = "组件名称长度不得超过 30 个字符"))]
+  name: String,
+  #[validate(length(min = 1, max = 255, message = "组件描述长度不得超过 255 个字符"))]
+  description: Option<String>,
+  #[serde(default, deserialize_with = "vec_str_to_i64")]
+  #[validate(length(max = 100, message = "组件中的应用不得超过 100 个"))]
+  app_ids: Vec<i64>,
+}
+
+pub async fn create_status_component(
+  db: &DbConn,
+  operator_id: i64,
+  data: &StatusComponentData,
+) -> Result<status_components::Model, AppError> {
+  check_admin(db, operator_id).await?;
+  check_component_apps(db, operator_id, &data.app_ids).await?;
+
+  let index = status_components::Entity::find().count(db).await? as i32;
+
+  let component = status_components::ActiveModel {
+    name: Set(data.name.clone()),
+    description: Set(data.description.clone()),
+    app_ids: Set(ComponentApps(data.app_ids.clone())),
+    index: Set(index),
+    ..Default::default()
+  }
+  .insert(db)
+  .await?;
+
+  clear_status_page_cache();
+
+  Ok(component)
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct UpdateStatusComponentData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+  #[serde(flatten)]
+  #[validate(nested)]
+  data: StatusComponentData,
+}
+
+pub async fn update_status_component(
+  db: &DbConn,
+  operator_id: i64,
+  data: &UpdateStatusComponentData,
+) -> Result<status_components::Model, AppError> {
+  check_admin(db, operator_id).await?;
+
+  let mut component = status_components::Entity::find_by_id(data.id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "组件不存在"))?
+    .into_active_model();
+
+  // 已经在组件中的应用保持不变，只校验新加入的应用
+  let current_ids = component.app_ids.clone().unwrap().0;
+  let added_ids = data
+    .data
+    .app_ids
+    .iter()
+    .copied()
+    .filter(|app_id| !current_ids.contains(app_id))
+    .collect::<Vec<i64>>();
+  check_component_apps(db, operator_id, &added_ids).await?;
+
+  component.name = Set(data.data.name.clone());
+  component.description = Set(data.data.description.clone());
+  component.app_ids = Set(ComponentApps(data.data.app_ids.clone()));
+
+  let component = component.update(db).await?;
+
+  clear_status_page_cache();
+
+  Ok(component)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SortStatusComponentData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+}
+
+pub async fn sort_status_component(
+  db: &DbConn,
+  operator_id: i64,
+  data: &[SortStatusComponentData],
+) -> Result<(), AppError> {
+  check_admin(db, operator_id).await?;
+
+  let sort_component_data = data.to_vec();
+  db.transaction::<_, (), DbErr>(|txn| {
+    Box::pin(async move {
+      for (index, &item) in sort_component_data.iter().enumerate() {
+        let mut component = status_components::Entity::find_by_id(item.id)
+          .one(txn)
+          .await?
+          .ok_or(DbErr::Custom("未查找到对应数据".to_string()))?
+          .into_active_model();
+
+        component.index = Set(index as i32);
+
+        component.update(txn).await?;
+      }
+
+      Ok(())
+    })
+  })
+  .await
+  .map_err(AppError::from_err)?;
+
+  clear_status_page_cache();
+
+  Ok(())
+}
+
+pub async fn delete_status_component(
+  db: &DbConn,
+  operator_id: i64,
+  component_id: i64,
+) -> Result<(), AppError> {
+  check_admin(db, operator_id).await?;
+
+  let component = status_components::Entity::find_by_id(component_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "组件不存在"))?;
+
+  db.transaction::<_, (), DbErr>(|txn| {
+    Box::pin(async move {
+      // 删除组件时，组件的事件改为影响整个站点
+      status_incidents::Entity::update_many()
+        .col_expr(
+          status_incidents::Column::ComponentId,
+          Expr::value(Option::<i64>::None),
+        )
+        .filter(status_incidents::Column::ComponentId.eq(component.id))
+        .exec(txn)
+        .await?;
+
+      component.delete(txn).await?;
+
+      Ok(())
+    })
+  })
+  .await
+  .map_err(AppError::from_err)?;
+
+  clear_status_page_cache();
+
+  Ok(())
+}
+
+pub async fn get_all_status_incident(
+  db: &DbConn,
+  operator_id: i64,
+) -> Result<Vec<status_incidents::Model>, AppError> {
+  check_admin(db, operator_id).await?;
+
+  status_incidents::Entity::find()
+    .order_by_desc(status_incidents::Column::StartedAt)
+    .all(db)
+    .await
+    .map_err(Into::into)
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct StatusIncidentData {
+  #[validate(length(min = 1, max = 100, message = "事件标题长度不得超过 100 个字符"))]
+  title: String,
+  #[validate(length(min = 1, max = 5000, message = "事件说明长度不得超过 5000 个字符"))]
+  content: String,
+  status: IncidentStatus,
+  #[serde(default, deserialize_with = "option_str_to_i64")]
+  component_id: Option<i64>,
+  // 事件开始时间，为空时使用当前时间
+  started_at: Option<NaiveDateTime>,
+}
+
+async fn check_incident_component(db: &DbConn, component_id: Option<i64>) -> Result<(), AppError> {
+  if let Some(component_id) = component_id {
+    status_components::Entity::find_by_id(component_id)
+      .one(db)
+      .await?
+      .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "组件不存在"))?;
+  }
+
+  Ok(())
+}
+
+// 事件标记为已解决时记录解决时间，重新打开时清除
+fn resolved_at(
+  status: IncidentStatus,
+  resolved_at: Option<NaiveDateTime>,
+  now: NaiveDateTime,
+) -> Option<NaiveDateTime> {
+  match status {
+    IncidentStatus::Resolved => resolved_at.or(Some(now)),
+    _ => None,
+  }
+}
+
+pub async fn create_status_incident(
+  db: &DbConn,
+  operator_id: i64,
+  data: &StatusIncidentData,
+) -> Result<status_incidents::Model, AppError> {
+  check_admin(db, operator_id).await?;
+  check_incident_component(db, data.component_id).await?;
+
+  let now = Utc::now().naive_utc();
+  let incident = status_incidents::ActiveModel {
+    title: Set(data.title.clone()),
+    content: Set(data.content.clone()),
+    status: Set(data.status),
+    component_id: Set(data.component_id),
+    started_at: Set(data.started_at.unwrap_or(now)),
+    resolved_at: Set(resolved_at(data.status, None, now)),
+    owner_id: Set(operator_id),
+    created_at: Set(now),
+    updated_at: Set(now),
+    ..Default::default()
+  }
+  .insert(db)
+  .await?;
+
+  clear_status_page_cache();
+
+  Ok(incident)
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct UpdateStatusIncidentData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+  #[serde(flatten)]
+  #[validate(nested)]
+  data: StatusIncidentData,
+}
+
+pub async fn update_status_incident(
+  db: &DbConn,
+  operator_id: i64,
+  data: &UpdateStatusIncidentData,
+) -> Result<status_incidents::Model, AppError> {
+  check_admin(db, operator_id).await?;
+  check_incident_component(db, data.data.component_id).await?;
+
+  let incident = status_incidents::Entity::find_by_id(data.id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "事件不存在"))?;
+
+  let now = Utc::now().naive_utc();
+  let data = &data.data;
+  let started_at = data.started_at.unwrap_or(incident.started_at);
+  let resolved_at = resolved_at(data.status, incident.resolved_at, now);
+  let mut incident = incident.into_active_model();
+
+  incident.title = Set(data.title.clone());
+  incident.content = Set(data.content.clone());
+  incident.status = Set(data.status);
+  incident.component_id = Set(data.component_id);
+  incident.started_at = Set(started_at);
+  incident.resolved_at = Set(resolved_at);
+  incident.updated_at = Set(now);
+
+  let incident = incident.update(db).await?;
+
+  clear_status_page_cache();
+
+  Ok(incident)
+}
+
+pub async fn delete_status_incident(
+  db: &DbConn,
+  operator_id: i64,
+  incident_id: i64,
+) -> Result<(), AppError> {
+  check_admin(db, operator_id).await?;
+
+  status_incidents::Entity::find_by_id(incident_id)
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "事件不存在"))?
+    .delete(db)
+    .await?;
+
+  clear_status_page_cache();
+
+  Ok(())
+}
@@ -0,0 +1,221 @@
+use crate::errors::AppError;
+
+use actix_web::http::StatusCode;
+use entity::{app_tags, tags};
+use sea_orm::{
+  entity::Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, DbErr, EntityTrait,
+  IntoActiveModel, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utils::deserialize::str_to_i64;
+use validator::Validate;
+
+#[derive(Debug, Serialize)]
+pub struct TagCount {
+  #[serde(flatten)]
+  pub tag: tags::Model,
+  // 使用该标签的应用数量
+  pub count: usize,
+}
+
+pub async fn get_user_all_tag(db: &DbConn, user_id: i64) -> Result<Vec<TagCount>, AppError> {
+  let user_tags = tags::Entity::find()
+    .filter(tags::Column::OwnerId.eq(user_id))
+    .order_by_asc(tags::Column::Name)
+    .all(db)
+    .await?;
+
+  let tag_ids = user_tags.iter().map(|tag| tag.id).collect::<Vec<i64>>();
+  let app_tag_list = app_tags::Entity::find()
+    .filter(app_tags::Column::TagId.is_in(tag_ids))
+    .all(db)
+    .await?;
+
+  let mut counts = HashMap::<i64, usize>::new();
+  for app_tag in app_tag_list {
+    *counts.entry(app_tag.tag_id).or_default() += 1;
+  }
+
+  Ok(
+    user_tags
+      .into_iter()
+      .map(|tag| TagCount {
+        count: counts.get(&tag.id).copied().unwrap_or(0),
+        tag,
+      })
+      .collect(),
+  )
+}
+
+// 查询应用对应的标签，返回应用 id 到标签列表的映射
+pub async fn get_apps_tags<C: ConnectionTrait>(
+  db: &C,
+  app_ids: Vec<i64>,
+) -> Result<HashMap<i64, Vec<tags::Model>>, AppError> {
+  let app_tag_list = app_tags::Entity::find()
+    .filter(app_tags::Column::AppId.is_in(app_ids))
+    .all(db)
+    .await?;
+
+  let tag_ids = app_tag_list
+    .iter()
+    .map(|app_tag| app_tag.tag_id)
+    .collect::<HashSet<i64>>();
+  let tag_list = tags::Entity::find()
+    .filter(tags::Column::Id.is_in(tag_ids))
+    .order_by_asc(tags::Column::Name)
+    .all(db)
+    .await?;
+
+  let mut apps_tags = HashMap::<i64, Vec<tags::Model>>::new();
+  for tag in tag_list {
+    for app_tag in app_tag_list.iter().filter(|item| item.tag_id == tag.id) {
+      apps_tags
+        .entry(app_tag.app_id)
+        .or_default()
+        .push(tag.clone());
+    }
+  }
+
+  Ok(apps_tags)
+}
+
+// 替换应用的全部标签，标签必须属于当前用户
+pub async fn set_app_tags<C: ConnectionTrait>(
+  db: &C,
+  operator_id: i64,
+  app_id: i64,
+  tag_ids: &[i64],
+) -> Result<(), AppError> {
+  let tag_ids = tag_ids.iter().copied().collect::<HashSet<i64>>();
+
+  let tag_count = tags::Entity::find()
+    .filter(tags::Column::Id.is_in(tag_ids.clone()))
+    .filter(tags::Column::OwnerId.eq(operator_id))
+    .count(db)
+    .await?;
+
+  if tag_count != tag_ids.len() as u64 {
+    return Err(AppError::new(StatusCode::NOT_FOUND, 404, "标签不存在"));
+  }
+
+  app_tags::Entity::delete_many()
+    .filter(app_tags::Column::AppId.eq(app_id))
+    .exec(db)
+    .await?;
+
+  if tag_ids.is_empty() {
+    return Ok(());
+  }
+
+  app_tags::Entity::insert_many(tag_ids.into_iter().map(|tag_id| app_tags::ActiveModel {
+    app_id: Set(app_id),
+    tag_id: Set(tag_id),
+  }))
+  .exec(db)
+  .await?;
+
+  Ok(())
+}
+
+async fn check_tag_name(
+  db: &DbConn,
+  operator_id: i64,
+  name: &String,
+  exclude_id: Option<i64>,
+) -> Result<(), AppError> {
+  let mut select = tags::Entity::find()
+    .filter(tags::Column::OwnerId.eq(operator_id))
+    .filter(tags::Column::Name.eq(name));
+
+  if let Some(exclude_id) = exclude_id {
+    select = select.filter(tags::Column::Id.ne(exclude_id));
+  }
+
+  match select.one(db).await? {
+    Some(_) => Err(AppError::new(StatusCode::CONFLICT, 409, "标签已经存在")),
+    None => Ok(()),
+  }
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct CreateTagData {
+  #[validate(length(min = 1, max = 20, message = "标签名称长度不得超过 20 个字符"))]
+  name: String,
+  #[validate(length(min = 1, max = 20, message = "标签颜色长度不得超过 20 个字符"))]
+  color: Option<String>,
+}
+
+pub async fn create_tag(
+  db: &DbConn,
+  operator_id: i64,
+  data: &CreateTagData,
+) -> Result<tags::Model, AppError> {
+  check_tag_name(db, operator_id, &data.name, None).await?;
+
+  tags::ActiveModel {
+    name: Set(data.name.clone()),
+    color: Set(data.color.clone()),
+    owner_id: Set(operator_id),
+    ..Default::default()
+  }
+  .insert(db)
+  .await
+  .map_err(Into::into)
+}
+
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct UpdateTagData {
+  #[serde(deserialize_with = "str_to_i64")]
+  id: i64,
+  #[validate(length(min = 1, max = 20, message = "标签名称长度不得超过 20 个字符"))]
+  name: String,
+  #[validate(length(min = 1, max = 20, message = "标签颜色长度不得超过 20 个字符"))]
+  color: Option<String>,
+}
+
+pub async fn update_tag(
+  db: &DbConn,
+  operator_id: i64,
+  data: &UpdateTagData,
+) -> Result<tags::Model, AppError> {
+  let mut tag = tags::Entity::find_by_id(data.id)
+    .filter(tags::Column::OwnerId.eq(operator_id))
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "标签不存在"))?
+    .into_active_model();
+
+  check_tag_name(db, operator_id, &data.name, Some(data.id)).await?;
+
+  tag.name = Set(data.name.clone());
+  tag.color = Set(data.color.clone());
+
+  tag.update(db).await.map_err(Into::into)
+}
+
+pub async fn delete_tag(db: &DbConn, operator_id: i64, tag_id: i64) -> Result<(), AppError> {
+  let tag = tags::Entity::find_by_id(tag_id)
+    .filter(tags::Column::OwnerId.eq(operator_id))
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "标签不存在"))?;
+
+  db.transaction::<_, (), DbErr>(|txn| {
+    Box::pin(async move {
+      app_tags::Entity::delete_many()
+        .filter(app_tags::Column::TagId.eq(tag.id))
+        .exec(txn)
+        .await?;
+
+      tag.delete(txn).await?;
+
+      Ok(())
+    })
+  })
+  .await
+  .map_err(AppError::from_err)?;
+
+  Ok(())
+}
@@ -0,0 +1,52 @@
+use super::dashboard::{create_default_dashboard, get_default_dashboard};
+
+use entity::{apps, users};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{entity::Set, ActiveModelTrait, ConnectOptions, Database, DbConn};
+
+// 内存数据库只在创建它的连接中可见，连接池只保留一个连接
+pub async fn setup_db() -> DbConn {
+  let mut connect_options = ConnectOptions::new("sqlite::memory:");
+  connect_options
+    .max_connections(1)
+    .min_connections(1)
+    .sqlx_logging(false);
+
+  let db = Database::connect(connect_options).await.unwrap();
+  Migrator::up(&db, None).await.unwrap();
+
+  db
+}
+
+pub async fn create_user(db: &DbConn, username: &str) -> users::Model {
+  let user = users::ActiveModel {
+    username: Set(username.to_string()),
+    password: Set(String::new()),
+    is_admin: Set(false),
+    ..Default::default()
+  }
+  .insert(db)
+  .await
+  .unwrap();
+  create_default_dashboard(db, user.id).await.unwrap();
+
+  user
+}
+
+// 在用户的默认面板中创建应用
+pub async fn create_app(db: &DbConn, owner_id: i64, name: &str, url: &str) -> apps::Model {
+  let dashboard = get_default_dashboard(db, owner_id).await.unwrap();
+
+  apps::ActiveModel {
+    name: Set(name.to_string()),
+    url: Set(url.to_string()),
+    index: Set(0),
+    owner_id: Set(owner_id),
+    is_global: Set(false),
+    dashboard_id: Set(Some(dashboard.id)),
+    ..Default::default()
+  }
+  .insert(db)
+  .await
+  .unwrap()
+}
@@ -1,12 +1,17 @@
+use super::{app::delete_apps, dashboard::create_default_dashboard};
 use crate::errors::AppError;
 
 use actix_web::http::StatusCode;
-use entity::{apps, users};
+use entity::{
+  app_clicks, app_prefs, app_tags, apps, dashboards, groups, maintenances, notifications, shares,
+  tags, users,
+};
 use lazy_static::lazy_static;
 use regex::Regex;
 use sea_orm::{
-  entity::Set, ActiveModelTrait, ColumnTrait, DbConn, DbErr, DeleteResult, EntityTrait,
+  entity::Set, ActiveModelTrait, ColumnTrait, Condition, DbConn, DbErr, DeleteResult, EntityTrait,
   IntoActiveModel, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder, RuntimeErr::SqlxError,
+  TransactionTrait,
 };
 use serde::{Deserialize, Serialize};
 use utils::crypto;
@@ -24,6 +29,15 @@ pub async fn get_user_info(db: &DbConn, id: i64) -> Result<users::Model, AppErro
     .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户信息不存在"))
 }
 
+// 校验当前用户是否为管理员
+pub async fn check_admin(db: &DbConn, operator_id: i64) -> Result<users::Model, AppError> {
+  users::Entity::find_by_id(operator_id)
+    .filter(users::Column::IsAdmin.eq(true))
+    .one(db)
+    .await?
+    .ok_or(AppError::new(StatusCode::FORBIDDEN, 403, "没有权限"))
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GetUserListQuery {
   page: Option<u64>,
@@ -41,11 +55,7 @@ pub async fn get_user_list(
   operator_id: i64,
   data: &GetUserListQuery,
 ) -> Result<GetUserListResp, AppError> {
-  users::Entity::find_by_id(operator_id)
-    .filter(users::Column::IsAdmin.eq(true))
-    .one(db)
-    .await?
-    .ok_or(AppError::new(StatusCode::FORBIDDEN, 403, "没有权限"))?;
+  check_admin(db, operator_id).await?;
 
   let user_pages = users::Entity::find()
     .order_by_asc(users::Column::CreatedAt)
@@ -86,22 +96,20 @@ pub async fn create_user(
   operator_id: i64,
   data: &CreateUserData,
 ) -> Result<users::Model, AppError> {
-  users::Entity::find_by_id(operator_id)
-    .filter(users::Column::IsAdmin.eq(true))
-    .one(db)
-    .await?
-    .ok_or(AppError::new(StatusCode::FORBIDDEN, 403, "没有权限"))?;
+  check_admin(db, operator_id).await?;
 
   let password = crypto::hash(&data.password).map_err(AppError::from_err)?;
 
-  users::ActiveModel {
+  let txn = db.begin().await?;
+
+  let user = users::ActiveModel {
     username: Set(data.username.clone()),
     password: Set(password),
     avatar: Set(data.avatar.clone()),
     is_admin: Set(false),
     ..Default::default()
   }
-  .insert(db)
+  .insert(&txn)
   .await
   .map_err(|err| match err {
     DbErr::Query(SqlxError(_)) => {
@@ -109,7 +117,12 @@ pub async fn create_user(
     }
     DbErr::Exec(SqlxError(_)) => AppError::new(StatusCode::CONFLICT, 409, "用户名已经被注册"),
     e => e.into(),
-  })
+  })?;
+  create_default_dashboard(&txn, user.id).await?;
+
+  txn.commit().await?;
+
+  Ok(user)
 }
 
 #[derive(Debug, Validate, Serialize, Deserialize)]
@@ -155,22 +168,88 @@ pub async fn delete_user(
     return Err(AppError::new(StatusCode::FORBIDDEN, 403, "不能删除自己"));
   }
 
-  users::Entity::find_by_id(operator_id)
-    .filter(users::Column::IsAdmin.eq(true))
+  check_admin(db, operator_id).await?;
+
+  let user = users::Entity::find_by_id(user_id)
     .one(db)
     .await?
-    .ok_or(AppError::new(StatusCode::FORBIDDEN, 403, "没有权限"))?;
+    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户不存在"))?;
 
-  users::Entity::find_by_id(user_id)
-    .one(db)
+  let txn = db.begin().await?;
+
+  user.delete(&txn).await?;
+
+  dashboards::Entity::delete_many()
+    .filter(dashboards::Column::OwnerId.eq(user_id))
+    .exec(&txn)
+    .await?;
+
+  groups::Entity::delete_many()
+    .filter(groups::Column::OwnerId.eq(user_id))
+    .exec(&txn)
+    .await?;
+
+  let tag_ids = tags::Entity::find()
+    .filter(tags::Column::OwnerId.eq(user_id))
+    .all(&txn)
     .await?
-    .ok_or(AppError::new(StatusCode::NOT_FOUND, 404, "用户不存在"))?
-    .delete(db)
+    .into_iter()
+    .map(|tag| tag.id)
+    .collect::<Vec<i64>>();
+
+  app_tags::Entity::delete_many()
+    .filter(app_tags::Column::TagId.is_in(tag_ids))
+    .exec(&txn)
+    .await?;
+
+  tags::Entity::delete_many()
+    .filter(tags::Column::OwnerId.eq(user_id))
+    .exec(&txn)
     .await?;
 
-  apps::Entity::delete_many()
+  shares::Entity::delete_many()
+    .filter(
+      Condition::any()
+        .add(shares::Column::OwnerId.eq(user_id))
+        .add(shares::Column::GranteeId.eq(user_id)),
+    )
+    .exec(&txn)
+    .await?;
+
+  app_prefs::Entity::delete_many()
+    .filter(app_prefs::Column::UserId.eq(user_id))
+    .exec(&txn)
+    .await?;
+
+  app_clicks::Entity::delete_many()
+    .filter(app_clicks::Column::UserId.eq(user_id))
+    .exec(&txn)
+    .await?;
+
+  notifications::Entity::delete_many()
+    .filter(notifications::Column::OwnerId.eq(user_id))
+    .filter(notifications::Column::IsGlobal.eq(false))
+    .exec(&txn)
+    .await?;
+
+  maintenances::Entity::delete_many()
+    .filter(maintenances::Column::OwnerId.eq(user_id))
+    .exec(&txn)
+    .await?;
+
+  // 全站应用不随创建者一起删除
+  let app_ids = apps::Entity::find()
     .filter(apps::Column::OwnerId.eq(user_id))
-    .exec(db)
-    .await
-    .map_err(Into::into)
+    .filter(apps::Column::IsGlobal.eq(false))
+    .all(&txn)
+    .await?
+    .into_iter()
+    .map(|app| app.id)
+    .collect::<Vec<i64>>();
+
+  let result = delete_apps(&txn, app_ids).await?;
+
+  txn.commit().await?;
+
+  Ok(result)
 }
@@ -3,6 +3,7 @@ use actix_web::{body::BoxBody, http::StatusCode, HttpResponse, ResponseError};
 use reqwest;
 use sea_orm::DbErr;
 use serde::Serialize;
+use serde_json::Value;
 use std::{error::Error, fmt, num::ParseIntError, result, string::FromUtf8Error};
 use validator::ValidationErrors;
 
@@ -13,6 +14,8 @@ pub struct AppError {
   pub status: StatusCode,
   pub code: u16,
   pub message: String,
+  // 便于客户端处理的结构化错误信息
+  pub details: Option<Value>,
 }
 
 impl fmt::Display for AppError {
@@ -26,6 +29,8 @@ impl fmt::Display for AppError {
 pub struct AppErrorJson {
   pub code: u16,
   pub message: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub details: Option<Value>,
 }
 
 impl AppError {
@@ -34,9 +39,15 @@ impl AppError {
       status,
       code,
       message: message.to_string(),
+      details: None,
     }
   }
 
+  pub fn with_details<D: Serialize>(mut self, details: D) -> Self {
+    self.details = serde_json::to_value(details).ok();
+    self
+  }
+
   pub fn from_err<E: Error>(err: E) -> Self {
     AppError::new(StatusCode::INTERNAL_SERVER_ERROR, 500, err.to_string())
   }
@@ -51,6 +62,7 @@ impl ResponseError for AppError {
     HttpResponse::build(self.status_code()).json(AppErrorJson {
       code: self.code,
       message: self.message.clone(),
+      details: self.details.clone(),
     })
   }
 }
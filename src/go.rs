@@ -0,0 +1,96 @@
+use crate::{
+  core::{
+    network::{self, NetworkMode},
+    short_link::{self, ShortLink, SlugSuggestion},
+  },
+  errors::Result,
+};
+
+use actix_identity::Identity;
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Responder};
+use sea_orm::DbConn;
+use serde::Deserialize;
+use std::fmt::Write;
+use utils::html::escape;
+
+fn render_not_found(slug: &str, suggestions: &[SlugSuggestion]) -> String {
+  let mut items = String::new();
+  for suggestion in suggestions {
+    let _ = write!(
+      items,
+      r#"<li><a href="/go/{slug}">/go/{slug}</a> <span class="muted">{name}</span></li>"#,
+      slug = escape(&suggestion.slug),
+      name = escape(&suggestion.name),
+    );
+  }
+  let suggestions = match items.is_empty() {
+    true => String::new(),
+    false => format!("<p>你是不是要找：</p><ul>{}</ul>", items),
+  };
+
+  format!(
+    r#"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>短链接不存在</title>
+<style>
+body {{ margin: 0; background: #f5f6f8; color: #1f2329; font: 14px/1.6 -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; }}
+main {{ max-width: 560px; margin: 64px auto; padding: 24px; background: #fff; border-radius: 8px; }}
+h1 {{ font-size: 20px; margin-top: 0; }}
+a {{ color: #0052d9; }}
+.muted {{ color: #8b8f97; }}
+</style>
+</head>
+<body>
+<main>
+<h1>短链接 /go/{slug} 不存在</h1>
+{suggestions}
+<p><a href="/">返回首页</a></p>
+</main>
+</body>
+</html>"#,
+    slug = escape(slug),
+    suggestions = suggestions,
+  )
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RedirectQuery {
+  // 为空时按客户端地址选择应用的内网或外网地址
+  network: Option<NetworkMode>,
+}
+
+// 未登录时跳转到登录页，短链接不存在时返回相近的短链接
+pub async fn redirect(
+  req: HttpRequest,
+  identity: Option<Identity>,
+  db: web::Data<DbConn>,
+  slug: web::Path<String>,
+  query: web::Query<RedirectQuery>,
+) -> Result<impl Responder> {
+  let Some(identity) = identity else {
+    return Ok(
+      HttpResponse::Found()
+        .insert_header((header::LOCATION, "/login"))
+        .finish(),
+    );
+  };
+  let user_id = identity.id().map(|id| id.parse::<i64>())??;
+
+  let internal = network::is_internal(query.network, network::client_ip(&req));
+
+  match short_link::resolve_short_link(&db, user_id, &slug, internal).await? {
+    ShortLink::Found(url) => Ok(
+      HttpResponse::Found()
+        .insert_header((header::LOCATION, url))
+        .finish(),
+    ),
+    ShortLink::NotFound(suggestions) => Ok(
+      HttpResponse::NotFound()
+        .content_type(header::ContentType::html())
+        .body(render_not_found(&slug, &suggestions)),
+    ),
+  }
+}
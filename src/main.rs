@@ -1,8 +1,11 @@
 pub mod api;
 pub mod asset;
+pub mod badge;
 pub mod core;
 pub mod errors;
+pub mod go;
 pub mod settings;
+pub mod status;
 
 use actix_files::Files;
 use actix_identity::IdentityMiddleware;
@@ -20,6 +23,21 @@ use sea_orm::{ConnectOptions, Database};
 use settings::SETTINGS;
 use std::{io, time::Duration};
 
+fn identity_middleware() -> IdentityMiddleware {
+  IdentityMiddleware::builder()
+    // 用户不活动超过一周，则清除登录状态
+    .visit_deadline(Some(Duration::from_secs(60 * 60 * 24 * 7)))
+    .build()
+}
+
+fn session_middleware() -> SessionMiddleware<CookieSessionStore> {
+  SessionMiddleware::builder(CookieSessionStore::default(), Key::from(&[0; 64]))
+    .cookie_secure(false)
+    .session_lifecycle(PersistentSession::default().session_ttl(time::Duration::days(14)))
+    .cookie_name(String::from("session"))
+    .build()
+}
+
 #[actix_web::main]
 async fn main() -> Result<(), io::Error> {
   dotenv().ok();
@@ -45,6 +63,10 @@ async fn main() -> Result<(), io::Error> {
     .await
     .expect("Database migrate failed");
 
+  if SETTINGS.monitor.enabled {
+    actix_web::rt::spawn(core::monitor::run(db.clone()));
+  }
+
   log::info!("starting HTTP server at http://0.0.0.0:{}", SETTINGS.port);
 
   HttpServer::new(move || {
@@ -59,21 +81,35 @@ async fn main() -> Result<(), io::Error> {
           }))
           .app_data(web::Data::new(db.clone()))
           .wrap(Logger::default())
-          .wrap(
-            IdentityMiddleware::builder()
-              // 用户不活动超过一周，则清除登录状态
-              .visit_deadline(Some(Duration::from_secs(60 * 60 * 24 * 7)))
-              .build(),
-          )
-          .wrap(
-            SessionMiddleware::builder(CookieSessionStore::default(), Key::from(&[0; 64]))
-              .cookie_secure(false)
-              .session_lifecycle(PersistentSession::default().session_ttl(time::Duration::days(14)))
-              .cookie_name(String::from("session"))
-              .build(),
-          )
+          .wrap(identity_middleware())
+          .wrap(session_middleware())
           .configure(api::init),
       )
+      .service(
+        web::scope("/go")
+          .app_data(web::Data::new(db.clone()))
+          .wrap(identity_middleware())
+          .wrap(session_middleware())
+          .route("/{slug}", web::get().to(go::redirect)),
+      )
+      .service(
+        web::scope("/badge/{badge_token}")
+          .app_data(web::Data::new(db.clone()))
+          .route("/status.svg", web::get().to(badge::status))
+          .route("/uptime.svg", web::get().to(badge::uptime))
+          .route("/latency.svg", web::get().to(badge::latency)),
+      )
+      .configure(|cfg| {
+        // 公开状态页不需要登录
+        if SETTINGS.status_page.enabled {
+          cfg.service(
+            web::scope(&SETTINGS.status_page.path)
+              .app_data(web::Data::new(db.clone()))
+              .route("", web::get().to(status::page))
+              .route("/data", web::get().to(status::data)),
+          );
+        }
+      })
       .service(Files::new("/files", SETTINGS.files_dir.clone()))
       .default_service(web::to(serve))
   })
@@ -1,10 +1,12 @@
 use anyhow::{Ok, Result};
 use config::Config;
+use ipnet::IpNet;
 use lazy_static::lazy_static;
 use serde::Deserialize;
 use std::{
   env,
   fs::{self, OpenOptions},
+  net::IpAddr,
   path::PathBuf,
 };
 
@@ -35,11 +37,180 @@ impl Default for Database {
   }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Monitor {
+  // 是否开启应用健康检查
+  pub enabled: bool,
+  // 检查间隔，单位秒
+  pub interval: u64,
+  // 请求超时时间，单位秒
+  pub timeout: u64,
+  // 视为正常的状态码，为空时 2xx 与 3xx 都视为正常
+  pub expected_status: Vec<u16>,
+  // 是否校验 TLS 证书
+  pub verify_tls: bool,
+  // 检查记录保留天数
+  pub retention_days: i64,
+  // 证书在多少天内过期时提示
+  pub cert_expiry_days: i64,
+  // 连续失败多少次后发送通知
+  pub notify_after: u32,
+  // 同一应用两次通知的最小间隔，单位秒，间隔内的状态抖动不会发送通知
+  pub notify_interval: i64,
+}
+
+impl Default for Monitor {
+  fn default() -> Self {
+    Monitor {
+      enabled: true,
+      interval: 60,
+      timeout: 10,
+      expected_status: Vec::new(),
+      verify_tls: true,
+      retention_days: 90,
+      cert_expiry_days: 14,
+      notify_after: 1,
+      notify_interval: 300,
+    }
+  }
+}
+
+impl Monitor {
+  // 检查间隔、超时时间与保留天数必须大于 0，否则使用默认值
+  fn normalize(mut self) -> Self {
+    let default = Monitor::default();
+    if self.interval == 0 {
+      log::error!("Monitor interval must be positive, use default interval");
+      self.interval = default.interval;
+    }
+    if self.timeout == 0 {
+      log::error!("Monitor timeout must be positive, use default timeout");
+      self.timeout = default.timeout;
+    }
+    if self.retention_days <= 0 {
+      log::error!("Monitor retention days must be positive, use default retention days");
+      self.retention_days = default.retention_days;
+    }
+
+    self
+  }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct StatusPage {
+  // 是否开启公开状态页
+  pub enabled: bool,
+  // 状态页访问路径
+  pub path: String,
+  // 状态页标题
+  pub title: String,
+  // 状态页数据缓存时间，单位秒
+  pub cache_seconds: u64,
+}
+
+impl Default for StatusPage {
+  fn default() -> Self {
+    StatusPage {
+      enabled: false,
+      path: String::from("/status"),
+      title: String::from("服务状态"),
+      cache_seconds: 60,
+    }
+  }
+}
+
+impl StatusPage {
+  // 状态页路径必须以 / 开头，且不能与接口、文件等路径冲突
+  fn normalize(mut self) -> Self {
+    let path = format!("/{}", self.path.trim_matches('/'));
+    let reserved = ["/", "/api", "/files", "/login", "/go", "/badge"];
+    let conflicted = reserved.contains(&path.as_str())
+      || ["/api/", "/files/", "/go/", "/badge/"]
+        .iter()
+        .any(|prefix| path.starts_with(prefix));
+
+    if conflicted {
+      log::error!("Status page path {} is reserved, use default path", path);
+      self.path = StatusPage::default().path;
+    } else {
+      self.path = path;
+    }
+
+    self
+  }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Network {
+  // 内网地址段，客户端地址在其中时打开应用的内网地址
+  pub internal_cidrs: Vec<String>,
+  // 可信的反向代理地址段，只有来自这些地址的请求才使用 X-Forwarded-For 中的客户端地址
+  pub trusted_proxies: Vec<String>,
+  #[serde(skip)]
+  pub internal_networks: Vec<IpNet>,
+  #[serde(skip)]
+  pub proxy_networks: Vec<IpNet>,
+}
+
+impl Default for Network {
+  fn default() -> Self {
+    Network {
+      internal_cidrs: [
+        "127.0.0.0/8",
+        "10.0.0.0/8",
+        "172.16.0.0/12",
+        "192.168.0.0/16",
+        "::1/128",
+        "fc00::/7",
+      ]
+      .into_iter()
+      .map(String::from)
+      .collect(),
+      trusted_proxies: Vec::new(),
+      internal_networks: Vec::new(),
+      proxy_networks: Vec::new(),
+    }
+  }
+}
+
+impl Network {
+  // 解析地址段，单个地址视为只包含该地址的地址段，无法解析的地址段忽略
+  fn parse_cidrs(cidrs: &[String]) -> Vec<IpNet> {
+    cidrs
+      .iter()
+      .filter_map(|cidr| {
+        let network = cidr
+          .parse::<IpNet>()
+          .or_else(|_| cidr.parse::<IpAddr>().map(IpNet::from));
+        if network.is_err() {
+          log::error!("Invalid network {}, ignored", cidr);
+        }
+        network.ok()
+      })
+      .collect()
+  }
+
+  fn normalize(mut self) -> Self {
+    self.internal_networks = Network::parse_cidrs(&self.internal_cidrs);
+    self.proxy_networks = Network::parse_cidrs(&self.trusted_proxies);
+    self
+  }
+}
+
 #[derive(Debug)]
 pub struct Settings {
   pub port: u16,
   // 数据库地址
   pub database: Database,
+  // 应用健康检查配置
+  pub monitor: Monitor,
+  // 公开状态页配置
+  pub status_page: StatusPage,
+  // 客户端网络配置
+  pub network: Network,
   pub data_dir: PathBuf,
   pub files_dir: PathBuf,
 }
@@ -57,9 +228,27 @@ impl Settings {
       .get::<Database>("database")
       .unwrap_or(Database::default());
 
+    let monitor = config
+      .get::<Monitor>("monitor")
+      .unwrap_or_default()
+      .normalize();
+
+    let status_page = config
+      .get::<StatusPage>("status_page")
+      .unwrap_or_default()
+      .normalize();
+
+    let network = config
+      .get::<Network>("network")
+      .unwrap_or_default()
+      .normalize();
+
     let settings = Settings {
       port,
       database,
+      monitor,
+      status_page,
+      network,
       data_dir: DATA_DIR.to_path_buf(),
       files_dir: DATA_DIR.join("files"),
     };
@@ -83,6 +272,7 @@ impl Settings {
         OpenOptions::new()
           .write(true)
           .create(true)
+          .truncate(false)
           .open(database_path)
           .ok()
       });
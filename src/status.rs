@@ -0,0 +1,222 @@
+use crate::{
+  core::status_page::{get_status_page, DayUptime, StatusPage},
+  errors::Result,
+};
+
+use actix_web::{http::header, web, HttpResponse, Responder};
+use chrono::NaiveDateTime;
+use entity::{app_checks::CheckStatus, status_incidents::IncidentStatus};
+use sea_orm::DbConn;
+use std::fmt::Write;
+use utils::html::escape;
+
+fn status_text(status: Option<CheckStatus>) -> (&'static str, &'static str) {
+  match status {
+    Some(CheckStatus::Up) => ("up", "正常"),
+    Some(CheckStatus::Down) => ("down", "异常"),
+    Some(CheckStatus::Maintenance) => ("maintenance", "维护中"),
+    None => ("none", "暂无数据"),
+  }
+}
+
+fn incident_status_text(status: IncidentStatus) -> &'static str {
+  match status {
+    IncidentStatus::Investigating => "调查中",
+    IncidentStatus::Identified => "已确认",
+    IncidentStatus::Monitoring => "观察中",
+    IncidentStatus::Resolved => "已解决",
+  }
+}
+
+fn uptime_text(uptime: Option<f64>) -> String {
+  uptime
+    .map(|uptime| format!("{:.2}%", uptime * 100.0))
+    .unwrap_or_else(|| "-".to_string())
+}
+
+fn time_text(time: NaiveDateTime) -> String {
+  time.format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+// 可用率不低于 99.9% 显示为绿色，不低于 95% 显示为黄色，其余显示为红色
+fn day_level(day: &DayUptime) -> &'static str {
+  match day.uptime {
+    None if day.maintenance => "maintenance",
+    None => "none",
+    Some(uptime) if uptime >= 0.999 => "up",
+    Some(uptime) if uptime >= 0.95 => "degraded",
+    Some(_) => "down",
+  }
+}
+
+fn render(page: &StatusPage) -> String {
+  let (overall_class, overall_text) = match page.status {
+    Some(CheckStatus::Up) => ("up", "所有服务运行正常"),
+    Some(CheckStatus::Down) => ("down", "部分服务出现异常"),
+    Some(CheckStatus::Maintenance) => ("maintenance", "部分服务正在维护"),
+    None => ("none", "暂无检查数据"),
+  };
+
+  let mut maintenances = String::new();
+  for maintenance in &page.maintenances {
+    let description = maintenance
+      .description
+      .as_ref()
+      .map(|description| format!("<p>{}</p>", escape(description)))
+      .unwrap_or_default();
+    let _ = write!(
+      maintenances,
+      r#"<div class="banner"><strong>{}{}</strong>{}<p class="muted">{} ~ {}</p></div>"#,
+      if maintenance.active { "[维护中] " } else { "[计划维护] " },
+      escape(&maintenance.title),
+      description,
+      time_text(maintenance.starts_at),
+      time_text(maintenance.ends_at),
+    );
+  }
+
+  let mut components = String::new();
+  for component in &page.components {
+    let (class, text) = status_text(component.status);
+    let _ = write!(
+      components,
+      r#"<section class="component"><header><h2>{}</h2><span class="status {}">{}</span></header>"#,
+      escape(&component.name),
+      class,
+      text,
+    );
+    if let Some(description) = &component.description {
+      let _ = write!(components, "<p class=\"muted\">{}</p>", escape(description));
+    }
+
+    for app in &component.apps {
+      let (class, text) = status_text(app.status);
+      let _ = write!(
+        components,
+        r#"<div class="app"><div class="row"><span>{}</span><span class="status {}">{}</span></div><div class="bars">"#,
+        escape(&app.name),
+        class,
+        text,
+      );
+      for day in &app.days {
+        let _ = write!(
+          components,
+          r#"<i class="{}" title="{} {}"></i>"#,
+          day_level(day),
+          day.date,
+          uptime_text(day.uptime),
+        );
+      }
+      let _ = write!(
+        components,
+        r#"</div><div class="row muted"><span>{} 天前</span><span>可用率 {}</span><span>今天</span></div></div>"#,
+        app.days.len(),
+        uptime_text(app.uptime),
+      );
+    }
+
+    components.push_str("</section>");
+  }
+
+  let mut incidents = String::new();
+  for incident in &page.incidents {
+    let component = incident
+      .component_id
+      .and_then(|id| page.components.iter().find(|component| component.id == id))
+      .map(|component| format!(" · {}", escape(&component.name)))
+      .unwrap_or_default();
+    let resolved = incident
+      .resolved_at
+      .map(|resolved_at| format!(" ~ {}", time_text(resolved_at)))
+      .unwrap_or_default();
+    let _ = write!(
+      incidents,
+      r#"<article class="incident"><h3>{} <small>{}{}</small></h3><p>{}</p><p class="muted">{}{}</p></article>"#,
+      escape(&incident.title),
+      incident_status_text(incident.status),
+      component,
+      escape(&incident.content).replace('\n', "<br>"),
+      time_text(incident.started_at),
+      resolved,
+    );
+  }
+  if incidents.is_empty() {
+    incidents.push_str(r#"<p class="muted">最近没有事件</p>"#);
+  }
+
+  format!(
+    r#"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<style>
+body {{ margin: 0; background: #f5f6f8; color: #1f2329; font: 14px/1.6 -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; }}
+main {{ max-width: 860px; margin: 0 auto; padding: 32px 16px; }}
+h1 {{ font-size: 24px; }}
+h2 {{ font-size: 16px; margin: 0; }}
+h3 {{ font-size: 15px; margin: 0 0 4px; }}
+section, article, .overall {{ background: #fff; border-radius: 8px; padding: 16px 20px; margin-bottom: 16px; }}
+header, .row {{ display: flex; justify-content: space-between; align-items: center; }}
+.overall {{ color: #fff; font-size: 16px; }}
+.overall.up {{ background: #2ba471; }}
+.overall.down {{ background: #d54941; }}
+.overall.maintenance {{ background: #0052d9; }}
+.overall.none {{ background: #8b8f97; }}
+.banner {{ background: #f2f3ff; border-left: 4px solid #0052d9; border-radius: 8px; padding: 12px 16px; margin-bottom: 16px; }}
+.banner p {{ margin: 4px 0 0; }}
+.status.up {{ color: #2ba471; }}
+.status.down {{ color: #d54941; }}
+.status.maintenance {{ color: #0052d9; }}
+.status.none, .muted {{ color: #8b8f97; }}
+.muted {{ font-size: 12px; margin: 4px 0 0; }}
+.app {{ margin-top: 16px; }}
+.bars {{ display: flex; gap: 2px; height: 32px; margin: 6px 0; }}
+.bars i {{ flex: 1; border-radius: 2px; background: #dcdfe4; }}
+.bars i.up {{ background: #2ba471; }}
+.bars i.degraded {{ background: #e37318; }}
+.bars i.down {{ background: #d54941; }}
+.bars i.maintenance {{ background: #0052d9; }}
+small {{ color: #8b8f97; font-weight: normal; }}
+footer {{ text-align: center; }}
+</style>
+</head>
+<body>
+<main>
+<h1>{title}</h1>
+<div class="overall {overall_class}">{overall_text}</div>
+{maintenances}
+{components}
+<h2>事件</h2>
+{incidents}
+<footer class="muted">更新于 {generated_at}</footer>
+</main>
+</body>
+</html>"#,
+    title = escape(&page.title),
+    overall_class = overall_class,
+    overall_text = overall_text,
+    components = components,
+    incidents = incidents,
+    maintenances = maintenances,
+    generated_at = time_text(page.generated_at),
+  )
+}
+
+// 公开状态页，无需登录即可访问
+pub async fn page(db: web::Data<DbConn>) -> Result<impl Responder> {
+  let status_page = get_status_page(&db).await?;
+
+  Ok(
+    HttpResponse::Ok()
+      .content_type(header::ContentType::html())
+      .body(render(&status_page)),
+  )
+}
+
+pub async fn data(db: web::Data<DbConn>) -> Result<impl Responder> {
+  let status_page = get_status_page(&db).await?;
+
+  Ok(HttpResponse::Ok().json(&*status_page))
+}
@@ -1,6 +1,6 @@
 use argon2::{Config, Result, Variant, Version, hash_encoded, verify_encoded};
 use lazy_static::lazy_static;
-use rand::{TryRngCore, rngs::OsRng};
+use rand::{TryRngCore, rand_core::OsError, rngs::OsRng};
 
 lazy_static! {
   static ref ARGON2_HASH_CONFIG: Config<'static> = Config {
@@ -24,6 +24,14 @@ pub fn hash(password: &String) -> Result<String> {
   hash_encoded(password.as_bytes(), &bytes, &ARGON2_HASH_CONFIG)
 }
 
-pub fn verify(hash: &String, password: &String) -> Result<bool> {
+pub fn verify(hash: &str, password: &String) -> Result<bool> {
   verify_encoded(hash, password.as_bytes())
 }
+
+// 生成随机令牌，以十六进制字符串表示
+pub fn random_token(len: usize) -> std::result::Result<String, OsError> {
+  let mut bytes = vec![0u8; len];
+  OsRng.try_fill_bytes(&mut bytes)?;
+
+  Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
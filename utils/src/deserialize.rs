@@ -8,3 +8,53 @@ where
     .parse::<i64>()
     .map_err(Error::custom)
 }
+
+pub fn option_str_to_i64<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  Option::<String>::deserialize(deserializer)?
+    .map(|val| val.parse::<i64>().map_err(Error::custom))
+    .transpose()
+}
+
+pub fn option_vec_str_to_i64<'de, D>(deserializer: D) -> Result<Option<Vec<i64>>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  Option::<Vec<String>>::deserialize(deserializer)?
+    .map(|vals| {
+      vals
+        .iter()
+        .map(|val| val.parse::<i64>().map_err(Error::custom))
+        .collect()
+    })
+    .transpose()
+}
+
+pub fn vec_str_to_i64<'de, D>(deserializer: D) -> Result<Vec<i64>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  Vec::<String>::deserialize(deserializer)?
+    .iter()
+    .map(|val| val.parse::<i64>().map_err(Error::custom))
+    .collect()
+}
+
+// 配合 #[serde(default)] 使用，区分字段缺失（None）与字段为 null（Some(None)）
+pub fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+  T: Deserialize<'de>,
+  D: Deserializer<'de>,
+{
+  Option::<T>::deserialize(deserializer).map(Some)
+}
+
+// 与 double_option 相同，缺失时为 None，为 null 时为 Some(None)
+pub fn double_option_str_to_i64<'de, D>(deserializer: D) -> Result<Option<Option<i64>>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  option_str_to_i64(deserializer).map(Some)
+}
@@ -0,0 +1,9 @@
+// 转义 HTML 与 SVG 中的特殊字符
+pub fn escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&#39;")
+}
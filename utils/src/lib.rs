@@ -1,3 +1,5 @@
 pub mod crypto;
+pub mod html;
 pub mod serialize;
+pub mod yaml;
 pub mod deserialize;
@@ -4,5 +4,22 @@ pub fn i64_to_str<S>(val: &i64, serializer: S) -> Result<S::Ok, S::Error>
 where
   S: Serializer,
 {
-  return serializer.serialize_str(&val.to_string());
+  serializer.serialize_str(&val.to_string())
+}
+
+pub fn option_i64_to_str<S>(val: &Option<i64>, serializer: S) -> Result<S::Ok, S::Error>
+where
+  S: Serializer,
+{
+  match val {
+    Some(val) => serializer.serialize_some(&val.to_string()),
+    None => serializer.serialize_none(),
+  }
+}
+
+pub fn vec_i64_to_str<S>(vals: &[i64], serializer: S) -> Result<S::Ok, S::Error>
+where
+  S: Serializer,
+{
+  serializer.collect_seq(vals.iter().map(|val| val.to_string()))
 }
@@ -0,0 +1,117 @@
+use serde_json::{Map, Number, Value};
+use yaml_rust2::{EmitError, ScanError, Yaml, YamlEmitter, YamlLoader, yaml::Hash};
+
+fn yaml_to_json(yaml: Yaml) -> Value {
+  match yaml {
+    Yaml::Real(real) => real
+      .parse::<f64>()
+      .ok()
+      .and_then(Number::from_f64)
+      .map(Value::Number)
+      .unwrap_or(Value::String(real)),
+    Yaml::Integer(integer) => Value::Number(integer.into()),
+    Yaml::String(string) => Value::String(string),
+    Yaml::Boolean(boolean) => Value::Bool(boolean),
+    Yaml::Array(array) => Value::Array(array.into_iter().map(yaml_to_json).collect()),
+    Yaml::Hash(hash) => Value::Object(
+      hash
+        .into_iter()
+        .filter_map(|(key, value)| {
+          let key = match key {
+            Yaml::String(key) | Yaml::Real(key) => key,
+            Yaml::Integer(key) => key.to_string(),
+            Yaml::Boolean(key) => key.to_string(),
+            _ => return None,
+          };
+          Some((key, yaml_to_json(value)))
+        })
+        .collect::<Map<String, Value>>(),
+    ),
+    Yaml::Alias(_) | Yaml::Null | Yaml::BadValue => Value::Null,
+  }
+}
+
+fn json_to_yaml(value: &Value) -> Yaml {
+  match value {
+    Value::Null => Yaml::Null,
+    Value::Bool(boolean) => Yaml::Boolean(*boolean),
+    Value::Number(number) => match number.as_i64() {
+      Some(integer) => Yaml::Integer(integer),
+      None => Yaml::Real(number.to_string()),
+    },
+    Value::String(string) => Yaml::String(string.clone()),
+    Value::Array(array) => Yaml::Array(array.iter().map(json_to_yaml).collect()),
+    Value::Object(object) => {
+      let mut hash = Hash::new();
+      for (key, value) in object {
+        hash.insert(Yaml::String(key.clone()), json_to_yaml(value));
+      }
+      Yaml::Hash(hash)
+    }
+  }
+}
+
+// 将 YAML 文档转换为 JSON，便于使用 serde 解析，内容为空时返回 Null
+pub fn from_str(source: &str) -> Result<Value, ScanError> {
+  Ok(
+    YamlLoader::load_from_str(source)?
+      .into_iter()
+      .next()
+      .map(yaml_to_json)
+      .unwrap_or(Value::Null),
+  )
+}
+
+pub fn to_string(value: &Value) -> Result<String, EmitError> {
+  let mut output = String::new();
+  let mut emitter = YamlEmitter::new(&mut output);
+  emitter.multiline_strings(true);
+  emitter.dump(&json_to_yaml(value))?;
+  output.push('\n');
+
+  Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn round_trip_keeps_value() {
+    let value = json!({
+      "version": 1,
+      "exported_at": "2026-10-19T08:00:00",
+      "ratio": 0.75,
+      "negative": -42,
+      "enabled": true,
+      "empty": null,
+      "apps": [
+        {
+          "name": "首页 🏠",
+          "url": "https://example.com/path?a=1&b=2",
+          "description": "第一行\n第二行\n",
+          "tags": ["ops", "media"],
+          "links": [],
+        },
+      ],
+      "groups": {},
+      // 看起来像其他类型的字符串需要保持为字符串
+      "quoted": ["true", "null", "123", "1.5", "", " padded ", "key: value", "- item", "#comment"],
+    });
+
+    let output = to_string(&value).unwrap();
+    assert_eq!(from_str(&output).unwrap(), value);
+  }
+
+  #[test]
+  fn empty_document_is_null() {
+    assert_eq!(from_str("").unwrap(), Value::Null);
+    assert_eq!(from_str("# comment only\n").unwrap(), Value::Null);
+  }
+
+  #[test]
+  fn invalid_document_is_error() {
+    assert!(from_str("apps: [unclosed").is_err());
+  }
+}
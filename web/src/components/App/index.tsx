@@ -47,7 +47,7 @@ export default function App({ app, className, clickable = true }: IAppProps) {
       <a
         className={cls(styles.app, className)}
         style={style}
-        href={app.url}
+        href={app.open_url ?? app.url}
         title={app.name}
         target="_blank"
         rel="noreferrer"
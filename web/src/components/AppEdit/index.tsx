@@ -16,7 +16,8 @@ import {
 import fetcher from '@/utils/fetcher';
 import useMessage from '@/hooks/useMessage';
 
-interface IAppModel extends Pick<IApp, 'url' | 'name' | 'description'> {
+interface IAppModel
+  extends Pick<IApp, 'url' | 'internal_url' | 'name' | 'description'> {
   icon?: IUploadFile[];
 }
 
@@ -101,6 +102,8 @@ export default function AppEdit({
     const appModel = form.getFieldsValue();
     onOk({
       url: appModel.url,
+      // 清空输入框时删除内网 URL
+      internal_url: appModel.internal_url || null,
       name: appModel.name,
       description: appModel.description,
       icon: uploadFileToUri(appModel.icon?.[0]),
@@ -115,6 +118,7 @@ export default function AppEdit({
     if (app) {
       form.setFieldsValue({
         url: app.url,
+        internal_url: app.internal_url,
         name: app.name,
         description: app.description,
         icon: app.icon ? [uriToUploadFile(app.icon)] : [],
@@ -184,6 +188,25 @@ export default function AppEdit({
             </Button>
           </Space.Compact>
         </Form.Item>
+        <Form.Item
+          label="内网 URL"
+          name="internal_url"
+          validateFirst
+          tooltip="客户端位于内网时使用该地址打开应用"
+          rules={[
+            {
+              type: 'url',
+              message: '请输入合法的内网 URL',
+            },
+            {
+              type: 'string',
+              max: 255,
+              message: '内网 URL 长度不得超过 255 个字符',
+            },
+          ]}
+        >
+          <Input showCount maxLength={255} />
+        </Form.Item>
         <Form.Item
           label="应用名称"
           name="name"
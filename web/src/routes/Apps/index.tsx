@@ -25,7 +25,7 @@ import EditableApp from './components/EditableApp';
 import styles from './index.module.less';
 
 type ICreateAppData = IAppEditData;
-type IUpdateAppData = IAppEditData & { id: string };
+type IUpdateAppData = IAppEditData & { id: string; group_id?: string | null };
 
 export default function Apps() {
   const {
@@ -52,7 +52,7 @@ export default function Apps() {
     async (url, { arg }: { arg: IApp[] }) => {
       await fetcher.put(
         'app/sort',
-        arg.map((item) => ({ id: item.id })),
+        arg.map((item) => ({ id: item.id, group_id: item.group_id })),
       );
       return arg;
     },
@@ -138,6 +138,7 @@ export default function Apps() {
           await updateApp({
             ...data,
             id: currentApp.id,
+            group_id: currentApp.group_id,
           });
           message.success('编辑成功');
         } else {
@@ -1,11 +1,118 @@
+export interface ITag {
+  id: string;
+  name: string;
+  color?: string;
+  owner_id?: string;
+  created_at: string;
+  deleted_at: string | null;
+}
+
+export type IMonitorKind = 'Http' | 'Tcp' | 'Dns' | 'Push';
+
+export interface IMonitor {
+  kind: IMonitorKind;
+  method?: string | null;
+  headers: { name: string; value: string }[];
+  keyword?: string | null;
+  keyword_regex: boolean;
+  keyword_absent: boolean;
+  json_path?: string | null;
+  expected?: string | null;
+  host?: string | null;
+  port?: number | null;
+  push_interval?: number | null;
+}
+
+export interface ICert {
+  issuer?: string | null;
+  subject?: string | null;
+  sans: string[];
+  not_before?: string | null;
+  expires_at?: string | null;
+  hostname_match: boolean;
+  error?: string | null;
+  checked_at: string;
+  expired: boolean;
+  expiring: boolean;
+}
+
 export interface IApp {
   id: string;
   name: string;
   url: string;
+  internal_url?: string | null;
+  open_url?: string;
   description?: string;
   icon?: string;
   index: number;
   owner_id?: string;
+  group_id?: string | null;
+  dashboard_id?: string | null;
+  is_global?: boolean;
+  monitor?: IMonitor | null;
+  tags?: ITag[];
+  cert?: ICert | null;
+  created_at: string;
+  deleted_at: string | null;
+}
+
+export interface IGroup {
+  id: string;
+  name: string;
+  icon?: string;
+  collapsed: boolean;
+  index: number;
+  owner_id?: string;
   created_at: string;
   deleted_at: string | null;
 }
+
+export interface IGroupApps extends IGroup {
+  apps: IApp[];
+}
+
+export enum ISharePermission {
+  Read = 'Read',
+  Edit = 'Edit',
+}
+
+export interface IShare {
+  id: string;
+  owner_id: string;
+  grantee_id: string;
+  group_id: string | null;
+  permission: ISharePermission;
+  created_at: string;
+  deleted_at: string | null;
+}
+
+export interface ISharedApps extends IShare {
+  owner_name: string;
+  groups: IGroupApps[];
+  apps: IApp[];
+}
+
+export interface IDashboard {
+  id: string;
+  name: string;
+  index: number;
+  is_default: boolean;
+  layout: {
+    columns?: number;
+    compact?: boolean;
+  } | null;
+  background: {
+    bg_image?: string;
+    bg_blur?: number;
+  } | null;
+  owner_id?: string;
+  created_at: string;
+  deleted_at: string | null;
+}
+
+export interface IUserApps {
+  dashboard: IDashboard;
+  groups: IGroupApps[];
+  apps: IApp[];
+  shared: ISharedApps[];
+}